@@ -235,7 +235,7 @@ pub(crate) type DiscreteTabModel =
 
 See [`DblTheoryBox`] for motivation.
  */
-#[derive(From, TryInto)]
+#[derive(Clone, From, TryInto)]
 #[try_into(ref)]
 pub enum DblModelBox {
     Discrete(DiscreteDblModel),
@@ -243,6 +243,7 @@ pub enum DblModelBox {
 }
 
 /// Wasm bindings for a model of a double theory.
+#[derive(Clone)]
 #[wasm_bindgen]
 pub struct DblModel(#[wasm_bindgen(skip)] pub DblModelBox);
 
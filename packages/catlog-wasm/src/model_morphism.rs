@@ -1,8 +1,10 @@
+use std::collections::HashMap;
 use std::hash::Hash;
 
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 use uuid::Uuid;
+use wasm_bindgen::prelude::*;
 
 use catlog::dbl::{model, model_morphism};
 use catlog::one::{FgCategory, fin_category::UstrFinCategory};
@@ -17,6 +19,36 @@ pub(crate) type DiscreteDblModelMapping = model_morphism::DiscreteDblModelMappin
 pub struct MotifsOptions {
     #[serde(rename = "maxPathLength")]
     max_path_len: Option<usize>,
+
+    /// Maximum number of distinct motifs to search for.
+    #[serde(rename = "maxResults")]
+    max_results: Option<usize>,
+
+    /// Time limit on the search, in milliseconds.
+    #[serde(rename = "timeLimit")]
+    time_limit: Option<f64>,
+}
+
+/// Motifs found in a model, possibly only some of them.
+#[wasm_bindgen]
+pub struct Motifs {
+    models: Vec<DblModel>,
+    truncated: bool,
+}
+
+#[wasm_bindgen]
+impl Motifs {
+    /// Submodels that are images of the motif, ordered from small to large.
+    #[wasm_bindgen(getter)]
+    pub fn models(&self) -> Vec<DblModel> {
+        self.models.clone()
+    }
+
+    /// Whether the search was stopped before all motifs were found.
+    #[wasm_bindgen(getter)]
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
 }
 
 /// Find motifs in a model of a discrete double theory.
@@ -24,7 +56,7 @@ pub fn motifs<Id>(
     motif: &model::DiscreteDblModel<Id, UstrFinCategory>,
     model: &DblModel,
     options: MotifsOptions,
-) -> Result<Vec<DblModel>, String>
+) -> Result<Motifs, String>
 where
     Id: Clone + Eq + Hash,
 {
@@ -36,36 +68,38 @@ where
     if let Some(n) = options.max_path_len {
         finder.max_path_len(n);
     }
-    let mut images: Vec<_> = finder
-        .monic()
-        .find_all()
-        .into_iter()
-        .map(|mapping| mapping.syntactic_image(model))
-        .collect();
-
-    // Order motifs from small to large.
-    images.sort_by_key(|im| (im.ob_generators().count(), im.mor_generators().count()));
-
-    // Remove duplicates: different morphisms can have the same image.
-    retain_unique(&mut images);
-
-    Ok(images.into_iter().map(|im| DblModel(im.into())).collect())
-}
-
-/** Remove duplicate elements from a vector.
+    if let Some(time_limit) = options.time_limit {
+        let deadline = js_sys::Date::now() + time_limit;
+        finder.cancel_when(move || js_sys::Date::now() > deadline);
+    }
 
-This is the naive quadratic algorithm that only uses equality tests.
- */
-fn retain_unique<T>(vec: &mut Vec<T>)
-where
-    T: Eq,
-{
-    let mut i = 0;
-    while i < vec.len() {
-        if (0..i).any(|j| vec[j] == vec[i]) {
-            vec.remove(i);
-        } else {
-            i += 1;
+    // Remove duplicates as the morphisms are found, since different morphisms
+    // can have the same image. Equal images have equal canonical forms, so only
+    // images with the same canonical form need be compared. The limit on the
+    // number of results applies to the distinct images, so the search stops
+    // once one more than the limit has been found.
+    let mut buckets: HashMap<_, Vec<usize>> = HashMap::new();
+    let mut unique: Vec<model::DiscreteDblModel<Uuid, _>> = Vec::new();
+    let mut truncated = false;
+    for mapping in finder.monic() {
+        let im = mapping.syntactic_image(model);
+        let bucket = buckets.entry(im.canonical_form()).or_default();
+        if bucket.iter().all(|i| unique[*i] != im) {
+            if options.max_results.is_some_and(|n| unique.len() >= n) {
+                truncated = true;
+                break;
+            }
+            bucket.push(unique.len());
+            unique.push(im);
         }
     }
+    let truncated = truncated || finder.is_truncated();
+
+    // Order motifs from small to large.
+    unique.sort_by_key(|im| (im.ob_generators().count(), im.mor_generators().count()));
+
+    Ok(Motifs {
+        models: unique.into_iter().map(|im| DblModel(im.into())).collect(),
+        truncated,
+    })
 }
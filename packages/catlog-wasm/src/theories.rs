@@ -13,7 +13,7 @@ use catlog::dbl::{model, theory};
 use catlog::one::fin_category::FinMor;
 use catlog::stdlib::{analyses, models, theories};
 
-use super::model_morphism::{MotifsOptions, motifs};
+use super::model_morphism::{Motifs, MotifsOptions, motifs};
 use super::{analyses::*, model::DblModel, theory::DblTheory};
 
 /// The empty or initial theory.
@@ -29,7 +29,7 @@ impl ThEmpty {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 }
 
@@ -46,7 +46,7 @@ impl ThCategory {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 }
 
@@ -63,7 +63,7 @@ impl ThSchema {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 }
 
@@ -80,7 +80,7 @@ impl ThSignedCategory {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 
     /// Find positive feedback loops in a model.
@@ -89,7 +89,7 @@ impl ThSignedCategory {
         &self,
         model: &DblModel,
         options: MotifsOptions,
-    ) -> Result<Vec<DblModel>, String> {
+    ) -> Result<Motifs, String> {
         let positive_loop = models::positive_loop(self.0.clone());
         motifs(&positive_loop, model, options)
     }
@@ -100,7 +100,7 @@ impl ThSignedCategory {
         &self,
         model: &DblModel,
         options: MotifsOptions,
-    ) -> Result<Vec<DblModel>, String> {
+    ) -> Result<Motifs, String> {
         let negative_loop = models::negative_loop(self.0.clone());
         motifs(&negative_loop, model, options)
     }
@@ -140,7 +140,7 @@ impl ThDelayableSignedCategory {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 
     /// Find (fast) positive feedback loops in a model.
@@ -149,7 +149,7 @@ impl ThDelayableSignedCategory {
         &self,
         model: &DblModel,
         options: MotifsOptions,
-    ) -> Result<Vec<DblModel>, String> {
+    ) -> Result<Motifs, String> {
         let positive_loop = models::positive_loop(self.0.clone());
         motifs(&positive_loop, model, options)
     }
@@ -160,7 +160,7 @@ impl ThDelayableSignedCategory {
         &self,
         model: &DblModel,
         options: MotifsOptions,
-    ) -> Result<Vec<DblModel>, String> {
+    ) -> Result<Motifs, String> {
         let negative_loop = models::negative_loop(self.0.clone());
         motifs(&negative_loop, model, options)
     }
@@ -171,7 +171,7 @@ impl ThDelayableSignedCategory {
         &self,
         model: &DblModel,
         options: MotifsOptions,
-    ) -> Result<Vec<DblModel>, String> {
+    ) -> Result<Motifs, String> {
         let delayed_positive_loop = models::delayed_positive_loop(self.0.clone());
         motifs(&delayed_positive_loop, model, options)
     }
@@ -182,7 +182,7 @@ impl ThDelayableSignedCategory {
         &self,
         model: &DblModel,
         options: MotifsOptions,
-    ) -> Result<Vec<DblModel>, String> {
+    ) -> Result<Motifs, String> {
         let delayed_negative_loop = models::delayed_negative_loop(self.0.clone());
         motifs(&delayed_negative_loop, model, options)
     }
@@ -201,7 +201,7 @@ impl ThNullableSignedCategory {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 }
 
@@ -218,7 +218,7 @@ impl ThCategoryWithScalars {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 }
 
@@ -235,7 +235,7 @@ impl ThCategoryLinks {
 
     #[wasm_bindgen]
     pub fn theory(&self) -> DblTheory {
-        DblTheory(self.0.clone().into())
+        DblTheory::new(self.0.clone().into())
     }
 
     /// Simulates the mass-action system derived from a model.
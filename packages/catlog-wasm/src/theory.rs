@@ -12,8 +12,8 @@ use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
 use wasm_bindgen::prelude::*;
 
-use catlog::dbl::theory;
 use catlog::dbl::theory::{DblTheory as _, TabMorType, TabObType};
+use catlog::dbl::{theory, theory_definition};
 use catlog::one::fin_category::*;
 
 /// Object type in a double theory.
@@ -138,13 +138,65 @@ pub enum DblTheoryBox {
     DiscreteTab(Arc<theory::UstrDiscreteTabTheory>),
 }
 
+/** Declarative definition of a double theory of any kind.
+
+Wraps the definition from `catlog` so that it can cross the Wasm boundary.
+ */
+#[derive(Clone, Serialize, Deserialize, Tsify)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub struct DblTheoryDefinition(pub theory_definition::DblTheoryDefinition<Ustr>);
+
 /** Wasm bindings for a double theory.
  */
 #[wasm_bindgen]
-pub struct DblTheory(#[wasm_bindgen(skip)] pub DblTheoryBox);
+pub struct DblTheory(
+    #[wasm_bindgen(skip)] pub DblTheoryBox,
+    #[wasm_bindgen(skip)] pub Option<Arc<theory_definition::DblTheoryDefinition<Ustr>>>,
+);
+
+impl DblTheory {
+    /// Wraps a double theory that has no declarative definition.
+    pub fn new(theory: DblTheoryBox) -> Self {
+        Self(theory, None)
+    }
+}
 
 #[wasm_bindgen]
 impl DblTheory {
+    /// Builds a double theory from a declarative definition.
+    #[wasm_bindgen(js_name = "fromDefinition")]
+    pub fn from_definition(def: DblTheoryDefinition) -> Result<DblTheory, String> {
+        let result: Result<DblTheoryBox, _> = match &def.0 {
+            theory_definition::DblTheoryDefinition::Discrete(def) => {
+                def.build().map(|th: theory::UstrDiscreteDblTheory| Arc::new(th).into())
+            }
+            theory_definition::DblTheoryDefinition::DiscreteTab(def) => {
+                def.build().map(|th: theory::UstrDiscreteTabTheory| Arc::new(th).into())
+            }
+        };
+        result.map(|th| DblTheory(th, Some(Arc::new(def.0)))).map_err(|errors| {
+            errors.into_iter().map(|err| err.to_string()).collect::<Vec<_>>().join("\n")
+        })
+    }
+
+    /// Label of a basic object type, if given in the theory's definition.
+    #[wasm_bindgen(js_name = "obTypeLabel")]
+    pub fn ob_type_label(&self, ob_type: ObType) -> Option<String> {
+        match (&self.1, ob_type) {
+            (Some(def), ObType::Basic(id)) => def.ob_type_label(&id).map(String::from),
+            _ => None,
+        }
+    }
+
+    /// Label of a basic morphism type, if given in the theory's definition.
+    #[wasm_bindgen(js_name = "morTypeLabel")]
+    pub fn mor_type_label(&self, mor_type: MorType) -> Option<String> {
+        match (&self.1, mor_type) {
+            (Some(def), MorType::Basic(id)) => def.mor_type_label(&id).map(String::from),
+            _ => None,
+        }
+    }
+
     /// Kind of double theory ("double doctrine").
     #[wasm_bindgen(getter)]
     pub fn kind(&self) -> String {
@@ -58,8 +58,6 @@ use crate::one::path::Path;
 /** A virtual double category (VDC).
 
 See the [module-level docs](super::category) for background on VDCs.
-
-TODO: The universal property of a composite is not part of the interface.
  */
 pub trait VDblCategory {
     /// Type of objects in the VDC.
@@ -205,6 +203,35 @@ pub trait VDblCategory {
     fn unit(&self, x: Self::Ob) -> Option<Self::Pro> {
         self.unit_ext(x).map(|α| self.cell_cod(&α))
     }
+
+    /** Factors a cell through the chosen composite of part of its domain.
+
+    The part of the domain is given by a range of positions in the path of
+    proarrows, where an empty range `i..i` refers to the empty path at an
+    object. By the universal property of the chosen
+    [extension](Self::composite_ext), there is a unique cell whose domain has
+    the part replaced by its composite and whose composite with the extension
+    cell is the given cell. That cell is returned if the part has a chosen
+    composite.
+
+    The default implementation handles unary composites, whose extension cells
+    are identities.
+     */
+    fn through_composite(
+        &self,
+        cell: Self::Cell,
+        range: std::ops::Range<usize>,
+    ) -> Option<Self::Cell> {
+        (range.len() == 1 && range.end <= self.arity(&cell)).then_some(cell)
+    }
+
+    /** Factors a cell through the chosen unit at a position in its domain.
+
+    This is the nullary case of [`through_composite`](Self::through_composite).
+     */
+    fn through_unit(&self, cell: Self::Cell, i: usize) -> Option<Self::Cell> {
+        self.through_composite(cell, i..i)
+    }
 }
 
 /// The underlying [virtual double graph](VDblGraph) of a VDC.
@@ -268,6 +295,9 @@ how a graph [freely generates](crate::one::category::FreeCategory) a category.
 This is not, however, the most general way to freely generate a VDC. A "virtual
 double computad" freely generates a VDC but allows the generating cells to have
 sources and targets that are *paths* of generating arrows.
+
+The only composites in a free VDC are the unary ones, so factoring a cell
+through a composite is trivial when possible at all.
  */
 #[derive(From, RefCast)]
 #[repr(transparent)]
@@ -384,6 +414,13 @@ impl VDblCategory for WalkingCategory {
     fn composite_ext(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Cell> {
         Some(path.len())
     }
+    fn through_composite(
+        &self,
+        n: Self::Cell,
+        range: std::ops::Range<usize>,
+    ) -> Option<Self::Cell> {
+        (range.start <= range.end && range.end <= n).then(|| n - range.len() + 1)
+    }
 }
 
 #[allow(non_snake_case)]
@@ -506,6 +543,14 @@ pub mod WalkingBimodule {
         fn composite_ext(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Cell> {
             Some(path)
         }
+        fn through_composite(
+            &self,
+            path: Self::Cell,
+            range: std::ops::Range<usize>,
+        ) -> Option<Self::Cell> {
+            let graph = ProedgeGraph::ref_cast(UnderlyingDblGraph::ref_cast(self));
+            path.splice_in(graph, range, |sub| Some(self.cell_cod(&sub)))
+        }
     }
 }
 
@@ -679,6 +724,15 @@ pub mod WalkingFunctor {
             assert_eq!(x, y, "Paths in walking functor have the same source and target");
             Some(Cell::with_src_and_tgt(self.id(x), path.len()))
         }
+        fn through_composite(
+            &self,
+            cell: Self::Cell,
+            range: std::ops::Range<usize>,
+        ) -> Option<Self::Cell> {
+            let n = self.arity(&cell);
+            (range.start <= range.end && range.end <= n)
+                .then(|| Cell::with_src_and_tgt(cell.src(), n - range.len() + 1))
+        }
     }
 }
 
@@ -732,5 +786,39 @@ mod tests {
         assert_eq!(vdc.cell_tgt(&ext), Arr::Zero);
         let new_cell = vdc.compose_cells2(vec![ext, ext], cell);
         assert_eq!(vdc.cell_dom(&new_cell).len(), 4);
+        assert_eq!(vdc.through_composite(new_cell, 1..3), Some(Cell::Arrow(3)));
+        assert_eq!(vdc.through_unit(cell, 2), Some(Cell::Arrow(3)));
+        assert_eq!(vdc.through_composite(cell, 1..3), None);
+    }
+
+    #[test]
+    fn factor_through_composites() {
+        let vdc = WalkingCategory();
+        assert_eq!(vdc.through_composite(3, 0..2), Some(2));
+        assert_eq!(vdc.through_unit(0, 0), Some(1));
+        assert_eq!(vdc.through_composite(1, 0..2), None);
+
+        use WalkingBimodule::{Ob, Pro};
+        let vdc = WalkingBimodule::Main();
+        let path = Path::from_vec(vec![Pro::Left, Pro::Middle, Pro::Right]).unwrap();
+        let cell = vdc.composite_ext(path.clone()).unwrap();
+        let factored = vdc.through_composite(cell.clone(), 0..2).unwrap();
+        assert_eq!(vdc.cell_dom(&factored), Path::pair(Pro::Middle, Pro::Right));
+        let ext = vdc.composite2_ext(Pro::Left, Pro::Middle).unwrap();
+        let cell_alt = vdc.compose_cells2([ext, vdc.id_cell(Pro::Right)], factored);
+        assert_eq!(cell_alt, cell);
+        let factored = vdc.through_unit(cell, 3).unwrap();
+        assert_eq!(vdc.cell_dom(&factored).iter().last(), Some(&Pro::Right));
+        assert_eq!(vdc.arity(&factored), 4);
+
+        let free = FreeVDblCategory(UnderlyingDblGraph(WalkingBimodule::Main()));
+        let mid = DblTree::single(vdc.composite_ext(path).unwrap());
+        let cell = free
+            .compose_cells2([free.id_cell(Pro::Left), mid.clone(), free.id_cell(Pro::Right)], mid);
+        assert_eq!(free.arity(&cell), 5);
+        assert_eq!(free.through_composite(cell.clone(), 2..3), Some(cell.clone()));
+        assert_eq!(free.through_composite(cell.clone(), 1..3), None);
+        assert_eq!(free.through_unit(cell, 0), None);
+        assert!(free.unit(Ob::Left).is_none());
     }
 }
@@ -8,4 +8,8 @@ pub mod tree_algorithms;
 pub mod model;
 pub mod model_diagram;
 pub mod model_morphism;
+pub mod model_pushout;
+pub mod open_model;
 pub mod theory;
+pub mod theory_definition;
+pub mod theory_morphism;
@@ -35,9 +35,10 @@ In addition, a model has the following operations:
 
 use std::hash::{BuildHasher, BuildHasherDefault, Hash, RandomState};
 use std::iter::Iterator;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 use derivative::Derivative;
+use ref_cast::RefCast;
 use ustr::{IdentityHasher, Ustr};
 
 #[cfg(feature = "serde")]
@@ -47,7 +48,11 @@ use tsify_next::Tsify;
 
 use super::category::VDblCategory;
 use super::theory::{DblTheory, DiscreteDblTheory};
-use crate::one::fin_category::{FpCategory, InvalidFpCategory, UstrFinCategory};
+use crate::one::canonical::{CanonicalForm, canonical_form};
+use crate::one::fin_category::{
+    FinMor, FpCategory, InvalidFpCategory, MAX_COMPLETION_RULES, UstrFinCategory,
+};
+use crate::one::rewriting::{IndexedRewriteSystem, RewriteSystem};
 use crate::one::*;
 use crate::validate::{self, Validate};
 use crate::zero::*;
@@ -247,6 +252,11 @@ where
         self.category.add_equation(key, eq);
     }
 
+    /// Iterates over the equations in the model, along with their keys.
+    pub fn equations(&self) -> impl Iterator<Item = (Id, &PathEq<Id, Id>)> {
+        self.category.keyed_equations()
+    }
+
     /// Iterates over failures of model to be well defined.
     pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidDblModel<Id>> + '_ {
         type Invalid<Id> = InvalidDblModel<Id>;
@@ -309,6 +319,51 @@ where
     }
 }
 
+impl<Id, Cat> DiscreteDblModel<Id, Cat>
+where
+    Id: Eq + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Ord + Hash,
+    Cat::Mor: Ord + Hash,
+{
+    /** Computes the canonical form of the model's generating graph.
+
+    Two models have the same canonical form just when their generating graphs
+    are isomorphic by an isomorphism preserving object and morphism types.
+    Equations in the model are not taken into account, so for free models the
+    canonical form determines the model up to renaming of its generators.
+     */
+    pub fn canonical_form(&self) -> CanonicalForm<Cat::Ob, Cat::Mor> {
+        canonical_form(
+            self.generating_graph(),
+            |x| self.ob_generator_type(x),
+            |m| self.mor_generator_type(m),
+        )
+    }
+
+    /// Is the model isomorphic to another, up to renaming generators?
+    pub fn is_isomorphic_to(&self, other: &Self) -> bool {
+        self.canonical_form() == other.canonical_form()
+    }
+}
+
+impl<Id, Cat> DiscreteDblModel<Id, Cat>
+where
+    Id: Ord + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    /** Completes the equations of the model into a rewriting system.
+
+    Normal forms with respect to the system decide equality of morphisms in
+    the model. See [`FpCategory::complete`] for the meaning of `max_rules`.
+     */
+    pub fn complete(&self, max_rules: usize) -> Option<RewriteSystem<Id, Id>> {
+        self.category.complete(max_rules)
+    }
+}
+
 impl<Id, Cat> Category for DiscreteDblModel<Id, Cat>
 where
     Id: Eq + Clone + Hash,
@@ -363,6 +418,26 @@ where
     }
 }
 
+impl<Id, Cat> PresentedCategory for DiscreteDblModel<Id, Cat>
+where
+    Id: Eq + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    fn mor_path(&self, path: &Path<Id, Id>) -> Path<Id, Id> {
+        path.clone()
+    }
+
+    fn relations(&self) -> impl Iterator<Item = PathEq<Id, Id>> {
+        self.category.relations()
+    }
+
+    fn decide_eq(&self, path1: &Path<Id, Id>, path2: &Path<Id, Id>) -> Option<bool> {
+        self.category.decide_eq(path1, path2)
+    }
+}
+
 impl<Id, Cat> DblModel for DiscreteDblModel<Id, Cat>
 where
     Id: Eq + Clone + Hash,
@@ -515,7 +590,7 @@ pub enum InvalidDblModel<Id> {
 }
 
 /// Object in a model of a discrete tabulator theory.
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TabOb<V, E> {
     /// Basic or generating object.
     Basic(V),
@@ -562,7 +637,7 @@ impl<V, E> TabOb<V, E> {
 
 Morphisms of these two forms generate all the morphisms in the model.
  */
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum TabEdge<V, E> {
     /// Basic morphism between any two objects.
     Basic(E),
@@ -592,6 +667,9 @@ impl<V, E> From<E> for TabEdge<V, E> {
 /// Morphism in a model of a discrete tabulator theory.
 pub type TabMor<V, E> = Path<TabOb<V, E>, TabEdge<V, E>>;
 
+/// Equation between morphisms in a model of a discrete tabulator theory.
+pub type TabPathEq<V, E> = PathEq<TabOb<V, E>, TabEdge<V, E>>;
+
 impl<V, E> From<E> for TabMor<V, E> {
     fn from(value: E) -> Self {
         Path::single(value.into())
@@ -674,11 +752,20 @@ pub struct DiscreteTabModel<Id, ThId, S = RandomState> {
     #[derivative(PartialEq(compare_with = "Arc::ptr_eq"))]
     theory: Arc<DiscreteTabTheory<ThId, ThId, S>>,
     generators: DiscreteTabGenerators<Id, Id>,
-    // TODO: Equations
+    equations: HashColumn<Id, TabPathEq<Id, Id>>,
     ob_types: IndexedHashColumn<Id, TabObType<ThId, ThId>>,
     mor_types: IndexedHashColumn<Id, TabMorType<ThId, ThId>>,
+    #[derivative(PartialEq = "ignore")]
+    rewriting: OnceLock<Option<TabRewriteSystem<Id>>>,
 }
 
+/// Rewriting system on paths in a model of a discrete tabulator theory.
+type TabRewriteSystem<Id> = IndexedRewriteSystem<TabOb<Id, Id>, TabEdge<Id, Id>>;
+
+/// Bound on the number of times the equations of a tabulator model are
+/// completed while normalizing the morphisms nested in objects and edges.
+const MAX_COMPLETION_ROUNDS: usize = 10;
+
 /// A model of a discrete tabulator theory where both theory and model have keys
 /// of type `Ustr`.
 pub type UstrDiscreteTabModel = DiscreteTabModel<Ustr, Ustr, BuildHasherDefault<IdentityHasher>>;
@@ -694,11 +781,18 @@ where
         Self {
             theory,
             generators: Default::default(),
+            equations: Default::default(),
             ob_types: Default::default(),
             mor_types: Default::default(),
+            rewriting: Default::default(),
         }
     }
 
+    /// Returns a reference-counting pointer to the theory for this model.
+    pub fn theory_arc(&self) -> Arc<DiscreteTabTheory<ThId, ThId, S>> {
+        self.theory.clone()
+    }
+
     /// Convenience method to turn a morphism into an object.
     pub fn tabulated(&self, mor: TabMor<Id, Id>) -> TabOb<Id, Id> {
         TabOb::Tabulated(Box::new(mor))
@@ -709,6 +803,137 @@ where
         self.tabulated(Path::single(TabEdge::Basic(f)))
     }
 
+    /** Constructs the graph of generating morphisms of the model.
+
+    The vertices of the graph are the basic objects along with any tabulated
+    morphisms occurring as the domain or codomain of a generating morphism.
+     */
+    pub fn generating_graph(&self) -> HashGraph<TabOb<Id, Id>, Id> {
+        let mut graph: HashGraph<_, _> = Default::default();
+        graph.add_vertices(self.generators.objects.iter().map(TabOb::Basic));
+        for f in self.generators.morphisms.iter() {
+            let (x, y) = (self.mor_generator_dom(&f), self.mor_generator_cod(&f));
+            graph.add_vertex(x.clone());
+            graph.add_vertex(y.clone());
+            graph.add_edge(f, x, y);
+        }
+        graph
+    }
+
+    /// Projects a tabulated morphism onto its domain or codomain.
+    fn ob_proj(&self, ob: TabOb<Id, Id>, proj: &TabObProj<ThId, ThId>) -> TabOb<Id, Id> {
+        let m = ob.unwrap_tabulated();
+        match proj {
+            TabObProj::Src(_) => self.dom(&m),
+            TabObProj::Tgt(_) => self.cod(&m),
+        }
+    }
+
+    /** Projects a morphism between tabulated morphisms.
+
+    Such a morphism is a path of commutative squares, or an identity. The source
+    and target projections take the squares to their sides, while the cone
+    projection takes them to the diagonal of the composite square. A basic
+    morphism of this type is not a square and so has no projections, which is
+    why the model's validation rejects such generators.
+     */
+    fn mor_proj(&self, mor: TabMor<Id, Id>, proj: &TabMorProj<ThId, ThId>) -> TabMor<Id, Id> {
+        let squares = match mor {
+            Path::Id(ob) => {
+                let m = ob.unwrap_tabulated();
+                return match proj {
+                    TabMorProj::Cone(_) => m,
+                    TabMorProj::Src(_) => Path::Id(self.dom(&m)),
+                    TabMorProj::Tgt(_) => Path::Id(self.cod(&m)),
+                };
+            }
+            Path::Seq(edges) => edges.map(|edge| match edge {
+                TabEdge::Square {
+                    dom,
+                    cod,
+                    pre,
+                    post,
+                } => (dom, cod, *pre, *post),
+                TabEdge::Basic(_) => panic!("Projection should act on commutative squares"),
+            }),
+        };
+        match proj {
+            TabMorProj::Src(_) => Path::Seq(squares.map(|(_, _, pre, _)| pre)),
+            TabMorProj::Tgt(_) => Path::Seq(squares.map(|(_, _, _, post)| post)),
+            TabMorProj::Cone(_) => {
+                let dom = *squares.head.0.clone();
+                let post = Path::Seq(squares.map(|(_, _, _, post)| post));
+                self.compose(Path::pair(dom, post))
+            }
+        }
+    }
+
+    /// Is the model freely generated?
+    pub fn is_free(&self) -> bool {
+        self.equations.is_empty()
+    }
+
+    /// Adds an equation to the model, making it not free.
+    pub fn add_equation(&mut self, key: Id, eq: TabPathEq<Id, Id>) {
+        self.rewriting.take();
+        self.equations.set(key, eq);
+    }
+
+    /// Iterates over the equations in the model, along with their keys.
+    pub fn equations(&self) -> impl Iterator<Item = (Id, &TabPathEq<Id, Id>)> {
+        self.equations.iter()
+    }
+
+    /** Decides whether two morphisms in the model are equal.
+
+    The equations are completed into a rewriting system on paths, so that
+    equality is closed under composition. Since tabulated objects and
+    commutative squares are themselves built from morphisms, the morphisms
+    nested in them are also brought into normal form, and the equations are
+    completed again until normalization no longer changes them. Returns `None`
+    if completion does not terminate within a fixed bound.
+     */
+    pub fn decide_eq(&self, mor1: &TabMor<Id, Id>, mor2: &TabMor<Id, Id>) -> Option<bool> {
+        if self.is_free() {
+            return Some(mor1 == mor2);
+        }
+        let rewriting = self.rewriting()?;
+        Some(
+            normalize_tab_mor(rewriting, mor1.clone())
+                == normalize_tab_mor(rewriting, mor2.clone()),
+        )
+    }
+
+    /// Gets the cached rewriting system, completing the equations if needed.
+    fn rewriting(&self) -> Option<&TabRewriteSystem<Id>> {
+        self.rewriting
+            .get_or_init(|| {
+                let mut equations: Vec<_> = self.equations.values().cloned().collect();
+                for _ in 0..MAX_COMPLETION_ROUNDS {
+                    let rewriting = IndexedRewriteSystem::complete(
+                        &self.generators,
+                        &equations,
+                        MAX_COMPLETION_RULES,
+                    )?;
+                    let normalized: Vec<_> = equations
+                        .iter()
+                        .map(|eq| {
+                            PathEq::new(
+                                normalize_tab_nested(&rewriting, eq.lhs.clone()),
+                                normalize_tab_nested(&rewriting, eq.rhs.clone()),
+                            )
+                        })
+                        .collect();
+                    if normalized == equations {
+                        return Some(rewriting);
+                    }
+                    equations = normalized;
+                }
+                None
+            })
+            .as_ref()
+    }
+
     /// Iterates over failures of model to be well defined.
     pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidDblModel<Id>> + '_ {
         type Invalid<Id> = InvalidDblModel<Id>;
@@ -729,8 +954,15 @@ where
             if cod.is_none() {
                 errs.push(Invalid::Cod(e.clone()));
             }
-            if let Some(mor_type) =
-                self.mor_types.get(&e).filter(|typ| self.theory.has_mor_type(typ))
+            // Morphisms between tabulated objects must be commutative squares
+            // for their projections to be defined, so they cannot be basic.
+            let is_square_type = |typ: &TabMorType<ThId, ThId>| {
+                matches!(typ, TabMorType::Hom(x) if matches!(**x, TabObType::Tabulator(_)))
+            };
+            if let Some(mor_type) = self
+                .mor_types
+                .get(&e)
+                .filter(|typ| self.theory.has_mor_type(typ) && !is_square_type(typ))
             {
                 if dom.is_some_and(|x| self.ob_type(x) != self.theory.src(mor_type)) {
                     errs.push(Invalid::DomType(e.clone()));
@@ -743,7 +975,15 @@ where
             }
             errs.into_iter()
         });
-        ob_errors.chain(mor_errors)
+        let eq_errors = self.equations.iter().flat_map(|(key, eq)| {
+            eq.iter_invalid_in(&self.generators).map(move |err| match err {
+                InvalidPathEq::Lhs() => Invalid::EqLhs(key.clone()),
+                InvalidPathEq::Rhs() => Invalid::EqRhs(key.clone()),
+                InvalidPathEq::Src() => Invalid::EqSrc(key.clone()),
+                InvalidPathEq::Tgt() => Invalid::EqTgt(key.clone()),
+            })
+        });
+        ob_errors.chain(mor_errors).chain(eq_errors)
     }
 }
 
@@ -831,12 +1071,15 @@ where
         self.theory.compose_types(types).expect("Morphism types should have composite")
     }
 
-    fn ob_act(&self, _ob: Self::Ob, _op: &Self::ObOp) -> Self::Ob {
-        panic!("Action on objects not implemented")
+    fn ob_act(&self, ob: Self::Ob, op: &Self::ObOp) -> Self::Ob {
+        match op {
+            Path::Id(_) => ob,
+            Path::Seq(projs) => projs.iter().fold(ob, |ob, proj| self.ob_proj(ob, proj)),
+        }
     }
 
-    fn mor_act(&self, _mor: Self::Mor, _op: &Self::MorOp) -> Self::Mor {
-        panic!("Action on morphisms not implemented")
+    fn mor_act(&self, mor: Self::Mor, op: &Self::MorOp) -> Self::Mor {
+        op.projections().iter().fold(mor, |mor, proj| self.mor_proj(mor, proj))
     }
 }
 
@@ -876,6 +1119,7 @@ where
     }
 
     fn make_mor(&mut self, f: Self::MorGen, mor_type: Self::MorType) -> bool {
+        self.rewriting.take();
         self.mor_types.set(f.clone(), mor_type);
         self.generators.morphisms.insert(f)
     }
@@ -887,13 +1131,85 @@ where
         self.generators.cod.get(f)
     }
     fn set_dom(&mut self, f: Self::MorGen, x: Self::Ob) -> Option<Self::Ob> {
+        self.rewriting.take();
         self.generators.dom.set(f, x)
     }
     fn set_cod(&mut self, f: Self::MorGen, x: Self::Ob) -> Option<Self::Ob> {
+        self.rewriting.take();
         self.generators.cod.set(f, x)
     }
 }
 
+/// Normalizes a morphism in a tabulator model, including nested morphisms.
+fn normalize_tab_mor<Id>(rewriting: &TabRewriteSystem<Id>, mor: TabMor<Id, Id>) -> TabMor<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    match rewriting.normalize(normalize_tab_nested(rewriting, mor)) {
+        Path::Id(x) => Path::Id(normalize_tab_ob(rewriting, x)),
+        path => path,
+    }
+}
+
+/// Normalizes the morphisms nested in the objects and edges of a path.
+fn normalize_tab_nested<Id>(rewriting: &TabRewriteSystem<Id>, mor: TabMor<Id, Id>) -> TabMor<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    mor.map(|x| normalize_tab_ob(rewriting, x), |e| normalize_tab_edge(rewriting, e))
+}
+
+/// Normalizes the morphism nested in a tabulated object.
+fn normalize_tab_ob<Id>(rewriting: &TabRewriteSystem<Id>, ob: TabOb<Id, Id>) -> TabOb<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    match ob {
+        TabOb::Basic(x) => TabOb::Basic(x),
+        TabOb::Tabulated(m) => TabOb::Tabulated(Box::new(normalize_tab_mor(rewriting, *m))),
+    }
+}
+
+/// Normalizes the morphisms nested in a commutative square.
+fn normalize_tab_edge<Id>(
+    rewriting: &TabRewriteSystem<Id>,
+    edge: TabEdge<Id, Id>,
+) -> TabEdge<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    match edge {
+        TabEdge::Basic(e) => TabEdge::Basic(e),
+        TabEdge::Square {
+            dom,
+            cod,
+            pre,
+            post,
+        } => TabEdge::Square {
+            dom: Box::new(normalize_tab_mor(rewriting, *dom)),
+            cod: Box::new(normalize_tab_mor(rewriting, *cod)),
+            pre: Box::new(normalize_tab_side(rewriting, *pre)),
+            post: Box::new(normalize_tab_side(rewriting, *post)),
+        },
+    }
+}
+
+/// Normalizes an edge on the side of a commutative square, which can only be
+/// replaced by an equal edge, not by an arbitrary path.
+fn normalize_tab_side<Id>(
+    rewriting: &TabRewriteSystem<Id>,
+    edge: TabEdge<Id, Id>,
+) -> TabEdge<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    let edge = normalize_tab_edge(rewriting, edge);
+    match rewriting.normalize(Path::single(edge.clone())) {
+        Path::Seq(edges) if edges.tail.is_empty() => edges.head,
+        _ => edge,
+    }
+}
+
 impl<Id, ThId, S> Validate for DiscreteTabModel<Id, ThId, S>
 where
     Id: Eq + Clone + Hash,
@@ -907,53 +1223,1248 @@ where
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use nonempty::nonempty;
-    use ustr::ustr;
+/// Object in a model of a simple double theory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimpleOb<Id, ThId> {
+    /// Basic or generating object.
+    Basic(Id),
 
-    use super::*;
-    use crate::one::fin_category::FinMor;
-    use crate::stdlib::{models::*, theories::*};
+    /// Basic object operation applied to an object.
+    App(Box<SimpleOb<Id, ThId>>, ThId),
+}
 
-    #[test]
-    fn validate_discrete_dbl_model() {
-        let th = Arc::new(th_schema());
-        let mut model = DiscreteDblModel::new(th.clone());
-        let entity = ustr("entity");
-        model.add_ob(entity, ustr("NotObType"));
-        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::ObType(entity)]));
+impl<Id, ThId> From<Id> for SimpleOb<Id, ThId> {
+    fn from(value: Id) -> Self {
+        SimpleOb::Basic(value)
+    }
+}
 
-        let mut model = DiscreteDblModel::new(th.clone());
-        model.add_ob(entity, ustr("Entity"));
-        model.add_mor(ustr("map"), entity, entity, FinMor::Generator(ustr("NotMorType")));
-        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::MorType(ustr("map"))]));
+impl<Id, ThId> SimpleOb<Id, ThId> {
+    /// Splits the object into a basic object and the operations applied to it.
+    fn unapply(self) -> (Id, Vec<ThId>) {
+        match self {
+            SimpleOb::Basic(x) => (x, Vec::new()),
+            SimpleOb::App(ob, e) => {
+                let (x, mut ops) = ob.unapply();
+                ops.push(e);
+                (x, ops)
+            }
+        }
+    }
+}
 
-        let mut model = DiscreteDblModel::new(th);
-        model.add_ob(entity, ustr("Entity"));
-        model.add_ob(ustr("type"), ustr("AttrType"));
-        model.add_mor(ustr("a"), entity, ustr("type"), FinMor::Generator(ustr("Attr")));
-        assert!(model.validate().is_ok());
-        model.add_mor(ustr("b"), entity, ustr("type"), FinMor::Id(ustr("Entity")));
-        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::CodType(ustr("b"))]));
+/** "Edge" in a model of a simple double theory.
+
+Morphisms of these two forms generate all the morphisms in the model.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimpleEdge<Id, ThId> {
+    /// Basic morphism between any two objects.
+    Basic(Id),
+
+    /// Unary morphism operation applied to a morphism.
+    App(Box<SimpleMor<Id, ThId>>, SimpleMorOpTerm<ThId, ThId>),
+}
+
+impl<Id, ThId> From<Id> for SimpleEdge<Id, ThId> {
+    fn from(value: Id) -> Self {
+        SimpleEdge::Basic(value)
     }
+}
 
-    #[test]
-    fn infer_discrete_dbl_model() {
-        let th = Arc::new(th_schema());
-        let mut model = DiscreteDblModel::new(th.clone());
-        model.add_mor(ustr("attr"), ustr("entity"), ustr("type"), FinMor::Generator(ustr("Attr")));
-        model.infer_missing();
-        assert_eq!(model, walking_attr(th));
+/// Morphism in a model of a simple double theory.
+pub type SimpleMor<Id, ThId> = Path<SimpleOb<Id, ThId>, SimpleEdge<Id, ThId>>;
+
+impl<Id, ThId> From<Id> for SimpleMor<Id, ThId> {
+    fn from(value: Id) -> Self {
+        Path::single(value.into())
     }
+}
 
-    #[test]
-    fn validate_discrete_tab_model() {
-        let th = Arc::new(th_category_links());
-        let mut model = DiscreteTabModel::new(th);
-        let (x, f) = (ustr("x"), ustr("f"));
-        model.add_ob(x, TabObType::Basic(ustr("Object")));
-        model.add_mor(f, TabOb::Basic(x), TabOb::Basic(x), TabMorType::Basic(ustr("Link")));
-        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::CodType(f)]));
+/** A finitely generated model of a simple double theory.
+
+Objects of the model are generated by basic objects under the action of the
+object operations in the [theory](SimpleDblTheory), with object operations
+reduced to normal form. Likewise, morphisms are generated by basic morphisms
+under the action of morphism operations. The Hom operation on an object
+operation acts on morphisms edge by edge, composing with any object operation
+already applied, making each object operation a functor, but morphism
+operations otherwise act formally. Equations between morphisms are not yet
+supported.
+ */
+#[derive(Clone, Derivative)]
+#[derivative(PartialEq(bound = "Id: Eq + Hash, ThId: Eq + Hash"))]
+#[derivative(Eq(bound = "Id: Eq + Hash, ThId: Eq + Hash"))]
+pub struct SimpleDblModel<Id, ThId, S = RandomState> {
+    #[derivative(PartialEq(compare_with = "Arc::ptr_eq"))]
+    theory: Arc<SimpleDblTheory<ThId, ThId, S>>,
+    objects: HashFinSet<Id>,
+    morphisms: HashFinSet<Id>,
+    dom: HashColumn<Id, SimpleOb<Id, ThId>>,
+    cod: HashColumn<Id, SimpleOb<Id, ThId>>,
+    ob_types: IndexedHashColumn<Id, ThId>,
+    mor_types: IndexedHashColumn<Id, FinMor<ThId, ThId>>,
+}
+
+/// A model of a simple double theory where both theory and model have keys of
+/// type `Ustr`.
+pub type UstrSimpleDblModel = SimpleDblModel<Ustr, Ustr, BuildHasherDefault<IdentityHasher>>;
+
+impl<Id, ThId, S> SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    /// Creates an empty model of the given theory.
+    pub fn new(theory: Arc<SimpleDblTheory<ThId, ThId, S>>) -> Self {
+        Self {
+            theory,
+            objects: Default::default(),
+            morphisms: Default::default(),
+            dom: Default::default(),
+            cod: Default::default(),
+            ob_types: Default::default(),
+            mor_types: Default::default(),
+        }
+    }
+
+    /// Returns a reference-counting pointer to the theory for this model.
+    pub fn theory_arc(&self) -> Arc<SimpleDblTheory<ThId, ThId, S>> {
+        self.theory.clone()
+    }
+
+    /// Applies a unary term for a morphism operation to a morphism.
+    fn act_term(
+        &self,
+        mor: SimpleMor<Id, ThId>,
+        term: &SimpleMorOpTerm<ThId, ThId>,
+    ) -> SimpleMor<Id, ThId> {
+        match term {
+            SimpleMorOpTerm::Id(_) => mor,
+            SimpleMorOpTerm::Vertical(terms) => {
+                terms.iter().fold(mor, |mor, term| self.act_term(mor, term))
+            }
+            SimpleMorOpTerm::Hom(f) => match mor {
+                Path::Id(x) => Path::Id(self.ob_act(x, f)),
+                Path::Seq(edges) => {
+                    Path::Seq(edges.map(|edge| self.hom_act_edge(edge, f))).flatten()
+                }
+            },
+            _ => Path::single(SimpleEdge::App(Box::new(mor), term.clone())),
+        }
+    }
+
+    /** Applies the Hom operation on an object operation to an edge.
+
+    An edge that is already the Hom operation on some object operation applied
+    to a morphism is replaced by the Hom operation on the composite, in normal
+    form, so that object operations act functorially on morphisms.
+     */
+    fn hom_act_edge(
+        &self,
+        edge: SimpleEdge<Id, ThId>,
+        f: &Path<ThId, ThId>,
+    ) -> SimpleMor<Id, ThId> {
+        let (mor, op) = match edge {
+            SimpleEdge::App(mor, SimpleMorOpTerm::Hom(g)) => (*mor, Path::pair(g, f.clone())),
+            edge => (Path::single(edge), Path::single(f.clone())),
+        };
+        match self.theory.compose_ob_ops(op) {
+            Path::Id(_) => mor,
+            op => Path::single(SimpleEdge::App(Box::new(mor), SimpleMorOpTerm::Hom(op))),
+        }
+    }
+
+    /// Iterates over failures of model to be well defined.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidDblModel<Id>> + '_ {
+        type Invalid<Id> = InvalidDblModel<Id>;
+        let ob_errors = self.objects.iter().filter_map(|x| {
+            if self.ob_types.get(&x).is_some_and(|typ| self.theory.has_ob_type(typ)) {
+                None
+            } else {
+                Some(Invalid::ObType(x))
+            }
+        });
+        let mor_errors = self.morphisms.iter().flat_map(|e| {
+            let mut errs = Vec::new();
+            let dom = self.dom.get(&e).filter(|x| self.has_ob(x));
+            let cod = self.cod.get(&e).filter(|x| self.has_ob(x));
+            if dom.is_none() {
+                errs.push(Invalid::Dom(e.clone()));
+            }
+            if cod.is_none() {
+                errs.push(Invalid::Cod(e.clone()));
+            }
+            if let Some(mor_type) =
+                self.mor_types.get(&e).filter(|typ| self.theory.has_mor_type(typ))
+            {
+                if dom.is_some_and(|x| self.ob_type(x) != self.theory.src_type(mor_type)) {
+                    errs.push(Invalid::DomType(e.clone()));
+                }
+                if cod.is_some_and(|x| self.ob_type(x) != self.theory.tgt_type(mor_type)) {
+                    errs.push(Invalid::CodType(e.clone()));
+                }
+            } else {
+                errs.push(Invalid::MorType(e));
+            }
+            errs.into_iter()
+        });
+        ob_errors.chain(mor_errors)
+    }
+}
+
+/// Graph of generating objects and edges in a model of a simple double theory.
+#[derive(RefCast)]
+#[repr(transparent)]
+struct SimpleModelGraph<Id, ThId, S>(SimpleDblModel<Id, ThId, S>);
+
+impl<Id, ThId, S> Graph for SimpleModelGraph<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type V = SimpleOb<Id, ThId>;
+    type E = SimpleEdge<Id, ThId>;
+
+    fn has_vertex(&self, ob: &Self::V) -> bool {
+        let model = &self.0;
+        match ob {
+            SimpleOb::Basic(x) => model.objects.contains(x),
+            SimpleOb::App(ob, e) => {
+                let f = Path::single(e.clone());
+                self.has_vertex(ob)
+                    && model.theory.has_ob_op(&f)
+                    && model.ob_type(ob) == model.theory.ob_op_dom(&f)
+            }
+        }
+    }
+
+    fn has_edge(&self, edge: &Self::E) -> bool {
+        let model = &self.0;
+        match edge {
+            SimpleEdge::Basic(e) => {
+                model.morphisms.contains(e) && model.dom.is_set(e) && model.cod.is_set(e)
+            }
+            SimpleEdge::App(mor, term) => {
+                mor.contained_in(self)
+                    && model
+                        .theory
+                        .boundary(term)
+                        .is_some_and(|boundary| model.mor_type(mor) == boundary.dom)
+            }
+        }
+    }
+
+    fn src(&self, edge: &Self::E) -> Self::V {
+        let model = &self.0;
+        match edge {
+            SimpleEdge::Basic(e) => {
+                model.dom.apply(e).expect("Domain of morphism should be defined")
+            }
+            SimpleEdge::App(mor, term) => {
+                model.ob_act(mor.src(self), &model.theory.unwrap_boundary(term).src)
+            }
+        }
+    }
+
+    fn tgt(&self, edge: &Self::E) -> Self::V {
+        let model = &self.0;
+        match edge {
+            SimpleEdge::Basic(e) => {
+                model.cod.apply(e).expect("Codomain of morphism should be defined")
+            }
+            SimpleEdge::App(mor, term) => {
+                model.ob_act(mor.tgt(self), &model.theory.unwrap_boundary(term).tgt)
+            }
+        }
+    }
+}
+
+impl<Id, ThId, S> Category for SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type Ob = SimpleOb<Id, ThId>;
+    type Mor = SimpleMor<Id, ThId>;
+
+    fn has_ob(&self, x: &Self::Ob) -> bool {
+        SimpleModelGraph::ref_cast(self).has_vertex(x)
+    }
+    fn has_mor(&self, path: &Self::Mor) -> bool {
+        path.contained_in(SimpleModelGraph::ref_cast(self))
+    }
+    fn dom(&self, path: &Self::Mor) -> Self::Ob {
+        path.src(SimpleModelGraph::ref_cast(self))
+    }
+    fn cod(&self, path: &Self::Mor) -> Self::Ob {
+        path.tgt(SimpleModelGraph::ref_cast(self))
+    }
+
+    fn compose(&self, path: Path<Self::Ob, Self::Mor>) -> Self::Mor {
+        path.flatten_in(SimpleModelGraph::ref_cast(self))
+            .expect("Paths should be composable")
+    }
+}
+
+impl<Id, ThId, S> FgCategory for SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type ObGen = Id;
+    type MorGen = Id;
+
+    fn ob_generators(&self) -> impl Iterator<Item = Self::ObGen> {
+        self.objects.iter()
+    }
+    fn mor_generators(&self) -> impl Iterator<Item = Self::MorGen> {
+        self.morphisms.iter()
+    }
+
+    fn mor_generator_dom(&self, f: &Self::MorGen) -> Self::Ob {
+        self.dom.apply(f).expect("Domain should be defined")
+    }
+    fn mor_generator_cod(&self, f: &Self::MorGen) -> Self::Ob {
+        self.cod.apply(f).expect("Codomain should be defined")
+    }
+}
+
+impl<Id, ThId, S> DblModel for SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type ObType = ThId;
+    type MorType = FinMor<ThId, ThId>;
+    type ObOp = Path<ThId, ThId>;
+    type MorOp = SimpleMorOp<ThId, ThId>;
+    type Theory = SimpleDblTheory<ThId, ThId, S>;
+
+    fn theory(&self) -> &Self::Theory {
+        &self.theory
+    }
+
+    fn ob_type(&self, ob: &Self::Ob) -> Self::ObType {
+        match ob {
+            SimpleOb::Basic(x) => self.ob_generator_type(x),
+            SimpleOb::App(_, e) => self.theory.ob_op_cod(&Path::single(e.clone())),
+        }
+    }
+
+    fn mor_type(&self, mor: &Self::Mor) -> Self::MorType {
+        let types = mor.clone().map(
+            |x| self.ob_type(&x),
+            |edge| match edge {
+                SimpleEdge::Basic(f) => self.mor_generator_type(&f),
+                SimpleEdge::App(_, term) => self.theory.unwrap_boundary(&term).cod,
+            },
+        );
+        self.theory.compose_types(types).expect("Morphism types should have composite")
+    }
+
+    fn ob_act(&self, ob: Self::Ob, op: &Self::ObOp) -> Self::Ob {
+        let (x, ops) = ob.unapply();
+        let path = Path::from_vec(ops).unwrap_or_else(|| Path::Id(self.ob_generator_type(&x)));
+        let path = self.theory.compose_ob_ops(Path::pair(path, op.clone()));
+        path.into_iter()
+            .fold(SimpleOb::Basic(x), |ob, e| SimpleOb::App(Box::new(ob), e))
+    }
+
+    fn mor_act(&self, mor: Self::Mor, op: &Self::MorOp) -> Self::Mor {
+        self.act_term(mor, op.term())
+    }
+}
+
+impl<Id, ThId, S> FgDblModel for SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    fn ob_generator_type(&self, ob: &Self::ObGen) -> Self::ObType {
+        self.ob_types.apply(ob).expect("Object should have type")
+    }
+    fn mor_generator_type(&self, mor: &Self::MorGen) -> Self::MorType {
+        self.mor_types.apply(mor).expect("Morphism should have type")
+    }
+
+    fn ob_generators_with_type(&self, obtype: &Self::ObType) -> impl Iterator<Item = Self::ObGen> {
+        self.ob_types.preimage(obtype)
+    }
+    fn mor_generators_with_type(
+        &self,
+        mortype: &Self::MorType,
+    ) -> impl Iterator<Item = Self::MorGen> {
+        self.mor_types.preimage(mortype)
+    }
+}
+
+impl<Id, ThId, S> MutDblModel for SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    fn add_ob(&mut self, x: Self::ObGen, ob_type: Self::ObType) -> bool {
+        self.ob_types.set(x.clone(), ob_type);
+        self.objects.insert(x)
+    }
+
+    fn make_mor(&mut self, f: Self::MorGen, mor_type: Self::MorType) -> bool {
+        self.mor_types.set(f.clone(), mor_type);
+        self.morphisms.insert(f)
+    }
+
+    fn get_dom(&self, f: &Self::MorGen) -> Option<&Self::Ob> {
+        self.dom.get(f)
+    }
+    fn get_cod(&self, f: &Self::MorGen) -> Option<&Self::Ob> {
+        self.cod.get(f)
+    }
+    fn set_dom(&mut self, f: Self::MorGen, x: Self::Ob) -> Option<Self::Ob> {
+        self.dom.set(f, x)
+    }
+    fn set_cod(&mut self, f: Self::MorGen, x: Self::Ob) -> Option<Self::Ob> {
+        self.cod.set(f, x)
+    }
+}
+
+impl<Id, ThId, S> Validate for SimpleDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type ValidationError = InvalidDblModel<Id>;
+
+    fn validate(&self) -> Result<(), nonempty::NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+/** Object in a model of a modal double theory.
+
+Lists formed with the [symmetric list](Modality::SymList) modality are equal
+when they are permutations of each other, so that the order in which the
+elements are given does not matter.
+ */
+#[derive(Clone, Debug)]
+pub enum ModalOb<Id, ThId> {
+    /// Basic or generating object.
+    Basic(Id),
+
+    /// Basic object operation, possibly with modalities, applied to an object.
+    App(Box<ModalOb<Id, ThId>>, ModeApp<ThId>),
+
+    /// List of objects of a common type, an object of that type with a list
+    /// modality applied.
+    List(Modality, ModalObType<ThId>, Vec<ModalOb<Id, ThId>>),
+}
+
+impl<Id: PartialEq, ThId: PartialEq> PartialEq for ModalOb<Id, ThId> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ModalOb::Basic(x), ModalOb::Basic(y)) => x == y,
+            (ModalOb::App(x, e), ModalOb::App(y, f)) => x == y && e == f,
+            (ModalOb::List(m, s, xs), ModalOb::List(n, t, ys)) => {
+                m == n
+                    && s == t
+                    && match m {
+                        Modality::List => xs == ys,
+                        Modality::SymList => is_permutation(xs, ys),
+                    }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<Id: Eq, ThId: Eq> Eq for ModalOb<Id, ThId> {}
+
+impl<Id, ThId> From<Id> for ModalOb<Id, ThId> {
+    fn from(value: Id) -> Self {
+        ModalOb::Basic(value)
+    }
+}
+
+/// Is one slice a permutation of another, up to equality of elements?
+fn is_permutation<T: PartialEq>(xs: &[T], ys: &[T]) -> bool {
+    let mut used = vec![false; ys.len()];
+    xs.len() == ys.len()
+        && xs.iter().all(|x| match (0..ys.len()).find(|i| !used[*i] && ys[*i] == *x) {
+            Some(i) => {
+                used[i] = true;
+                true
+            }
+            None => false,
+        })
+}
+
+impl<Id, ThId> ModalOb<Id, ThId> {
+    /// Constructs a list of objects of the given type.
+    pub fn list(
+        modality: Modality,
+        ob_type: ModalObType<ThId>,
+        obs: impl IntoIterator<Item = ModalOb<Id, ThId>>,
+    ) -> Self {
+        ModalOb::List(modality, ob_type, obs.into_iter().collect())
+    }
+
+    /// Applies a basic object operation to the object.
+    pub fn app(self, op: ThId) -> Self {
+        ModalOb::App(Box::new(self), ModeApp::new(op))
+    }
+}
+
+/** "Edge" in a model of a modal double theory.
+
+Morphisms of these two forms generate all the morphisms in the model.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ModalEdge<Id, ThId> {
+    /// Basic morphism between any two objects.
+    Basic(Id),
+
+    /// Hom operation on an object operation applied to a morphism.
+    App(Box<ModalMor<Id, ThId>>, ModalObOp<ThId, ThId>),
+}
+
+impl<Id, ThId> From<Id> for ModalEdge<Id, ThId> {
+    fn from(value: Id) -> Self {
+        ModalEdge::Basic(value)
+    }
+}
+
+/// Morphism in a model of a modal double theory.
+pub type ModalMor<Id, ThId> = Path<ModalOb<Id, ThId>, ModalEdge<Id, ThId>>;
+
+impl<Id, ThId> From<Id> for ModalMor<Id, ThId> {
+    fn from(value: Id) -> Self {
+        Path::single(value.into())
+    }
+}
+
+/** A finitely generated model of a modal double theory.
+
+Objects of the model are generated by basic objects under the action of the
+object operations in the [theory](ModalDblTheory) and under the formation of
+lists. Morphisms are generated by basic morphisms, which can go between lists of
+objects, under the action of Hom operations. For example, a Petri net is a model
+of the theory of [symmetric monoidal
+categories](crate::stdlib::theories::th_symmetric_monoidal_category) whose
+transitions are morphisms between tensor products of lists of places.
+
+Lists record the type of their elements, so that even an empty list, such as
+the inputs of a transition with no inputs, has a type.
+ */
+#[derive(Clone, Derivative)]
+#[derivative(PartialEq(bound = "Id: Eq + Hash, ThId: Eq + Hash"))]
+#[derivative(Eq(bound = "Id: Eq + Hash, ThId: Eq + Hash"))]
+pub struct ModalDblModel<Id, ThId, S = RandomState> {
+    #[derivative(PartialEq(compare_with = "Arc::ptr_eq"))]
+    theory: Arc<ModalDblTheory<ThId, ThId, S>>,
+    objects: HashFinSet<Id>,
+    morphisms: HashFinSet<Id>,
+    dom: HashColumn<Id, ModalOb<Id, ThId>>,
+    cod: HashColumn<Id, ModalOb<Id, ThId>>,
+    ob_types: IndexedHashColumn<Id, ModalObType<ThId>>,
+    mor_types: IndexedHashColumn<Id, ModalMorType<ThId, ThId>>,
+}
+
+/// A model of a modal double theory where both theory and model have keys of
+/// type `Ustr`.
+pub type UstrModalDblModel = ModalDblModel<Ustr, Ustr, BuildHasherDefault<IdentityHasher>>;
+
+impl<Id, ThId, S> ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    /// Creates an empty model of the given theory.
+    pub fn new(theory: Arc<ModalDblTheory<ThId, ThId, S>>) -> Self {
+        Self {
+            theory,
+            objects: Default::default(),
+            morphisms: Default::default(),
+            dom: Default::default(),
+            cod: Default::default(),
+            ob_types: Default::default(),
+            mor_types: Default::default(),
+        }
+    }
+
+    /// Returns a reference-counting pointer to the theory for this model.
+    pub fn theory_arc(&self) -> Arc<ModalDblTheory<ThId, ThId, S>> {
+        self.theory.clone()
+    }
+
+    /// Does the object belong to the model and have the given type?
+    pub fn has_ob_with_type(&self, ob: &ModalOb<Id, ThId>, ob_type: &ModalObType<ThId>) -> bool {
+        match ob {
+            ModalOb::Basic(x) => self.objects.contains(x) && self.ob_types.get(x) == Some(ob_type),
+            ModalOb::App(x, e) => {
+                let op = Path::single(e.clone());
+                self.theory.has_ob_op(&op)
+                    && &self.theory.ob_op_cod(&op) == ob_type
+                    && self.has_ob_with_type(x, &self.theory.ob_op_dom(&op))
+            }
+            ModalOb::List(modality, typ, obs) => match ob_type.clone().unapply() {
+                (inner, Some(m)) if m == *modality && inner == *typ => {
+                    obs.iter().all(|ob| self.has_ob_with_type(ob, typ))
+                }
+                _ => false,
+            },
+        }
+    }
+
+    /// Gets the type of an object, if it has one.
+    fn try_ob_type(&self, ob: &ModalOb<Id, ThId>) -> Option<ModalObType<ThId>> {
+        match ob {
+            ModalOb::Basic(x) => self.ob_types.get(x).cloned(),
+            ModalOb::App(_, e) => {
+                let op = Path::single(e.clone());
+                self.theory.has_ob_op(&op).then(|| self.theory.ob_op_cod(&op))
+            }
+            ModalOb::List(modality, typ, _) => Some(typ.clone().apply(*modality)),
+        }
+    }
+
+    /** Applies a basic object operation, possibly with modalities, to an object.
+
+    An operation under a list modality acts elementwise on a list formed with
+    that modality. On any other object, such as a basic object whose type is a
+    list type, the operation is applied formally.
+     */
+    fn ob_act_basic(&self, ob: ModalOb<Id, ThId>, op: ModeApp<ThId>) -> ModalOb<Id, ThId> {
+        match (ob, op.clone().unapply()) {
+            (ModalOb::List(m, typ, obs), (inner, Some(modality))) if m == modality => {
+                let inner_op = Path::single(inner.clone());
+                let typ = if self.theory.has_ob_op(&inner_op) {
+                    self.theory.ob_op_cod(&inner_op)
+                } else {
+                    typ
+                };
+                let obs = obs.into_iter().map(|ob| self.ob_act_basic(ob, inner.clone()));
+                ModalOb::List(m, typ, obs.collect())
+            }
+            (ob, _) => ModalOb::App(Box::new(ob), op),
+        }
+    }
+
+    /// Gets the type of a generating edge.
+    fn edge_type(&self, edge: ModalEdge<Id, ThId>) -> ModalMorType<ThId, ThId> {
+        match edge {
+            ModalEdge::Basic(f) => self.mor_generator_type(&f),
+            ModalEdge::App(_, op) => ModalMorType::Hom(self.theory.ob_op_cod(&op)),
+        }
+    }
+
+    /// Iterates over failures of model to be well defined.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidDblModel<Id>> + '_ {
+        type Invalid<Id> = InvalidDblModel<Id>;
+        let ob_errors = self.objects.iter().filter_map(|x| {
+            if self.ob_types.get(&x).is_some_and(|typ| self.theory.has_ob_type(typ)) {
+                None
+            } else {
+                Some(Invalid::ObType(x))
+            }
+        });
+        let mor_errors = self.morphisms.iter().flat_map(|e| {
+            let mut errs = Vec::new();
+            let dom = self.dom.get(&e).filter(|x| self.has_ob(x));
+            let cod = self.cod.get(&e).filter(|x| self.has_ob(x));
+            if dom.is_none() {
+                errs.push(Invalid::Dom(e.clone()));
+            }
+            if cod.is_none() {
+                errs.push(Invalid::Cod(e.clone()));
+            }
+            if let Some(mor_type) =
+                self.mor_types.get(&e).filter(|typ| self.theory.has_mor_type(typ))
+            {
+                if dom.is_some_and(|x| !self.has_ob_with_type(x, &self.theory.src_type(mor_type))) {
+                    errs.push(Invalid::DomType(e.clone()));
+                }
+                if cod.is_some_and(|x| !self.has_ob_with_type(x, &self.theory.tgt_type(mor_type))) {
+                    errs.push(Invalid::CodType(e.clone()));
+                }
+            } else {
+                errs.push(Invalid::MorType(e));
+            }
+            errs.into_iter()
+        });
+        ob_errors.chain(mor_errors)
+    }
+}
+
+/// Graph of generating objects and edges in a model of a modal double theory.
+#[derive(RefCast)]
+#[repr(transparent)]
+struct ModalModelGraph<Id, ThId, S>(ModalDblModel<Id, ThId, S>);
+
+impl<Id, ThId, S> Graph for ModalModelGraph<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type V = ModalOb<Id, ThId>;
+    type E = ModalEdge<Id, ThId>;
+
+    fn has_vertex(&self, ob: &Self::V) -> bool {
+        let model = &self.0;
+        match ob {
+            ModalOb::Basic(x) => model.objects.contains(x),
+            ModalOb::App(..) => {
+                model.try_ob_type(ob).is_some_and(|typ| model.has_ob_with_type(ob, &typ))
+            }
+            ModalOb::List(_, typ, obs) => {
+                obs.iter().all(|ob| self.has_vertex(ob) && model.has_ob_with_type(ob, typ))
+            }
+        }
+    }
+
+    fn has_edge(&self, edge: &Self::E) -> bool {
+        let model = &self.0;
+        match edge {
+            ModalEdge::Basic(e) => {
+                model.morphisms.contains(e) && model.dom.is_set(e) && model.cod.is_set(e)
+            }
+            ModalEdge::App(mor, op) => {
+                mor.contained_in(self)
+                    && model.theory.has_ob_op(op)
+                    && model.has_ob_with_type(&mor.src(self), &model.theory.ob_op_dom(op))
+            }
+        }
+    }
+
+    fn src(&self, edge: &Self::E) -> Self::V {
+        let model = &self.0;
+        match edge {
+            ModalEdge::Basic(e) => {
+                model.dom.apply(e).expect("Domain of morphism should be defined")
+            }
+            ModalEdge::App(mor, op) => model.ob_act(mor.src(self), op),
+        }
+    }
+
+    fn tgt(&self, edge: &Self::E) -> Self::V {
+        let model = &self.0;
+        match edge {
+            ModalEdge::Basic(e) => {
+                model.cod.apply(e).expect("Codomain of morphism should be defined")
+            }
+            ModalEdge::App(mor, op) => model.ob_act(mor.tgt(self), op),
+        }
+    }
+}
+
+impl<Id, ThId, S> Category for ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type Ob = ModalOb<Id, ThId>;
+    type Mor = ModalMor<Id, ThId>;
+
+    fn has_ob(&self, x: &Self::Ob) -> bool {
+        ModalModelGraph::ref_cast(self).has_vertex(x)
+    }
+    fn has_mor(&self, path: &Self::Mor) -> bool {
+        path.contained_in(ModalModelGraph::ref_cast(self))
+    }
+    fn dom(&self, path: &Self::Mor) -> Self::Ob {
+        path.src(ModalModelGraph::ref_cast(self))
+    }
+    fn cod(&self, path: &Self::Mor) -> Self::Ob {
+        path.tgt(ModalModelGraph::ref_cast(self))
+    }
+
+    fn compose(&self, path: Path<Self::Ob, Self::Mor>) -> Self::Mor {
+        path.flatten_in(ModalModelGraph::ref_cast(self))
+            .expect("Paths should be composable")
+    }
+}
+
+impl<Id, ThId, S> FgCategory for ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type ObGen = Id;
+    type MorGen = Id;
+
+    fn ob_generators(&self) -> impl Iterator<Item = Self::ObGen> {
+        self.objects.iter()
+    }
+    fn mor_generators(&self) -> impl Iterator<Item = Self::MorGen> {
+        self.morphisms.iter()
+    }
+
+    fn mor_generator_dom(&self, f: &Self::MorGen) -> Self::Ob {
+        self.dom.apply(f).expect("Domain should be defined")
+    }
+    fn mor_generator_cod(&self, f: &Self::MorGen) -> Self::Ob {
+        self.cod.apply(f).expect("Codomain should be defined")
+    }
+}
+
+impl<Id, ThId, S> DblModel for ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type ObType = ModalObType<ThId>;
+    type MorType = ModalMorType<ThId, ThId>;
+    type ObOp = ModalObOp<ThId, ThId>;
+    type MorOp = ModalMorOp<ThId, ThId>;
+    type Theory = ModalDblTheory<ThId, ThId, S>;
+
+    fn theory(&self) -> &Self::Theory {
+        &self.theory
+    }
+
+    fn ob_type(&self, ob: &Self::Ob) -> Self::ObType {
+        match ob {
+            ModalOb::Basic(x) => self.ob_generator_type(x),
+            _ => self.try_ob_type(ob).expect("Object should have a type"),
+        }
+    }
+
+    fn mor_type(&self, mor: &Self::Mor) -> Self::MorType {
+        match mor {
+            Path::Id(x) => self.theory.hom_type(self.ob_type(x)),
+            Path::Seq(edges) => {
+                let types = Path::Seq(edges.clone().map(|edge| self.edge_type(edge)));
+                self.theory.compose_types(types).expect("Morphism types should have composite")
+            }
+        }
+    }
+
+    fn ob_act(&self, ob: Self::Ob, op: &Self::ObOp) -> Self::Ob {
+        op.iter().cloned().fold(ob, |ob, e| self.ob_act_basic(ob, e))
+    }
+
+    fn mor_act(&self, mor: Self::Mor, op: &Self::MorOp) -> Self::Mor {
+        let Some(op) = op.hom_op() else {
+            return mor;
+        };
+        match mor {
+            Path::Id(x) => Path::Id(self.ob_act(x, op)),
+            Path::Seq(edges) => Path::Seq(
+                edges.map(|edge| ModalEdge::App(Box::new(Path::single(edge)), op.clone())),
+            ),
+        }
+    }
+}
+
+impl<Id, ThId, S> FgDblModel for ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    fn ob_generator_type(&self, ob: &Self::ObGen) -> Self::ObType {
+        self.ob_types.apply(ob).expect("Object should have type")
+    }
+    fn mor_generator_type(&self, mor: &Self::MorGen) -> Self::MorType {
+        self.mor_types.apply(mor).expect("Morphism should have type")
+    }
+
+    fn ob_generators_with_type(&self, obtype: &Self::ObType) -> impl Iterator<Item = Self::ObGen> {
+        self.ob_types.preimage(obtype)
+    }
+    fn mor_generators_with_type(
+        &self,
+        mortype: &Self::MorType,
+    ) -> impl Iterator<Item = Self::MorGen> {
+        self.mor_types.preimage(mortype)
+    }
+}
+
+impl<Id, ThId, S> MutDblModel for ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    fn add_ob(&mut self, x: Self::ObGen, ob_type: Self::ObType) -> bool {
+        self.ob_types.set(x.clone(), ob_type);
+        self.objects.insert(x)
+    }
+
+    fn make_mor(&mut self, f: Self::MorGen, mor_type: Self::MorType) -> bool {
+        self.mor_types.set(f.clone(), mor_type);
+        self.morphisms.insert(f)
+    }
+
+    fn get_dom(&self, f: &Self::MorGen) -> Option<&Self::Ob> {
+        self.dom.get(f)
+    }
+    fn get_cod(&self, f: &Self::MorGen) -> Option<&Self::Ob> {
+        self.cod.get(f)
+    }
+    fn set_dom(&mut self, f: Self::MorGen, x: Self::Ob) -> Option<Self::Ob> {
+        self.dom.set(f, x)
+    }
+    fn set_cod(&mut self, f: Self::MorGen, x: Self::Ob) -> Option<Self::Ob> {
+        self.cod.set(f, x)
+    }
+}
+
+impl<Id, ThId, S> Validate for ModalDblModel<Id, ThId, S>
+where
+    Id: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type ValidationError = InvalidDblModel<Id>;
+
+    fn validate(&self) -> Result<(), nonempty::NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nonempty::nonempty;
+    use ustr::ustr;
+
+    use super::*;
+    use crate::one::fin_category::FinMor;
+    use crate::stdlib::{models::*, theories::*};
+
+    #[test]
+    fn validate_discrete_dbl_model() {
+        let th = Arc::new(th_schema());
+        let mut model = DiscreteDblModel::new(th.clone());
+        let entity = ustr("entity");
+        model.add_ob(entity, ustr("NotObType"));
+        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::ObType(entity)]));
+
+        let mut model = DiscreteDblModel::new(th.clone());
+        model.add_ob(entity, ustr("Entity"));
+        model.add_mor(ustr("map"), entity, entity, FinMor::Generator(ustr("NotMorType")));
+        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::MorType(ustr("map"))]));
+
+        let mut model = DiscreteDblModel::new(th);
+        model.add_ob(entity, ustr("Entity"));
+        model.add_ob(ustr("type"), ustr("AttrType"));
+        model.add_mor(ustr("a"), entity, ustr("type"), FinMor::Generator(ustr("Attr")));
+        assert!(model.validate().is_ok());
+        model.add_mor(ustr("b"), entity, ustr("type"), FinMor::Id(ustr("Entity")));
+        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::CodType(ustr("b"))]));
+    }
+
+    #[test]
+    fn infer_discrete_dbl_model() {
+        let th = Arc::new(th_schema());
+        let mut model = DiscreteDblModel::new(th.clone());
+        model.add_mor(ustr("attr"), ustr("entity"), ustr("type"), FinMor::Generator(ustr("Attr")));
+        model.infer_missing();
+        assert_eq!(model, walking_attr(th));
+    }
+
+    #[test]
+    fn isomorphic_discrete_dbl_models() {
+        let th = Arc::new(th_signed_category());
+        let positive = positive_feedback(th.clone());
+        assert!(positive.is_isomorphic_to(&positive_feedback(th.clone())));
+        assert!(!positive.is_isomorphic_to(&negative_feedback(th.clone())));
+
+        let mut model = DiscreteDblModel::new(th.clone());
+        let (x, y) = (ustr("y"), ustr("x"));
+        model.add_ob(x, ustr("Object"));
+        model.add_ob(y, ustr("Object"));
+        model.add_mor(ustr("negative"), x, y, FinMor::Generator(ustr("Negative")));
+        model.add_mor(ustr("positive"), y, x, FinMor::Id(ustr("Object")));
+        assert!(model.is_isomorphic_to(&negative_feedback(th)));
+    }
+
+    #[test]
+    fn validate_discrete_tab_model() {
+        let th = Arc::new(th_category_links());
+        let mut model = DiscreteTabModel::new(th);
+        let (x, f) = (ustr("x"), ustr("f"));
+        model.add_ob(x, TabObType::Basic(ustr("Object")));
+        model.add_mor(f, TabOb::Basic(x), TabOb::Basic(x), TabMorType::Basic(ustr("Link")));
+        assert_eq!(model.validate(), Err(nonempty![InvalidDblModel::CodType(f)]));
+    }
+
+    #[test]
+    fn discrete_tab_model_equations() {
+        let th = Arc::new(th_category_links());
+        let mut model = DiscreteTabModel::new(th.clone());
+        let (x, y, f) = (ustr("x"), ustr("y"), ustr("f"));
+        let ob_type = TabObType::Basic(ustr("Object"));
+        model.add_ob(x, ob_type.clone());
+        model.add_ob(y, ob_type.clone());
+        model.add_mor(f, TabOb::Basic(x), TabOb::Basic(y), th.hom_type(ob_type));
+        let link = TabMorType::Basic(ustr("Link"));
+        let (l1, l2) = (ustr("l1"), ustr("l2"));
+        model.add_mor(l1, TabOb::Basic(x), model.tabulated_gen(f), link.clone());
+        model.add_mor(l2, TabOb::Basic(x), model.tabulated_gen(f), link);
+        assert!(model.is_free());
+
+        model.add_equation(ustr("same"), PathEq::new(l1.into(), l2.into()));
+        assert!(!model.is_free());
+        assert_eq!(model.equations().count(), 1);
+        assert!(model.validate().is_ok());
+
+        model.add_equation(ustr("tgt"), PathEq::new(l1.into(), f.into()));
+        model.add_equation(ustr("rhs"), PathEq::new(l1.into(), ustr("g").into()));
+        let mut errs: Vec<_> = model.validate().unwrap_err().into();
+        errs.sort_by_key(|err| format!("{err:?}"));
+        assert_eq!(
+            errs,
+            vec![InvalidDblModel::EqRhs(ustr("rhs")), InvalidDblModel::EqTgt(ustr("tgt"))]
+        );
+    }
+
+    #[test]
+    fn discrete_tab_model_decide_eq() {
+        let th = Arc::new(th_category_links());
+        let mut model = DiscreteTabModel::new(th.clone());
+        let (x, y) = (ustr("x"), ustr("y"));
+        let ob_type = TabObType::Basic(ustr("Object"));
+        model.add_ob(x, ob_type.clone());
+        model.add_ob(y, ob_type.clone());
+        let hom = th.hom_type(ob_type);
+        let [f, g, h, k] = ["f", "g", "h", "k"].map(ustr);
+        for e in [f, g, h] {
+            model.add_mor(e, TabOb::Basic(x), TabOb::Basic(y), hom.clone());
+        }
+        model.add_mor(k, TabOb::Basic(y), TabOb::Basic(y), hom.clone());
+        assert_eq!(model.decide_eq(&f.into(), &g.into()), Some(false));
+
+        // Equality is transitive.
+        model.add_equation(ustr("fg"), PathEq::new(f.into(), g.into()));
+        model.add_equation(ustr("gh"), PathEq::new(g.into(), h.into()));
+        assert_eq!(model.decide_eq(&f.into(), &h.into()), Some(true));
+        assert_eq!(model.decide_eq(&f.into(), &Path::pair(f.into(), k.into())), Some(false));
+
+        // Equality is preserved by composition.
+        model.add_equation(ustr("kk"), PathEq::new(Path::pair(k.into(), k.into()), k.into()));
+        let fkk = Path::Seq(nonempty![f.into(), k.into(), k.into()]);
+        assert_eq!(model.decide_eq(&fkk, &Path::pair(h.into(), k.into())), Some(true));
+
+        // Equality extends to tabulated morphisms and commutative squares.
+        let (tab_f, tab_h) = (model.tabulated_gen(f), model.tabulated_gen(h));
+        assert_eq!(model.decide_eq(&Path::Id(tab_f), &Path::Id(tab_h)), Some(true));
+        let square = |m: Ustr| -> TabMor<_, _> {
+            Path::single(TabEdge::Square {
+                dom: Box::new(m.into()),
+                cod: Box::new(k.into()),
+                pre: Box::new(m.into()),
+                post: Box::new(k.into()),
+            })
+        };
+        assert!(model.has_mor(&square(f)));
+        assert_eq!(model.decide_eq(&square(f), &square(h)), Some(true));
+        assert_eq!(model.decide_eq(&square(f), &square(k)), Some(false));
+    }
+
+    #[test]
+    fn discrete_tab_model_actions() {
+        let th = Arc::new(th_category_links());
+        let mut model = backward_link(th.clone());
+        let (x, y, f, g) = (ustr("x"), ustr("y"), ustr("f"), ustr("g"));
+        let ob_type = TabObType::Basic(ustr("Object"));
+        let hom = th.hom_type(ob_type.clone());
+        let tab_f = model.tabulated_gen(f);
+
+        let src = Path::single(TabObProj::Src(hom.clone()));
+        let tgt = Path::single(TabObProj::Tgt(hom.clone()));
+        assert_eq!(model.ob_act(tab_f.clone(), &src), TabOb::Basic(x));
+        assert_eq!(model.ob_act(tab_f.clone(), &tgt), TabOb::Basic(y));
+        assert_eq!(model.ob_act(tab_f.clone(), &Path::Id(th.tabulator(hom.clone()))), tab_f);
+
+        let cone = th.unary_projection(TabMorProj::Cone(hom.clone()));
+        let src = th.unary_projection(TabMorProj::Src(hom.clone()));
+        assert_eq!(model.mor_act(Path::Id(tab_f.clone()), &cone), f.into());
+        assert_eq!(model.mor_act(Path::Id(tab_f), &src), Path::Id(TabOb::Basic(x)));
+
+        model.add_mor(g, TabOb::Basic(y), TabOb::Basic(y), hom.clone());
+        let square: TabMor<_, _> = Path::single(TabEdge::Square {
+            dom: Box::new(f.into()),
+            cod: Box::new(g.into()),
+            pre: Box::new(f.into()),
+            post: Box::new(g.into()),
+        });
+        assert!(model.has_mor(&square));
+        let tgt = th.unary_projection(TabMorProj::Tgt(hom.clone()));
+        assert_eq!(model.mor_act(square.clone(), &src), f.into());
+        assert_eq!(model.mor_act(square.clone(), &tgt), g.into());
+        assert_eq!(model.mor_act(square, &cone), Path::pair(f.into(), g.into()));
+
+        // A basic morphism between tabulated objects has no projections.
+        assert!(model.validate().is_ok());
+        let (tab_f, tab_g) = (model.tabulated_gen(f), model.tabulated_gen(g));
+        let tab_hom = th.hom_type(th.tabulator(hom));
+        model.add_mor(ustr("h"), tab_f, tab_g, tab_hom);
+        let errs: Vec<_> = model.validate().unwrap_err().into();
+        assert_eq!(errs, vec![InvalidDblModel::MorType(ustr("h"))]);
+    }
+
+    #[test]
+    fn simple_dbl_model_actions() {
+        let th = Arc::new(th_category_with_endofunctor());
+        let mut model = UstrSimpleDblModel::new(th.clone());
+        let (x, y, f) = (ustr("x"), ustr("y"), ustr("f"));
+        let ob_type = ustr("Object");
+        let hom = th.hom_type(ob_type);
+        model.add_ob(x, ob_type);
+        model.add_ob(y, ob_type);
+        model.add_mor(f, x.into(), y.into(), hom.clone());
+        assert!(model.validate().is_ok());
+
+        let endo = Path::single(ustr("Endofunctor"));
+        let fx = model.ob_act(x.into(), &endo);
+        assert_eq!(fx, SimpleOb::App(Box::new(x.into()), ustr("Endofunctor")));
+        assert!(model.has_ob(&fx));
+        assert_eq!(model.ob_type(&fx), ob_type);
+        let ffx = model.ob_act(fx.clone(), &endo);
+        assert_eq!(model.ob_act(x.into(), &th.compose2(endo.clone(), endo.clone())), ffx);
+        assert_eq!(model.ob_act(fx.clone(), &Path::Id(ob_type)), fx);
+
+        let hom_op = th.hom_op(endo.clone());
+        let ff = model.mor_act(f.into(), &hom_op);
+        assert!(model.has_mor(&ff));
+        assert_eq!(model.dom(&ff), fx);
+        assert_eq!(model.cod(&ff), model.ob_act(y.into(), &endo));
+        assert_eq!(model.mor_type(&ff), hom.clone());
+        assert_eq!(model.mor_act(Path::Id(x.into()), &hom_op), Path::Id(fx));
+        assert_eq!(model.mor_act(f.into(), &th.id_mor_op(hom)), f.into());
+
+        // Object operations act functorially on morphisms.
+        let endo2 = th.compose2(endo.clone(), endo);
+        let ffff = model.mor_act(ff, &hom_op);
+        assert_eq!(ffff, model.mor_act(f.into(), &th.hom_op(endo2.clone())));
+        assert_eq!(model.cod(&ffff), model.ob_act(y.into(), &endo2));
+    }
+
+    #[test]
+    fn validate_simple_dbl_model() {
+        let mut th = SimpleDblTheory::<char, char>::new();
+        th.add_ob_type('x');
+        th.add_ob_op('F', 'x', 'x');
+        th.add_ob_op_equation('i', PathEq::new(Path::pair('F', 'F'), Path::single('F')));
+        let hom = th.hom_type('x');
+        th.add_mor_op('η', hom.clone(), hom.clone(), Path::Id('x'), Path::single('F'));
+        let th = Arc::new(th);
+
+        let mut model = SimpleDblModel::<char, char>::new(th.clone());
+        model.add_ob('a', 'x');
+        model.add_mor('f', 'a'.into(), 'a'.into(), hom.clone());
+        assert!(model.validate().is_ok());
+        let fa = model.ob_act('a'.into(), &Path::single('F'));
+        assert_eq!(model.ob_act(fa.clone(), &Path::single('F')), fa);
+
+        let ηf = model.mor_act('f'.into(), &th.basic_mor_op('η'));
+        assert!(model.has_mor(&ηf));
+        assert_eq!(model.dom(&ηf), SimpleOb::Basic('a'));
+        assert_eq!(model.cod(&ηf), fa);
+        let hom_f = th.hom_op(Path::single('F'));
+        let ff = model.mor_act('f'.into(), &hom_f);
+        assert_eq!(model.mor_act(ff.clone(), &hom_f), ff);
+
+        model.add_mor('g', 'a'.into(), fa.clone(), hom.clone());
+        model.add_mor('h', 'a'.into(), 'b'.into(), hom);
+        assert_eq!(
+            model.validate().map_err(|errs| errs.into()),
+            Err(vec![InvalidDblModel::Cod('h')])
+        );
+    }
+
+    #[test]
+    fn modal_dbl_model_actions() {
+        let mut th = ModalDblTheory::<char, char>::new();
+        th.add_ob_type('x');
+        let x = ModeApp::new('x');
+        th.add_ob_op('F', x.clone(), x.clone());
+        let th = Arc::new(th);
+
+        let mut model = ModalDblModel::<char, char>::new(th.clone());
+        model.add_ob('a', x.clone());
+        model.add_ob('b', x.clone());
+        let xs = x.clone().apply(Modality::List);
+        let list = ModalOb::list(Modality::List, x.clone(), ['a'.into(), 'b'.into()]);
+        let empty = ModalOb::list(Modality::List, x.clone(), []);
+        model.add_mor('f', list.clone(), empty.clone(), th.hom_type(xs.clone()));
+        assert!(model.validate().is_ok());
+        assert_eq!(model.ob_type(&list), xs);
+        assert_eq!(model.ob_type(&empty), xs);
+        assert_eq!(model.mor_type(&Path::Id(empty.clone())), th.hom_type(xs.clone()));
+
+        let f = th.basic_ob_op('F');
+        let list_f = Path::single(ModeApp::new('F').apply(Modality::List));
+        let fs = ModalOb::list(
+            Modality::List,
+            x.clone(),
+            [ModalOb::from('a').app('F'), ModalOb::from('b').app('F')],
+        );
+        assert_eq!(model.ob_act(list.clone(), &list_f), fs);
+        assert_eq!(model.ob_act('a'.into(), &f), ModalOb::from('a').app('F'));
+        assert!(model.has_ob(&fs));
+
+        let ff = model.mor_act('f'.into(), &th.hom_op(list_f.clone()));
+        assert!(model.has_mor(&ff));
+        assert_eq!(model.dom(&ff), fs);
+        assert_eq!(model.cod(&ff), empty);
+        assert_eq!(model.mor_type(&ff), th.hom_type(xs.clone()));
+        assert_eq!(model.mor_act('f'.into(), &th.id_mor_op(th.hom_type(xs.clone()))), 'f'.into());
+
+        // A lifted operation acts formally on a basic object of list type.
+        model.add_ob('l', xs.clone());
+        let fl = model.ob_act('l'.into(), &list_f);
+        assert_eq!(fl, ModalOb::App(Box::new('l'.into()), ModeApp::new('F').apply(Modality::List)));
+        assert!(model.has_ob(&fl));
+        assert_eq!(model.ob_type(&fl), xs);
+
+        model.add_mor('g', 'a'.into(), list, th.hom_type(x));
+        assert_eq!(
+            model.validate().map_err(|errs| errs.into()),
+            Err(vec![InvalidDblModel::CodType('g')])
+        );
+    }
+
+    #[test]
+    fn petri_net_model() {
+        let model = sir_petri(Arc::new(th_symmetric_monoidal_category()));
+        let (s, i) = (ustr("S"), ustr("I"));
+        let infection: ModalMor<_, _> = ustr("infection").into();
+        let ob_type = ModeApp::new(ustr("Object"));
+        let tensor = |obs: Vec<Ustr>| {
+            let obs = obs.into_iter().map(ModalOb::Basic);
+            ModalOb::list(Modality::SymList, ob_type.clone(), obs).app(ustr("tensor"))
+        };
+        let dom = tensor(vec![s, i]);
+        assert_eq!(model.dom(&infection), dom);
+        assert_eq!(model.ob_type(&dom), ob_type);
+
+        // Symmetric lists are equal up to permutation.
+        assert_eq!(model.dom(&infection), tensor(vec![i, s]));
+        assert_ne!(model.dom(&infection), tensor(vec![i, i]));
+        assert_ne!(model.dom(&infection), tensor(vec![s]));
+
+        // A transition can have no inputs.
+        let mut model = model;
+        let birth = ustr("birth");
+        let th = model.theory_arc();
+        model.add_mor(birth, tensor(vec![]), tensor(vec![s]), th.hom_type(ob_type.clone()));
+        assert!(model.validate().is_ok());
+        assert_eq!(model.ob_type(&tensor(vec![])), ob_type);
+        let unit = Path::Id(model.dom(&birth.into()));
+        assert_eq!(model.mor_type(&unit), th.hom_type(ob_type));
     }
 }
@@ -2,7 +2,7 @@
 
 A **diagram** in a [model](super::model) is simply a
 [morphism](super::model_morphism) into that model. This includes the domain of
-that morphism, which is assumed to be a free model.
+that morphism, which need not be a free model.
 
 Diagrams are currently used primarily to represent instances of models from a
 fibered perspective, generalizing how a diagram in a category can be used to
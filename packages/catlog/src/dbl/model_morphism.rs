@@ -18,8 +18,10 @@ oplax.
   Section 7: Lax transformations
  */
 
-use std::collections::HashSet;
-use std::hash::Hash;
+use std::collections::{HashMap, HashSet, VecDeque, hash_map::Entry};
+use std::fmt::Debug;
+use std::hash::{BuildHasher, Hash};
+use std::marker::PhantomData;
 use std::sync::Arc;
 
 use derivative::Derivative;
@@ -31,12 +33,15 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "serde-wasm")]
 use tsify_next::Tsify;
 
-use crate::one::graph_algorithms::{bounded_simple_paths, simple_paths, spec_order};
+use crate::one::fin_category::MAX_COMPLETION_RULES;
+use crate::one::graph_algorithms::{bounded_simple_paths, simple_paths};
+use crate::one::rewriting::IndexedRewriteSystem;
 use crate::one::*;
 use crate::validate::{self, Validate};
 use crate::zero::{Column, HashColumn, Mapping, MutMapping};
 
 use super::model::*;
+use super::theory::DiscreteDblTheory;
 
 /** A mapping between models of a double theory.
 
@@ -82,6 +87,7 @@ axioms for a model morphism also become trivial.
 #[derive(Clone, Debug, Derivative)]
 #[derivative(Default(bound = ""))]
 #[derivative(PartialEq(bound = "DomId: Eq + Hash, CodId: PartialEq"))]
+#[derivative(Eq(bound = "DomId: Eq + Hash, CodId: Eq"))]
 pub struct DiscreteDblModelMapping<DomId, CodId> {
     ob_map: HashColumn<DomId, CodId>,
     mor_map: HashColumn<DomId, Path<CodId, CodId>>,
@@ -134,7 +140,8 @@ where
     submodel spanned by the image, generalizing the subcategory spanned by the
     image of a functor. Instead, this method constructs a "syntactical image"
     comprising all *basic* objects and morphisms appearing in the image of the
-    model morphism, possibly inside composites.
+    model morphism, possibly inside composites. Equations of the codomain model
+    involving only these basic objects and morphisms are also included.
      */
     pub fn syntactic_image<Cat>(
         &self,
@@ -145,10 +152,6 @@ where
         Cat::Ob: Hash,
         Cat::Mor: Hash,
     {
-        // TODO: For non-free models, we should filter the equations to those
-        // involving only generators that appear in the image.
-        assert!(cod.is_free(), "Codomain model should be free");
-
         let mut im = DiscreteDblModel::new(cod.theory_arc());
         for x in self.ob_map.values() {
             im.add_ob(x.clone(), cod.ob_type(x));
@@ -161,6 +164,11 @@ where
                 im.add_mor(e.clone(), x, y, cod.mor_generator_type(e));
             }
         }
+        for (key, eq) in cod.equations() {
+            if im.has_mor(&eq.lhs) && im.has_mor(&eq.rhs) {
+                im.add_equation(key, eq.clone());
+            }
+        }
         im
     }
 
@@ -174,7 +182,96 @@ where
         Cat::Ob: Hash,
         Cat::Mor: Hash,
     {
-        DiscreteDblModelMorphismFinder::new(dom, cod)
+        DblModelMorphismFinder::new(DiscreteDblModelSearch::new(dom, cod))
+    }
+
+    /** Composes this mapping with another one.
+
+    The image of a morphism generator under this mapping is a path in the
+    intermediate model. Each of its generators is replaced by its image under
+    the other mapping and the resulting path of paths is flattened in the
+    codomain model `cod`. The composite is defined wherever both mappings are.
+    Returns nothing if some flattened path is not well formed, which cannot
+    happen when the mappings are valid model morphisms.
+     */
+    pub fn compose<Cod2Id, Cat>(
+        &self,
+        other: &DiscreteDblModelMapping<CodId, Cod2Id>,
+        cod: &DiscreteDblModel<Cod2Id, Cat>,
+    ) -> Option<DiscreteDblModelMapping<DomId, Cod2Id>>
+    where
+        Cod2Id: Clone + Eq + Hash,
+        Cat: FgCategory,
+        Cat::Ob: Hash,
+        Cat::Mor: Hash,
+    {
+        let ob_map = self.ob_map.iter().filter_map(|(x, y)| Some((x, other.apply_ob(y)?)));
+        let mor_map = self.mor_map.iter().filter_map(|(e, path)| {
+            let paths = path
+                .clone()
+                .partial_map(|y| other.apply_ob(&y), |g| other.apply_basic_mor(&g))?;
+            Some(paths.flatten_in(cod.generating_graph()).map(|path| (e, path)))
+        });
+        Some(DiscreteDblModelMapping {
+            ob_map: ob_map.collect(),
+            mor_map: mor_map.collect::<Option<_>>()?,
+        })
+    }
+
+    /** Inverts the mapping, if it is an isomorphism between the given models.
+
+    Only isomorphisms that are bijective on generators are recognized: every
+    object generator of `dom` should be sent to a distinct object generator of
+    `cod`, and likewise every morphism generator to a distinct path of length
+    one, with all the generators of `cod` being hit. Assumes that the mapping
+    is a valid model morphism. When the models have equations, the inverse
+    should itself be validated to ensure that it preserves them.
+     */
+    pub fn inverse<Cat>(
+        &self,
+        dom: &DiscreteDblModel<DomId, Cat>,
+        cod: &DiscreteDblModel<CodId, Cat>,
+    ) -> Option<DiscreteDblModelMapping<CodId, DomId>>
+    where
+        Cat: FgCategory,
+        Cat::Ob: Hash,
+        Cat::Mor: Hash,
+    {
+        let mut inv: DiscreteDblModelMapping<CodId, DomId> = Default::default();
+        for x in dom.ob_generators() {
+            if inv.assign_ob(self.apply_ob(&x)?, x).is_some() {
+                return None;
+            }
+        }
+        for e in dom.mor_generators() {
+            let g = self.apply_basic_mor(&e)?.only()?;
+            if inv.assign_basic_mor(g, Path::single(e)).is_some() {
+                return None;
+            }
+        }
+        let is_onto = cod.ob_generators().all(|y| inv.is_ob_assigned(&y))
+            && cod.mor_generators().all(|g| inv.is_basic_mor_assigned(&g));
+        is_onto.then_some(inv)
+    }
+}
+
+impl<Id> DiscreteDblModelMapping<Id, Id>
+where
+    Id: Clone + Eq + Hash,
+{
+    /// Constructs the identity mapping on a model.
+    pub fn identity<Cat>(model: &DiscreteDblModel<Id, Cat>) -> Self
+    where
+        Cat: FgCategory,
+        Cat::Ob: Hash,
+        Cat::Mor: Hash,
+    {
+        let ob_map = model.ob_generators().map(|x| (x.clone(), x));
+        let mor_map = model.mor_generators().map(|e| (e.clone(), Path::single(e)));
+        DiscreteDblModelMapping {
+            ob_map: ob_map.collect(),
+            mor_map: mor_map.collect(),
+        }
     }
 }
 
@@ -234,15 +331,16 @@ where
     Cat::Ob: Hash,
     Cat::Mor: Hash,
 {
-    /// Iterates over failures of the mapping to be a model morphism.
+    /** Iterates over failures of the mapping to be a model morphism.
+
+    Equations in the domain are checked, by solving the word problem in the
+    codomain, only when the generators are mapped correctly.
+     */
     pub fn iter_invalid(
         &self,
     ) -> impl Iterator<Item = InvalidDblModelMorphism<DomId, DomId>> + 'a + use<'a, DomId, CodId, Cat>
     {
         let DblModelMorphism(mapping, dom, cod) = *self;
-        // TODO: We don't yet have the ability to solve word problems.
-        // Equations in the domain induce equations to check in the codomain.
-        assert!(dom.is_free(), "Domain model should be free");
 
         let ob_errors = dom.ob_generators().filter_map(|v| {
             if let Some(f_v) = mapping.apply_ob(&v) {
@@ -284,7 +382,20 @@ where
                 [InvalidDblModelMorphism::MissingMor(f)].to_vec()
             }
         });
-        ob_errors.chain(mor_errors)
+
+        let mut errs: Vec<_> = ob_errors.chain(mor_errors).collect();
+        if errs.is_empty() {
+            for (key, eq) in dom.equations() {
+                let lhs = mapping.apply_mor(&eq.lhs).expect("Generators should be mapped");
+                let rhs = mapping.apply_mor(&eq.rhs).expect("Generators should be mapped");
+                match cod.decide_eq(&lhs, &rhs) {
+                    Some(true) => {}
+                    Some(false) => errs.push(InvalidDblModelMorphism::Eq(key)),
+                    None => errs.push(InvalidDblModelMorphism::UndecidedEq(key)),
+                }
+            }
+        }
+        errs.into_iter()
     }
 
     /// Are morphism generators sent to simple composites of morphisms in the
@@ -370,6 +481,137 @@ where
     }
 }
 
+/** A mapping between models of a discrete double theory, bundled with its
+domain and codomain.
+
+Whereas a [`DblModelMorphism`] borrows its data in order to validate it, this
+struct owns the mapping and shares the models, so that it can serve as a
+morphism in the [category of models](DiscreteDblModelCategory).
+ */
+#[derive(Derivative)]
+#[derivative(Clone(bound = "Id: Clone"))]
+#[derivative(Debug(bound = "Id: Debug, Cat: Debug, Cat::Ob: Debug, Cat::Mor: Debug"))]
+#[derivative(PartialEq(bound = "Id: Eq + Hash"))]
+#[derivative(Eq(bound = "Id: Eq + Hash"))]
+pub struct DiscreteDblModelMor<Id, Cat: FgCategory> {
+    /// Mapping between the models.
+    pub mapping: DiscreteDblModelMapping<Id, Id>,
+
+    /// Domain model.
+    pub dom: Arc<DiscreteDblModel<Id, Cat>>,
+
+    /// Codomain model.
+    pub cod: Arc<DiscreteDblModel<Id, Cat>>,
+}
+
+impl<Id, Cat> DiscreteDblModelMor<Id, Cat>
+where
+    Id: Eq + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    /// Bundles a mapping with its domain and codomain models.
+    pub fn new(
+        mapping: DiscreteDblModelMapping<Id, Id>,
+        dom: Arc<DiscreteDblModel<Id, Cat>>,
+        cod: Arc<DiscreteDblModel<Id, Cat>>,
+    ) -> Self {
+        Self { mapping, dom, cod }
+    }
+
+    /// Borrows the data as a model morphism, such as for validation.
+    pub fn morphism(&self) -> DiscreteDblModelMorphism<'_, Id, Id, Cat> {
+        DblModelMorphism(&self.mapping, &self.dom, &self.cod)
+    }
+
+    /** Inverts the morphism, if it is an isomorphism.
+
+    Subject to the same restrictions as
+    [`DiscreteDblModelMapping::inverse`].
+     */
+    pub fn inverse(&self) -> Option<Self> {
+        let mapping = self.mapping.inverse(&self.dom, &self.cod)?;
+        Some(Self::new(mapping, self.cod.clone(), self.dom.clone()))
+    }
+}
+
+/** The category of models of a discrete double theory.
+
+Objects are models of a fixed theory, shared by reference counting, and
+morphisms are [bundled mappings](DiscreteDblModelMor) between them. Composing a
+chain of mappings, say an embedding of one model into a second followed by an
+embedding of the second into a third, collapses it into a single mapping by
+substituting paths for generators. Whether the result is a valid morphism is
+then checked by [`has_mor`](Category::has_mor). Morphisms are composable only
+when the codomain of one is the very model shared as the domain of the next.
+
+Equality of morphisms is syntactic: two mappings sending a generator to
+different paths are distinct even if the paths are equal in the codomain.
+ */
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct DiscreteDblModelCategory<Id, Cat: FgCategory> {
+    theory: Arc<DiscreteDblTheory<Cat>>,
+    id_type: PhantomData<Id>,
+}
+
+impl<Id, Cat: FgCategory> DiscreteDblModelCategory<Id, Cat> {
+    /// Creates the category of models of the given theory.
+    pub fn new(theory: Arc<DiscreteDblTheory<Cat>>) -> Self {
+        Self {
+            theory,
+            id_type: PhantomData,
+        }
+    }
+}
+
+impl<Id, Cat> Category for DiscreteDblModelCategory<Id, Cat>
+where
+    Id: Eq + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    type Ob = Arc<DiscreteDblModel<Id, Cat>>;
+    type Mor = DiscreteDblModelMor<Id, Cat>;
+
+    fn has_ob(&self, x: &Self::Ob) -> bool {
+        Arc::ptr_eq(&x.theory_arc(), &self.theory) && x.validate().is_ok()
+    }
+
+    fn has_mor(&self, f: &Self::Mor) -> bool {
+        self.has_ob(&f.dom) && self.has_ob(&f.cod) && f.morphism().validate().is_ok()
+    }
+
+    fn dom(&self, f: &Self::Mor) -> Self::Ob {
+        f.dom.clone()
+    }
+
+    fn cod(&self, f: &Self::Mor) -> Self::Ob {
+        f.cod.clone()
+    }
+
+    fn compose(&self, path: Path<Self::Ob, Self::Mor>) -> Self::Mor {
+        match path {
+            Path::Id(x) => {
+                DiscreteDblModelMor::new(DiscreteDblModelMapping::identity(&x), x.clone(), x)
+            }
+            Path::Seq(fs) => {
+                let NonEmpty { head, tail } = fs;
+                tail.into_iter().fold(head, |f, g| {
+                    assert!(Arc::ptr_eq(&f.cod, &g.dom), "Morphisms should be composable");
+                    let mapping = f
+                        .mapping
+                        .compose(&g.mapping, &g.cod)
+                        .expect("Composite of morphisms should be well formed");
+                    DiscreteDblModelMor::new(mapping, f.dom, g.cod)
+                })
+            }
+        }
+    }
+}
+
 /** An invalid assignment in a double model morphism defined explicitly by data.
  *
  * Note that, by specifying a model morphism via its action on generators, we
@@ -413,273 +655,1491 @@ pub enum InvalidDblModelMorphism<Ob, Mor> {
     /// Not functorial
     #[error("Morphism `{0}` has codomain not preserved by the mapping")]
     Cod(Mor),
-}
 
-/** Finds morphisms between two models of a discrete double theory.
+    /// Equation in the domain not preserved
+    #[error("Equation `{0}` is not preserved by the mapping")]
+    Eq(Mor),
 
-Morphisms are found using backtracking search. In general, there can be
-infinitely many morphisms between two models, so not all of them can be
-reported. The search is restricted to morphisms that send each basic morphism in
-the domain to a [simple path](crate::one::graph_algorithms::simple_paths) of
-basic morphisms in the codomain.
+    /// Cannot decide whether an equation is preserved
+    #[error("Cannot decide whether equation `{0}` is preserved by the mapping")]
+    UndecidedEq(Mor),
+}
+
+/** Finds morphisms between two models of a double theory.
+
+Morphisms are found using backtracking search over the generators of the domain
+model. The order in which the generators are assigned and the candidate values
+for each of them are determined by the `Search`, which also holds the mapping
+under construction, while the finder itself keeps the options, the stack of the
+search, and its limits. The finder is generic, so it is usually referred to
+through an alias for a particular kind of model, such as
+[`DiscreteDblModelMorphismFinder`] or [`DiscreteTabModelMorphismFinder`].
+
+The finder is an [iterator](Iterator) that performs the search lazily, yielding
+morphisms as they are found. The search can also be bounded in the number of
+morphisms found or in the number of assignments tried, or cancelled by a hook.
+Whether the search was stopped short is reported by
+[`is_truncated`](Self::is_truncated).
 */
-pub struct DiscreteDblModelMorphismFinder<'a, DomId, CodId, Cat: FgCategory> {
-    dom: &'a DiscreteDblModel<DomId, Cat>,
-    cod: &'a DiscreteDblModel<CodId, Cat>,
-    map: DiscreteDblModelMapping<DomId, CodId>,
-    results: Vec<DiscreteDblModelMapping<DomId, CodId>>,
-    var_order: Vec<GraphElem<DomId, DomId>>,
-    max_path_len: Option<usize>,
-    injective_ob: bool,
-    faithful: bool,
-    ob_init: HashColumn<DomId, CodId>,
-    mor_init: HashColumn<DomId, Path<CodId, CodId>>,
+pub struct DblModelMorphismFinder<'a, Search, DomId, CodId, Mor> {
+    search: Search,
+    options: SearchOptions<DomId, CodId, Mor>,
     ob_inv: HashColumn<CodId, DomId>,
+    stack: Vec<SearchFrame<DomId, CodId, Mor>>,
+    limits: SearchLimits<'a>,
 }
 
-impl<'a, DomId, CodId, Cat> DiscreteDblModelMorphismFinder<'a, DomId, CodId, Cat>
+impl<'a, Search, DomId, CodId, Mor> DblModelMorphismFinder<'a, Search, DomId, CodId, Mor>
 where
     DomId: Clone + Eq + Hash,
     CodId: Clone + Eq + Hash,
-    Cat: FgCategory,
-    Cat::Ob: Hash,
-    Cat::Mor: Hash,
+    Mor: Eq + Clone,
 {
-    fn new(dom: &'a DiscreteDblModel<DomId, Cat>, cod: &'a DiscreteDblModel<CodId, Cat>) -> Self {
-        assert!(
-            Arc::ptr_eq(&dom.theory_arc(), &cod.theory_arc()),
-            "Domain and codomain model should have the same theory"
-        );
-        assert!(dom.is_free(), "Domain model should be free");
-
-        // Order the variables of the CSP, which are the elements of the domain
-        // graph. Prefer vertices with high degree since they are more
-        // constrained. This is a version of the well known "most constrained
-        // variable" heuristic in CSP.
-        let dom_graph = dom.generating_graph();
-        let mut vertices: Vec<_> = dom_graph.vertices().collect();
-        vertices.sort_by_key(|v| std::cmp::Reverse(dom_graph.degree(v)));
-        let var_order = spec_order(dom_graph, vertices.into_iter());
-
+    fn new(search: Search) -> Self {
         Self {
-            dom,
-            cod,
-            map: Default::default(),
-            results: Default::default(),
-            var_order,
-            max_path_len: None,
-            injective_ob: false,
-            faithful: false,
-            ob_init: Default::default(),
-            mor_init: Default::default(),
+            search,
+            options: Default::default(),
             ob_inv: Default::default(),
+            stack: Vec::new(),
+            limits: Default::default(),
         }
     }
 
     /// Restrict the maximum length of the image of a generator.
     pub fn max_path_len(&mut self, n: usize) -> &mut Self {
-        self.max_path_len = Some(n);
+        self.options.max_path_len = Some(n);
         self
     }
 
     /// Restrict the search to monomorphisms between models.
     pub fn monic(&mut self) -> &mut Self {
-        self.injective_ob = true;
-        self.faithful = true;
+        self.options.injective_ob = true;
+        self.options.faithful = true;
         self
     }
 
     /// Restrict the search to model morphisms that are injective on objects.
     pub fn injective_ob(&mut self) -> &mut Self {
-        self.injective_ob = true;
+        self.options.injective_ob = true;
         self
     }
 
     /** Restrict the search to model morphisms that are faithful.
 
     A faithful morphism is an injective map on morphisms when restricted to any
-    domain/codomain pair of objects in the domain.
-
-    In future work, this will be efficiently checked for early search tree
-    pruning; however, this is currently enforced by filtering with
-    [is_free_simple_faithful](DiscreteDblModelMorphism::is_free_simple_faithful).
+    domain/codomain pair of objects in the domain. How faithfulness is enforced
+    during the search depends on the kind of model, as described for each alias
+    of the finder.
      */
     pub fn faithful(&mut self) -> &mut Self {
-        self.faithful = true;
+        self.options.faithful = true;
         self
     }
 
     /// Require morphisms to send object `ob` in domain to `val` in codomain.
     pub fn initialize_ob(&mut self, ob: DomId, val: CodId) -> &mut Self {
-        self.ob_init.set(ob, val);
+        self.options.ob_init.set(ob, val);
         self
     }
 
     /// Require morphisms to send morphism `m` in domain to `val` in codomain.
-    pub fn initialize_mor(&mut self, m: DomId, val: Path<CodId, CodId>) -> &mut Self {
-        self.mor_init.set(m, val);
+    pub fn initialize_mor(&mut self, m: DomId, val: Mor) -> &mut Self {
+        self.options.mor_init.set(m, val);
+        self
+    }
+
+    /// Stop the search after finding `n` morphisms.
+    pub fn max_results(&mut self, n: usize) -> &mut Self {
+        self.limits.max_results = Some(n);
         self
     }
 
-    /// Finds all morphisms.
-    pub fn find_all(&mut self) -> Vec<DiscreteDblModelMapping<DomId, CodId>> {
-        self.search(0);
-        std::mem::take(&mut self.results)
+    /** Stop the search after trying `n` assignments.
+
+    Each value tried for an object or morphism in the domain expands the search
+    tree by one node, whether or not the assignment leads to a morphism.
+     */
+    pub fn max_expansions(&mut self, n: usize) -> &mut Self {
+        self.limits.max_expansions = Some(n);
+        self
     }
 
-    fn search(&mut self, depth: usize) {
-        if depth >= self.var_order.len() {
-            if !self.faithful
-                || DblModelMorphism(&self.map, self.dom, self.cod).is_free_simple_faithful()
-            {
-                self.results.push(self.map.clone());
+    /** Stop the search as soon as the given function returns true.
+
+    The function is polled before every expansion of the search tree, so it
+    should be cheap to call, such as checking a flag or a deadline.
+     */
+    pub fn cancel_when(&mut self, f: impl FnMut() -> bool + 'a) -> &mut Self {
+        self.limits.cancel = Some(Box::new(f));
+        self
+    }
+
+    /// Was the search stopped by a limit or by cancellation before it finished?
+    pub fn is_truncated(&self) -> bool {
+        self.limits.truncated
+    }
+
+    /// Finds all morphisms, up to the limits set on the search.
+    pub fn find_all(&mut self) -> Vec<<Self as Iterator>::Item>
+    where
+        Self: Iterator,
+    {
+        self.by_ref().collect()
+    }
+
+    /** Advances the search to the next morphism.
+
+    Returns whether a morphism was found, in which case it is the mapping held
+    by the search.
+     */
+    fn advance(&mut self) -> bool
+    where
+        Search: MorphismSearch<DomId, CodId, Mor>,
+    {
+        if self.limits.finished {
+            return false;
+        }
+        if !self.limits.started {
+            if !self.limits.start() {
+                return false;
             }
-            return;
+            let Some(frame) = self.search.select(0, &self.options, &self.ob_inv) else {
+                self.limits.finish();
+                return self.search.accept(&self.options);
+            };
+            self.stack.push(frame);
         }
-        let var = &self.var_order[depth];
-        match var.clone() {
-            GraphElem::Vertex(x) => {
-                if self.ob_init.is_set(&x) {
-                    let y = self.ob_init.apply(&x).unwrap();
-                    let can_assign = self.assign_ob(x.clone(), y.clone());
-                    if can_assign {
-                        self.search(depth + 1);
-                        self.unassign_ob(x, y)
-                    }
-                } else {
-                    for y in self.cod.ob_generators_with_type(&self.dom.ob_type(&x)) {
-                        let can_assign = self.assign_ob(x.clone(), y.clone());
-                        if can_assign {
-                            self.search(depth + 1);
-                            self.unassign_ob(x.clone(), y)
-                        }
-                    }
-                }
+        while let Some(mut frame) = self.stack.pop() {
+            if frame.assigned {
+                self.unassign(&frame.var);
             }
-            GraphElem::Edge(m) => {
-                if self.mor_init.is_set(&m) {
-                    let path = self.mor_init.apply(&m).unwrap();
-                    self.map.assign_basic_mor(m, path);
-                    self.search(depth + 1);
-                } else {
-                    let mor_type = self.dom.mor_generator_type(&m);
-                    let w = self
-                        .map
-                        .apply_ob(&self.dom.mor_generator_dom(&m))
-                        .expect("Domain should already be assigned");
-                    let z = self
-                        .map
-                        .apply_ob(&self.dom.mor_generator_cod(&m))
-                        .expect("Codomain should already be assigned");
-
-                    let cod_graph = self.cod.generating_graph();
-                    for path in bounded_simple_paths(cod_graph, &w, &z, self.max_path_len) {
-                        if self.cod.mor_type(&path) == mor_type
-                            && !(self.faithful && path.is_empty())
-                        {
-                            self.map.assign_basic_mor(m.clone(), path);
-                            self.search(depth + 1);
-                        }
-                    }
-                }
+            let Some(value) = frame.untried.pop() else {
+                continue;
+            };
+            if !self.limits.expand() {
+                return false;
+            }
+            let depth = self.stack.len();
+            frame.assigned = self.assign(&frame.var, value);
+            let can_assign = frame.assigned;
+            self.stack.push(frame);
+            if !can_assign {
+                continue;
+            }
+            if let Some(frame) = self.search.select(depth + 1, &self.options, &self.ob_inv) {
+                self.stack.push(frame);
+            } else if self.search.accept(&self.options) {
+                let exhausted = self.stack.iter().all(|frame| frame.untried.is_empty());
+                self.limits.record_result(exhausted);
+                return true;
             }
         }
+        self.limits.finish();
+        false
     }
 
-    /// Attempt an object assignment, returning true iff successful.
-    fn assign_ob(&mut self, x: DomId, y: CodId) -> bool {
-        if self.injective_ob {
-            if let Some(y_inv) = self.ob_inv.get(&y) {
-                if *y_inv != x {
+    /// Attempt to assign a value to a variable, returning true iff successful.
+    fn assign(&mut self, var: &GraphElem<DomId, DomId>, value: GraphElem<CodId, Mor>) -> bool
+    where
+        Search: MorphismSearch<DomId, CodId, Mor>,
+    {
+        match (var.clone(), value) {
+            (GraphElem::Vertex(x), GraphElem::Vertex(y)) => {
+                if self.options.injective_ob && self.ob_inv.get(&y).is_some_and(|y_inv| *y_inv != x)
+                {
                     return false;
                 }
+                self.search.assign_ob(x.clone(), y.clone());
+                self.ob_inv.set(y, x);
+                true
+            }
+            (GraphElem::Edge(m), GraphElem::Edge(value)) => {
+                self.search.assign_mor(m, value, &self.options)
             }
+            _ => panic!("Value should be of the same kind as the variable"),
         }
-        self.map.assign_ob(x.clone(), y.clone());
-        self.ob_inv.set(y, x);
-        true
     }
 
-    /// Undo an object assignment.
-    fn unassign_ob(&mut self, _: DomId, y: CodId) {
-        self.ob_inv.unset(&y);
+    /// Undo the assignment of a variable.
+    fn unassign(&mut self, var: &GraphElem<DomId, DomId>)
+    where
+        Search: MorphismSearch<DomId, CodId, Mor>,
+    {
+        match var {
+            GraphElem::Vertex(x) => {
+                if let Some(y) = self.search.unassign_ob(x) {
+                    self.ob_inv.unset(&y);
+                }
+            }
+            GraphElem::Edge(m) => self.search.unassign_mor(m),
+        }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Options set on a search for model morphisms.
+#[derive(Derivative)]
+#[derivative(Default(bound = ""))]
+struct SearchOptions<DomId, CodId, Mor> {
+    max_path_len: Option<usize>,
+    injective_ob: bool,
+    faithful: bool,
+    ob_init: HashColumn<DomId, CodId>,
+    mor_init: HashColumn<DomId, Mor>,
+}
 
-    use crate::dbl::model::UstrDiscreteDblModel;
-    use crate::one::fin_category::FinMor;
-    use crate::stdlib::*;
-    use crate::validate::Validate;
+/** A backtracking search for model morphisms, as run by a
+[`DblModelMorphismFinder`].
 
-    use std::collections::HashMap;
-    use ustr::ustr;
+The search decides which variable to assign next and which values to try for
+it, and it holds the mapping under construction. The finder assigns and
+unassigns the variables, checking injectivity on objects itself.
+ */
+trait MorphismSearch<DomId, CodId, Mor> {
+    /** Selects the variable to assign at the given depth, returning its frame.
 
-    #[test]
-    fn discrete_model_mapping() {
-        let mut f: DiscreteDblModelMapping<_, _> = Default::default();
-        f.assign_ob('a', 'x');
-        f.assign_ob('b', 'y');
-        assert!(f.is_ob_assigned(&'a'));
-        assert_eq!(f.apply_ob(&'b'), Some('y'));
-        f.assign_basic_mor('f', Path::pair('p', 'q'));
-        f.assign_basic_mor('g', Path::pair('r', 's'));
-        assert!(f.is_mor_assigned(&Path::single('f')));
-        assert_eq!(f.apply_mor(&Path::pair('f', 'g')), Path::from_vec(vec!['p', 'q', 'r', 's']));
-    }
+    Returns nothing when all the variables have been assigned. The inverse of
+    the object mapping is passed for forward checking against injectivity.
+     */
+    fn select(
+        &mut self,
+        depth: usize,
+        options: &SearchOptions<DomId, CodId, Mor>,
+        ob_inv: &HashColumn<CodId, DomId>,
+    ) -> Option<SearchFrame<DomId, CodId, Mor>>;
 
-    #[test]
-    fn find_positive_loops() {
-        let th = Arc::new(th_signed_category());
-        let positive_loop = positive_loop(th.clone());
-        let pos = positive_loop.mor_generators().next().unwrap().into();
+    /// Assigns the mapping at an object.
+    fn assign_ob(&mut self, x: DomId, y: CodId);
 
-        let maps = DiscreteDblModelMapping::morphisms(&positive_loop, &positive_loop).find_all();
-        assert_eq!(maps.len(), 2);
-        let mors: Vec<_> = maps.into_iter().map(|mor| mor.apply_mor(&pos)).collect();
-        assert!(mors.iter().any(|mor| matches!(mor, Some(Path::Id(_)))));
-        assert!(mors.iter().any(|mor| matches!(mor, Some(Path::Seq(_)))));
+    /// Unassigns the mapping at an object, returning the previous assignment.
+    fn unassign_ob(&mut self, x: &DomId) -> Option<CodId>;
 
-        let maps = DiscreteDblModelMapping::morphisms(&positive_loop, &positive_loop)
-            .monic()
-            .find_all();
-        assert_eq!(maps.len(), 1);
-        assert!(matches!(maps[0].apply_mor(&pos), Some(Path::Seq(_))));
+    /// Attempt a morphism assignment, returning true iff successful.
+    fn assign_mor(
+        &mut self,
+        m: DomId,
+        value: Mor,
+        options: &SearchOptions<DomId, CodId, Mor>,
+    ) -> bool;
+
+    /// Unassigns the mapping at a morphism.
+    fn unassign_mor(&mut self, m: &DomId);
+
+    /// Should the mapping be reported, once all the variables are assigned?
+    fn accept(&self, _options: &SearchOptions<DomId, CodId, Mor>) -> bool {
+        true
     }
+}
 
-    /// The [simple path](crate::one::graph_algorithms::simple_paths) should
-    /// give identical results to hom search from a walking morphism (assuming
-    /// all the object/morphism types are the same).   
-    #[test]
-    fn find_simple_paths() {
-        let th = Arc::new(th_signed_category());
+/** Frame of a backtracking search for model morphisms.
 
-        let mut walking = UstrDiscreteDblModel::new(th.clone());
-        let (a, b) = (ustr("A"), ustr("B"));
-        walking.add_ob(a, ustr("Object"));
-        walking.add_ob(b, ustr("Object"));
-        walking.add_mor(ustr("f"), a, b, FinMor::Id(ustr("Object")));
-        let w = Path::single(ustr("f"));
+Holds a variable of the search, an object or morphism generator in the domain,
+together with the values not yet tried for it and whether it is currently
+assigned, so that the assignment can be undone.
+ */
+struct SearchFrame<DomId, Ob, Mor> {
+    var: GraphElem<DomId, DomId>,
+    untried: Vec<GraphElem<Ob, Mor>>,
+    assigned: bool,
+}
 
-        //     y         Graph with lots of cyclic paths.
-        //   ↗  ↘
-        // ↻x ⇆ z
-        let mut model = UstrDiscreteDblModel::new(th);
-        let (x, y, z) = (ustr("X"), ustr("Y"), ustr("Z"));
-        model.add_ob(x, ustr("Object"));
-        model.add_ob(y, ustr("Object"));
-        model.add_ob(z, ustr("Object"));
-        model.add_mor(ustr("xy"), x, y, FinMor::Id(ustr("Object")));
-        model.add_mor(ustr("yz"), y, z, FinMor::Id(ustr("Object")));
-        model.add_mor(ustr("zx"), z, x, FinMor::Id(ustr("Object")));
-        model.add_mor(ustr("xz"), x, z, FinMor::Id(ustr("Object")));
-        model.add_mor(ustr("xx"), x, x, FinMor::Id(ustr("Object")));
+impl<DomId, Ob, Mor> SearchFrame<DomId, Ob, Mor> {
+    fn new(
+        var: GraphElem<DomId, DomId>,
+        untried: impl Iterator<Item = GraphElem<Ob, Mor>>,
+    ) -> Self {
+        Self {
+            var,
+            untried: untried.collect(),
+            assigned: false,
+        }
+    }
+}
 
-        for i in model.ob_generators() {
-            for j in model.ob_generators() {
+/// Limits on a backtracking search, and the progress made against them.
+#[derive(Default)]
+struct SearchLimits<'a> {
+    max_results: Option<usize>,
+    max_expansions: Option<usize>,
+    cancel: Option<Box<dyn FnMut() -> bool + 'a>>,
+    results: usize,
+    expansions: usize,
+    started: bool,
+    finished: bool,
+    truncated: bool,
+}
+
+impl SearchLimits<'_> {
+    /// Starts the search, returning whether it can proceed.
+    fn start(&mut self) -> bool {
+        self.started = true;
+        if self.max_results == Some(0) {
+            self.truncate();
+        }
+        !self.finished
+    }
+
+    /// Records an expansion of the search tree, returning whether the search
+    /// can proceed.
+    fn expand(&mut self) -> bool {
+        self.expansions += 1;
+        if self.max_expansions.is_some_and(|n| self.expansions > n)
+            || self.cancel.as_mut().is_some_and(|cancel| cancel())
+        {
+            self.truncate();
+        }
+        !self.finished
+    }
+
+    /// Records a result, given whether the search space is now exhausted.
+    fn record_result(&mut self, exhausted: bool) {
+        self.results += 1;
+        if !exhausted && self.max_results.is_some_and(|n| self.results >= n) {
+            self.truncate();
+        }
+    }
+
+    fn truncate(&mut self) {
+        self.finished = true;
+        self.truncated = true;
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+/** Finds morphisms between two models of a discrete double theory.
+
+The search is restricted to morphisms that send each basic morphism in the
+domain to a [simple path](crate::one::graph_algorithms::simple_paths) of basic
+morphisms in the codomain, since in general there can be infinitely many
+morphisms between two models.
+
+The variables of the search, which are the elements of the domain graph, are
+ordered dynamically. Morphisms whose domain and codomain have been assigned are
+the most constrained variables, so they are assigned first; otherwise, an
+object with the fewest candidate values is chosen. The candidates for an object
+are pruned by forward checking against injectivity and by arc consistency with
+its neighbors, which must be reachable in the codomain by paths of allowed
+length, unless the morphism between them has an initial value, whose domain and
+codomain are then required. Candidate values for a morphism are cached by the
+images of its domain and codomain.
+
+When searching for faithful morphisms, a morphism is assigned only if the
+mapping stays faithful on the paths of already assigned morphisms, as in
+[is_free_simple_faithful](DiscreteDblModelMorphism::is_free_simple_faithful)
+but with paths compared up to the equations in each model, so that equal paths
+in the domain may have equal images but distinct ones may not. Only the paths
+through the newly assigned morphism need to be compared.
+
+The domain model need not be free. Equations in either model are completed into
+rewriting systems when the search is created, and a morphism is assigned only if
+every equation in the domain whose morphisms are all assigned is sent to an
+equation that holds in the codomain, as decided by comparing normal forms. If
+completion of the codomain equations fails within a fixed bound, paths in the
+codomain are compared syntactically, so that some morphisms may be missed; if
+completion of the domain equations fails, the same is true when searching for
+faithful morphisms.
+*/
+pub type DiscreteDblModelMorphismFinder<'a, DomId, CodId, Cat> = DblModelMorphismFinder<
+    'a,
+    DiscreteDblModelSearch<'a, DomId, CodId, Cat>,
+    DomId,
+    CodId,
+    Path<CodId, CodId>,
+>;
+
+/// Search for morphisms between two models of a discrete double theory, run by
+/// a [`DiscreteDblModelMorphismFinder`].
+pub struct DiscreteDblModelSearch<'a, DomId, CodId, Cat: FgCategory> {
+    dom: &'a DiscreteDblModel<DomId, Cat>,
+    cod: &'a DiscreteDblModel<CodId, Cat>,
+    map: DiscreteDblModelMapping<DomId, CodId>,
+    distances: HashMap<CodId, HashMap<CodId, usize>>,
+    mor_candidates: HashMap<(DomId, CodId, CodId), Vec<Path<CodId, CodId>>>,
+    dom_rewriting: Option<IndexedRewriteSystem<DomId, DomId>>,
+    cod_rewriting: Option<IndexedRewriteSystem<CodId, CodId>>,
+}
+
+impl<'a, DomId, CodId, Cat> DiscreteDblModelSearch<'a, DomId, CodId, Cat>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    fn new(dom: &'a DiscreteDblModel<DomId, Cat>, cod: &'a DiscreteDblModel<CodId, Cat>) -> Self {
+        assert!(
+            Arc::ptr_eq(&dom.theory_arc(), &cod.theory_arc()),
+            "Domain and codomain model should have the same theory"
+        );
+
+        Self {
+            dom,
+            cod,
+            map: Default::default(),
+            distances: Default::default(),
+            mor_candidates: Default::default(),
+            dom_rewriting: IndexedRewriteSystem::complete(
+                dom.generating_graph(),
+                dom.equations().map(|(_, eq)| eq),
+                MAX_COMPLETION_RULES,
+            ),
+            cod_rewriting: IndexedRewriteSystem::complete(
+                cod.generating_graph(),
+                cod.equations().map(|(_, eq)| eq),
+                MAX_COMPLETION_RULES,
+            ),
+        }
+    }
+
+    /// Makes a frame holding the candidate values for an object.
+    fn ob_frame(
+        &mut self,
+        x: DomId,
+        options: &SearchOptions<DomId, CodId, Path<CodId, CodId>>,
+        ob_inv: &HashColumn<CodId, DomId>,
+    ) -> SearchFrame<DomId, CodId, Path<CodId, CodId>> {
+        let candidates: Vec<_> = if let Some(y) = options.ob_init.apply(&x) {
+            vec![y]
+        } else {
+            self.cod.ob_generators_with_type(&self.dom.ob_type(&x)).collect()
+        };
+
+        // Forward checking: objects already in the image are excluded.
+        let mut candidates: Vec<_> = candidates
+            .into_iter()
+            .filter(|y| !(options.injective_ob && ob_inv.is_set(y)))
+            .collect();
+
+        // Arc consistency: the morphisms to and from assigned neighbors must
+        // have a possible value.
+        let dom_graph = self.dom.generating_graph();
+        for m in dom_graph.out_edges(&x) {
+            let z = dom_graph.tgt(&m);
+            if z == x {
+                candidates.retain(|y| self.admits_mor(&m, Some(y), Some(y), options));
+            } else {
+                let w = self.map.apply_ob(&z);
+                candidates.retain(|y| self.admits_mor(&m, Some(y), w.as_ref(), options));
+            }
+        }
+        for m in dom_graph.in_edges(&x) {
+            let w = self.map.apply_ob(&dom_graph.src(&m));
+            candidates.retain(|y| self.admits_mor(&m, w.as_ref(), Some(y), options));
+        }
+
+        candidates.reverse();
+        SearchFrame::new(GraphElem::Vertex(x), candidates.into_iter().map(GraphElem::Vertex))
+    }
+
+    /** Candidate values for a morphism whose domain and codomain have been
+    assigned, in the order in which they are to be tried.
+
+    The candidates depend only on the images of the domain and codomain, so
+    they are cached for each morphism and pair of images.
+     */
+    fn mor_candidates(
+        &mut self,
+        m: &DomId,
+        options: &SearchOptions<DomId, CodId, Path<CodId, CodId>>,
+    ) -> &[Path<CodId, CodId>] {
+        let w = self
+            .map
+            .apply_ob(&self.dom.mor_generator_dom(m))
+            .expect("Domain should already be assigned");
+        let z = self
+            .map
+            .apply_ob(&self.dom.mor_generator_cod(m))
+            .expect("Codomain should already be assigned");
+
+        let (dom, cod) = (self.dom, self.cod);
+        self.mor_candidates.entry((m.clone(), w, z)).or_insert_with_key(|(m, w, z)| {
+            let cod_graph = cod.generating_graph();
+            if let Some(path) = options.mor_init.apply(m) {
+                if path.src(cod_graph) == *w && path.tgt(cod_graph) == *z {
+                    vec![path]
+                } else {
+                    Vec::new()
+                }
+            } else {
+                let mor_type = dom.mor_generator_type(m);
+                bounded_simple_paths(cod_graph, w, z, options.max_path_len)
+                    .filter(|path| {
+                        cod.mor_type(path) == mor_type && !(options.faithful && path.is_empty())
+                    })
+                    .collect()
+            }
+        })
+    }
+
+    /** Can a morphism have a value, given the images of its domain and codomain?
+
+    The images may be partly unknown. A morphism with an initial value must be
+    sent to it, so its domain and codomain must be sent to those of the initial
+    value; otherwise, the image of the codomain must be reachable from that of
+    the domain.
+     */
+    fn admits_mor(
+        &mut self,
+        m: &DomId,
+        x: Option<&CodId>,
+        y: Option<&CodId>,
+        options: &SearchOptions<DomId, CodId, Path<CodId, CodId>>,
+    ) -> bool {
+        if let Some(path) = options.mor_init.apply(m) {
+            let cod_graph = self.cod.generating_graph();
+            x.is_none_or(|x| *x == path.src(cod_graph))
+                && y.is_none_or(|y| *y == path.tgt(cod_graph))
+        } else if let (Some(x), Some(y)) = (x, y) {
+            self.is_reachable(x, y, options)
+        } else {
+            true
+        }
+    }
+
+    /** Is there a path of allowed length between two objects in the codomain?
+
+    The path must be nonempty when searching for faithful morphisms, since an
+    empty path cannot be the image of a generator. Distances are computed by
+    breadth-first search from each source as needed, then cached.
+     */
+    fn is_reachable(
+        &mut self,
+        x: &CodId,
+        y: &CodId,
+        options: &SearchOptions<DomId, CodId, Path<CodId, CodId>>,
+    ) -> bool {
+        if x == y && !options.faithful {
+            return true;
+        }
+        let cod_graph = self.cod.generating_graph();
+        let distances = self.distances.entry(x.clone()).or_insert_with(|| {
+            // Distances along nonempty paths, so that the distance from a
+            // vertex to itself is the length of the shortest cycle through it.
+            let mut distances = HashMap::new();
+            let mut queue: VecDeque<_> =
+                cod_graph.out_edges(x).map(|e| (cod_graph.tgt(&e), 1)).collect();
+            while let Some((v, d)) = queue.pop_front() {
+                if distances.contains_key(&v) {
+                    continue;
+                }
+                queue.extend(cod_graph.out_edges(&v).map(|e| (cod_graph.tgt(&e), d + 1)));
+                distances.insert(v, d);
+            }
+            distances
+        });
+        distances.get(y).is_some_and(|d| options.max_path_len.is_none_or(|n| *d <= n))
+    }
+
+    /** Does the mapping preserve the equations involving a newly assigned
+    morphism?
+
+    Only equations whose morphisms are all assigned are checked, since the
+    others will be checked when their last morphism is assigned.
+     */
+    fn preserves_equations(&self, m: &DomId) -> bool {
+        self.dom.equations().all(|(_, eq)| {
+            if !eq.lhs.iter().chain(eq.rhs.iter()).any(|e| e == m) {
+                return true;
+            }
+            match (self.map.apply_mor(&eq.lhs), self.map.apply_mor(&eq.rhs)) {
+                (Some(lhs), Some(rhs)) => self.cod_normal_form(lhs) == self.cod_normal_form(rhs),
+                _ => true,
+            }
+        })
+    }
+
+    /** Does the mapping stay faithful after assigning a morphism?
+
+    Only the simple paths through the newly assigned morphism `m` are compared
+    with the other paths between the same objects, since any other two paths
+    were compared when the last of their morphisms was assigned. Paths are
+    compared by their normal forms, so that two paths in the domain may have the
+    same image only if they are equal.
+     */
+    fn is_faithful_at(&self, m: &DomId) -> bool {
+        let assigned = AssignedGraph {
+            graph: self.dom.generating_graph(),
+            map: &self.map,
+        };
+        let (src, tgt) = (assigned.src(m), assigned.tgt(m));
+        let avoids_m = |path: &Path<DomId, DomId>| path.iter().all(|e| e != m);
+        let suffixes: Vec<(DomId, Vec<_>)> = assigned
+            .vertices()
+            .map(|y| {
+                let paths = simple_paths(&assigned, &tgt, &y).filter(avoids_m).collect();
+                (y, paths)
+            })
+            .collect();
+
+        for x in assigned.vertices() {
+            let prefixes: Vec<_> = simple_paths(&assigned, &x, &src).filter(avoids_m).collect();
+            for (y, suffixes) in suffixes.iter() {
+                let new_paths: Vec<_> = prefixes
+                    .iter()
+                    .flat_map(|p| {
+                        suffixes.iter().filter_map(move |q| {
+                            let edges = p.iter().chain([m]).chain(q.iter()).cloned();
+                            Path::collect(edges).filter(|path| path.is_simple())
+                        })
+                    })
+                    .collect();
+                if new_paths.is_empty() {
+                    continue;
+                }
+                let mut seen: HashMap<_, _> = simple_paths(&assigned, &x, y)
+                    .filter(avoids_m)
+                    .filter_map(|path| {
+                        let f_path = self.map.apply_mor(&path)?;
+                        Some((self.cod_normal_form(f_path), self.dom_normal_form(path)))
+                    })
+                    .collect();
+                for path in new_paths {
+                    let Some(f_path) = self.map.apply_mor(&path) else {
+                        continue;
+                    };
+                    let path = self.dom_normal_form(path);
+                    match seen.entry(self.cod_normal_form(f_path)) {
+                        Entry::Occupied(entry) if *entry.get() != path => return false,
+                        Entry::Occupied(_) => {}
+                        Entry::Vacant(entry) => {
+                            entry.insert(path);
+                        }
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    /// Reduces a path in the domain to normal form, if possible.
+    fn dom_normal_form(&self, path: Path<DomId, DomId>) -> Path<DomId, DomId> {
+        match &self.dom_rewriting {
+            Some(rewriting) => rewriting.normalize(path),
+            None => path,
+        }
+    }
+
+    /// Reduces a path in the codomain to normal form, if possible.
+    fn cod_normal_form(&self, path: Path<CodId, CodId>) -> Path<CodId, CodId> {
+        match &self.cod_rewriting {
+            Some(rewriting) => rewriting.normalize(path),
+            None => path,
+        }
+    }
+}
+
+impl<DomId, CodId, Cat> MorphismSearch<DomId, CodId, Path<CodId, CodId>>
+    for DiscreteDblModelSearch<'_, DomId, CodId, Cat>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    fn select(
+        &mut self,
+        _depth: usize,
+        options: &SearchOptions<DomId, CodId, Path<CodId, CodId>>,
+        ob_inv: &HashColumn<CodId, DomId>,
+    ) -> Option<SearchFrame<DomId, CodId, Path<CodId, CodId>>> {
+        let mut best_mor = None;
+        for m in self.dom.mor_generators() {
+            if self.map.is_basic_mor_assigned(&m)
+                || !self.map.is_ob_assigned(&self.dom.mor_generator_dom(&m))
+                || !self.map.is_ob_assigned(&self.dom.mor_generator_cod(&m))
+            {
+                continue;
+            }
+            let n = self.mor_candidates(&m, options).len();
+            if best_mor.as_ref().is_none_or(|(_, best_n)| n < *best_n) {
+                best_mor = Some((m, n));
+            }
+            if n == 0 {
+                break; // Fail first.
+            }
+        }
+        if let Some((m, _)) = best_mor {
+            let values = self.mor_candidates(&m, options).iter().rev().cloned();
+            return Some(SearchFrame::new(GraphElem::Edge(m), values.map(GraphElem::Edge)));
+        }
+
+        let mut best: Option<SearchFrame<_, _, _>> = None;
+        let dom_graph = self.dom.generating_graph();
+        let mut best_degree = 0;
+        for x in self.dom.ob_generators() {
+            if self.map.is_ob_assigned(&x) {
+                continue;
+            }
+            let degree = dom_graph.degree(&x);
+            let frame = self.ob_frame(x, options, ob_inv);
+            if frame.untried.is_empty() {
+                return Some(frame); // Fail first.
+            }
+            if best.as_ref().is_none_or(|best| {
+                (frame.untried.len(), std::cmp::Reverse(degree))
+                    < (best.untried.len(), std::cmp::Reverse(best_degree))
+            }) {
+                best = Some(frame);
+                best_degree = degree;
+            }
+        }
+        best
+    }
+
+    fn assign_ob(&mut self, x: DomId, y: CodId) {
+        self.map.assign_ob(x, y);
+    }
+
+    fn unassign_ob(&mut self, x: &DomId) -> Option<CodId> {
+        self.map.unassign_ob(x)
+    }
+
+    fn assign_mor(
+        &mut self,
+        m: DomId,
+        path: Path<CodId, CodId>,
+        options: &SearchOptions<DomId, CodId, Path<CodId, CodId>>,
+    ) -> bool {
+        self.map.assign_basic_mor(m.clone(), path);
+        if !self.preserves_equations(&m) || (options.faithful && !self.is_faithful_at(&m)) {
+            self.map.unassign_basic_mor(&m);
+            false
+        } else {
+            true
+        }
+    }
+
+    fn unassign_mor(&mut self, m: &DomId) {
+        self.map.unassign_basic_mor(m);
+    }
+}
+
+/// Subgraph of a generating graph spanned by the generators assigned by a mapping.
+struct AssignedGraph<'a, G, DomId, CodId> {
+    graph: &'a G,
+    map: &'a DiscreteDblModelMapping<DomId, CodId>,
+}
+
+impl<G, DomId, CodId> Graph for AssignedGraph<'_, G, DomId, CodId>
+where
+    G: FinGraph<V = DomId, E = DomId>,
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+{
+    type V = DomId;
+    type E = DomId;
+
+    fn has_vertex(&self, x: &DomId) -> bool {
+        self.graph.has_vertex(x) && self.map.is_ob_assigned(x)
+    }
+    fn has_edge(&self, m: &DomId) -> bool {
+        self.graph.has_edge(m) && self.map.is_basic_mor_assigned(m)
+    }
+    fn src(&self, m: &DomId) -> DomId {
+        self.graph.src(m)
+    }
+    fn tgt(&self, m: &DomId) -> DomId {
+        self.graph.tgt(m)
+    }
+}
+
+impl<G, DomId, CodId> FinGraph for AssignedGraph<'_, G, DomId, CodId>
+where
+    G: FinGraph<V = DomId, E = DomId>,
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+{
+    fn vertices(&self) -> impl Iterator<Item = DomId> {
+        self.graph.vertices().filter(|x| self.map.is_ob_assigned(x))
+    }
+    fn edges(&self) -> impl Iterator<Item = DomId> {
+        self.graph.edges().filter(|m| self.map.is_basic_mor_assigned(m))
+    }
+    fn in_edges(&self, x: &DomId) -> impl Iterator<Item = DomId> {
+        self.graph.in_edges(x).filter(|m| self.map.is_basic_mor_assigned(m))
+    }
+    fn out_edges(&self, x: &DomId) -> impl Iterator<Item = DomId> {
+        self.graph.out_edges(x).filter(|m| self.map.is_basic_mor_assigned(m))
+    }
+}
+
+impl<DomId, CodId, Cat> Iterator for DiscreteDblModelMorphismFinder<'_, DomId, CodId, Cat>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    type Item = DiscreteDblModelMapping<DomId, CodId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance().then(|| self.search.map.clone())
+    }
+}
+
+/** A mapping between models of a discrete tabulator theory.
+
+The mapping is specified on the object and morphism generators of the domain
+model. It is extended to tabulated objects and to commutative squares by
+functoriality, where a square is sent to a square only when its sides are sent
+to single edges.
+ */
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+#[derivative(PartialEq(bound = "DomId: Eq + Hash, CodId: PartialEq"))]
+pub struct DiscreteTabModelMapping<DomId, CodId> {
+    ob_map: HashColumn<DomId, TabOb<CodId, CodId>>,
+    mor_map: HashColumn<DomId, TabMor<CodId, CodId>>,
+}
+
+impl<DomId, CodId> DiscreteTabModelMapping<DomId, CodId>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+{
+    /// Applies the mapping at a basic morphism in the domain model.
+    pub fn apply_basic_mor(&self, e: &DomId) -> Option<TabMor<CodId, CodId>> {
+        self.mor_map.apply(e)
+    }
+
+    /// Is the mapping defined at a basic morphism?
+    pub fn is_basic_mor_assigned(&self, e: &DomId) -> bool {
+        self.mor_map.is_set(e)
+    }
+
+    /// Assigns the mapping at an object, returning the previous assignment.
+    pub fn assign_ob(&mut self, x: DomId, y: TabOb<CodId, CodId>) -> Option<TabOb<CodId, CodId>> {
+        self.ob_map.set(x, y)
+    }
+
+    /// Assigns the mapping at a basic morphism, returning the previous assignment.
+    pub fn assign_basic_mor(
+        &mut self,
+        e: DomId,
+        n: TabMor<CodId, CodId>,
+    ) -> Option<TabMor<CodId, CodId>> {
+        self.mor_map.set(e, n)
+    }
+
+    /// Unassigns the mapping at an object, returning the previous assignment.
+    pub fn unassign_ob(&mut self, x: &DomId) -> Option<TabOb<CodId, CodId>> {
+        self.ob_map.unset(x)
+    }
+
+    /// Unassigns the mapping a basic morphism, returning the previous assignment.
+    pub fn unassign_basic_mor(&mut self, e: &DomId) -> Option<TabMor<CodId, CodId>> {
+        self.mor_map.unset(e)
+    }
+
+    /// Applies the mapping at an edge in the domain model.
+    fn apply_edge(&self, edge: TabEdge<DomId, DomId>) -> Option<TabMor<CodId, CodId>> {
+        match edge {
+            TabEdge::Basic(e) => self.apply_basic_mor(&e),
+            TabEdge::Square {
+                dom,
+                cod,
+                pre,
+                post,
+            } => {
+                let square = TabEdge::Square {
+                    dom: Box::new(self.apply_mor(&dom)?),
+                    cod: Box::new(self.apply_mor(&cod)?),
+                    pre: Box::new(self.apply_edge(*pre)?.only()?),
+                    post: Box::new(self.apply_edge(*post)?.only()?),
+                };
+                Some(Path::single(square))
+            }
+        }
+    }
+
+    /// Finder of morphisms between two models of a discrete tabulator theory.
+    pub fn morphisms<'a, ThId, S>(
+        dom: &'a DiscreteTabModel<DomId, ThId, S>,
+        cod: &'a DiscreteTabModel<CodId, ThId, S>,
+    ) -> DiscreteTabModelMorphismFinder<'a, DomId, CodId, ThId, S>
+    where
+        ThId: Clone + Eq + Hash,
+        S: BuildHasher,
+    {
+        DblModelMorphismFinder::new(DiscreteTabModelSearch::new(dom, cod))
+    }
+}
+
+impl<DomId, CodId> DblModelMapping for DiscreteTabModelMapping<DomId, CodId>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+{
+    type DomOb = TabOb<DomId, DomId>;
+    type DomMor = TabMor<DomId, DomId>;
+    type CodOb = TabOb<CodId, CodId>;
+    type CodMor = TabMor<CodId, CodId>;
+
+    fn apply_ob(&self, x: &Self::DomOb) -> Option<Self::CodOb> {
+        match x {
+            TabOb::Basic(v) => self.ob_map.apply(v),
+            TabOb::Tabulated(m) => Some(TabOb::Tabulated(Box::new(self.apply_mor(m)?))),
+        }
+    }
+
+    fn apply_mor(&self, m: &Self::DomMor) -> Option<Self::CodMor> {
+        m.clone()
+            .partial_map(|x| self.apply_ob(&x), |edge| self.apply_edge(edge))
+            .map(|path| path.flatten())
+    }
+}
+
+/// A morphism between models of a discrete tabulator theory.
+pub type DiscreteTabModelMorphism<'a, DomId, CodId, ThId, S> = DblModelMorphism<
+    'a,
+    DiscreteTabModelMapping<DomId, CodId>,
+    DiscreteTabModel<DomId, ThId, S>,
+    DiscreteTabModel<CodId, ThId, S>,
+>;
+
+impl<'a, DomId, CodId, ThId, S> DiscreteTabModelMorphism<'a, DomId, CodId, ThId, S>
+where
+    DomId: Eq + Clone + Hash,
+    CodId: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    /// Iterates over failures of the mapping to be a model morphism.
+    pub fn iter_invalid(
+        &self,
+    ) -> impl Iterator<Item = InvalidDblModelMorphism<DomId, DomId>> + 'a + use<'a, DomId, CodId, ThId, S>
+    {
+        let DblModelMorphism(mapping, dom, cod) = *self;
+
+        let ob_errors = dom.ob_generators().filter_map(|v| {
+            let x = TabOb::Basic(v.clone());
+            if let Some(f_x) = mapping.apply_ob(&x) {
+                if !cod.has_ob(&f_x) {
+                    Some(InvalidDblModelMorphism::Ob(v))
+                } else if dom.ob_type(&x) != cod.ob_type(&f_x) {
+                    Some(InvalidDblModelMorphism::ObType(v))
+                } else {
+                    None
+                }
+            } else {
+                Some(InvalidDblModelMorphism::MissingOb(v))
+            }
+        });
+
+        let mor_errors = dom.mor_generators().flat_map(|f| {
+            if let Some(f_f) = mapping.apply_basic_mor(&f) {
+                if !cod.has_mor(&f_f) {
+                    [InvalidDblModelMorphism::Mor(f)].to_vec()
+                } else {
+                    let dom_f = mapping.apply_ob(&dom.mor_generator_dom(&f));
+                    let cod_f = mapping.apply_ob(&dom.mor_generator_cod(&f));
+
+                    let mut errs = vec![];
+                    if Some(cod.dom(&f_f)) != dom_f {
+                        errs.push(InvalidDblModelMorphism::Dom(f.clone()));
+                    }
+                    if Some(cod.cod(&f_f)) != cod_f {
+                        errs.push(InvalidDblModelMorphism::Cod(f.clone()));
+                    }
+                    if dom.mor_generator_type(&f) != cod.mor_type(&f_f) {
+                        errs.push(InvalidDblModelMorphism::MorType(f));
+                    }
+                    errs
+                }
+            } else {
+                [InvalidDblModelMorphism::MissingMor(f)].to_vec()
+            }
+        });
+
+        let mut errs: Vec<_> = ob_errors.chain(mor_errors).collect();
+        if errs.is_empty() {
+            for (key, eq) in dom.equations() {
+                // Squares are mapped only when their sides are sent to edges.
+                let (Some(lhs), Some(rhs)) =
+                    (mapping.apply_mor(&eq.lhs), mapping.apply_mor(&eq.rhs))
+                else {
+                    errs.push(InvalidDblModelMorphism::UndecidedEq(key));
+                    continue;
+                };
+                match cod.decide_eq(&lhs, &rhs) {
+                    Some(true) => {}
+                    Some(false) => errs.push(InvalidDblModelMorphism::Eq(key)),
+                    None => errs.push(InvalidDblModelMorphism::UndecidedEq(key)),
+                }
+            }
+        }
+        errs.into_iter()
+    }
+
+    /// Are morphism generators sent to simple paths of basic morphisms in the
+    /// codomain?
+    fn is_simple(&self) -> bool {
+        let DblModelMorphism(mapping, dom, _) = *self;
+        dom.mor_generators().all(|e| {
+            mapping.apply_basic_mor(&e).is_none_or(|p| {
+                p.iter().all(|edge| matches!(edge, TabEdge::Basic(_))) && p.is_simple()
+            })
+        })
+    }
+
+    /// Is the model morphism injective on objects?
+    pub fn is_injective_objects(&self) -> bool {
+        let DblModelMorphism(mapping, dom, _) = *self;
+        let mut seen_obs: HashSet<_> = HashSet::new();
+        for x in dom.ob_generators() {
+            if mapping.apply_ob(&TabOb::Basic(x)).is_some_and(|f_x| !seen_obs.insert(f_x)) {
+                return false; // not monic
+            }
+        }
+        true
+    }
+
+    /** Is the model morphism faithful?
+
+    As for the [analogous method](DiscreteDblModelMorphism::is_free_simple_faithful)
+    for discrete double theories, morphism generators must be sent to simple
+    paths of basic morphisms. Only the paths in the
+    [generating graph](DiscreteTabModel::generating_graph) of the domain are
+    compared.
+
+    The models need not be free, but then [deciding](DiscreteTabModel::decide_eq)
+    equality of morphisms can fail, so the check is conservative: two paths
+    whose images are not known to be distinct must be known to be equal. For
+    free models, this is the same as requiring the images to be distinct.
+     */
+    pub fn is_free_simple_faithful(&self) -> bool {
+        let DblModelMorphism(mapping, dom, cod) = *self;
+
+        assert!(self.is_simple(), "Morphism assignments should be simple");
+
+        let dom_graph = dom.generating_graph();
+        for x in dom_graph.vertices() {
+            for y in dom_graph.vertices() {
+                let mut seen: Vec<(TabMor<DomId, DomId>, TabMor<CodId, CodId>)> = Vec::new();
+                for path in simple_paths(&dom_graph, &x, &y) {
+                    let path = path.map(|x| x, TabEdge::Basic);
+                    let Some(f_path) = mapping.apply_mor(&path) else {
+                        continue;
+                    };
+                    let collides = seen.iter().any(|(other, f_other)| {
+                        cod.decide_eq(&f_path, f_other) != Some(false)
+                            && dom.decide_eq(&path, other) != Some(true)
+                    });
+                    if collides {
+                        return false; // not faithful
+                    }
+                    seen.push((path, f_path));
+                }
+            }
+        }
+        true
+    }
+
+    /// Is the model morphism a monomorphism?
+    pub fn is_free_simple_monic(&self) -> bool {
+        self.is_injective_objects() && self.is_free_simple_faithful()
+    }
+}
+
+impl<DomId, CodId, ThId, S> Validate for DiscreteTabModelMorphism<'_, DomId, CodId, ThId, S>
+where
+    DomId: Eq + Clone + Hash,
+    CodId: Eq + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type ValidationError = InvalidDblModelMorphism<DomId, DomId>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+/** Finds morphisms between two models of a discrete tabulator theory.
+
+Like its [counterpart](DiscreteDblModelMorphismFinder) for discrete double
+theories, the finder sends each basic morphism in the domain to a simple path
+of basic morphisms in the codomain. A basic morphism is assigned only once its
+domain and codomain are determined, which, for a morphism into or out of a
+tabulated object, means after the morphisms being tabulated. A morphism whose
+domain or codomain involves the morphism itself, such as a link into its own
+tabulator, is never determined, so no morphisms out of such a model are found.
+
+The domain model need not be free. A morphism is assigned only if every
+equation in the domain whose sides can both be mapped is sent to an equation
+that [holds](DiscreteTabModel::decide_eq) in the codomain, and a mapping is
+reported only if all the equations are preserved. When searching for faithful
+morphisms, mappings are filtered by
+[is_free_simple_faithful](DiscreteTabModelMorphism::is_free_simple_faithful).
+*/
+pub type DiscreteTabModelMorphismFinder<'a, DomId, CodId, ThId, S> = DblModelMorphismFinder<
+    'a,
+    DiscreteTabModelSearch<'a, DomId, CodId, ThId, S>,
+    DomId,
+    CodId,
+    TabMor<CodId, CodId>,
+>;
+
+/// Search for morphisms between two models of a discrete tabulator theory, run
+/// by a [`DiscreteTabModelMorphismFinder`].
+pub struct DiscreteTabModelSearch<'a, DomId, CodId, ThId, S> {
+    dom: &'a DiscreteTabModel<DomId, ThId, S>,
+    cod: &'a DiscreteTabModel<CodId, ThId, S>,
+    cod_graph: HashGraph<TabOb<CodId, CodId>, CodId>,
+    map: DiscreteTabModelMapping<DomId, CodId>,
+    var_order: Vec<GraphElem<DomId, DomId>>,
+    is_circular: bool,
+}
+
+impl<'a, DomId, CodId, ThId, S> DiscreteTabModelSearch<'a, DomId, CodId, ThId, S>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+    ThId: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    fn new(
+        dom: &'a DiscreteTabModel<DomId, ThId, S>,
+        cod: &'a DiscreteTabModel<CodId, ThId, S>,
+    ) -> Self {
+        assert!(
+            Arc::ptr_eq(&dom.theory_arc(), &cod.theory_arc()),
+            "Domain and codomain model should have the same theory"
+        );
+
+        // Visit the objects in order of decreasing degree, as for discrete
+        // models. After each object, visit every morphism whose domain and
+        // codomain are then determined. The identity mapping on the generators
+        // visited so far tracks what is determined.
+        let dom_graph = dom.generating_graph();
+        let mut obs: Vec<_> = dom.ob_generators().collect();
+        obs.sort_by_key(|x| std::cmp::Reverse(dom_graph.degree(&TabOb::Basic(x.clone()))));
+
+        let mut visited: DiscreteTabModelMapping<DomId, DomId> = Default::default();
+        let mut pending: Vec<_> = dom.mor_generators().collect();
+        let mut var_order = Vec::new();
+        for x in obs {
+            visited.assign_ob(x.clone(), TabOb::Basic(x.clone()));
+            var_order.push(GraphElem::Vertex(x));
+            loop {
+                let (ready, rest): (Vec<_>, Vec<_>) = pending.into_iter().partition(|m| {
+                    visited.is_ob_assigned(&dom.mor_generator_dom(m))
+                        && visited.is_ob_assigned(&dom.mor_generator_cod(m))
+                });
+                pending = rest;
+                if ready.is_empty() {
+                    break;
+                }
+                for m in ready {
+                    visited.assign_basic_mor(m.clone(), TabEdge::Basic(m.clone()).into());
+                    var_order.push(GraphElem::Edge(m));
+                }
+            }
+        }
+        // Morphisms whose domain or codomain depends on themselves, such as a
+        // morphism into its own tabulator, are never determined. Nothing is
+        // found in that case, since such morphisms cannot be assigned.
+        let is_circular = !pending.is_empty();
+        if is_circular {
+            var_order.clear();
+        }
+
+        Self {
+            dom,
+            cod,
+            cod_graph: cod.generating_graph(),
+            map: Default::default(),
+            var_order,
+            is_circular,
+        }
+    }
+
+    /** Is an equation in the domain sent to an equation in the codomain?
+
+    Returns nothing when a side of the equation cannot be mapped, either because
+    it involves an unassigned generator or because it has a square whose sides
+    are not sent to single edges.
+     */
+    fn preserves_equation(&self, eq: &TabPathEq<DomId, DomId>) -> Option<bool> {
+        let lhs = self.map.apply_mor(&eq.lhs)?;
+        let rhs = self.map.apply_mor(&eq.rhs)?;
+        Some(self.cod.decide_eq(&lhs, &rhs) == Some(true))
+    }
+}
+
+impl<DomId, CodId, ThId, S> MorphismSearch<DomId, CodId, TabMor<CodId, CodId>>
+    for DiscreteTabModelSearch<'_, DomId, CodId, ThId, S>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+    ThId: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    fn select(
+        &mut self,
+        depth: usize,
+        options: &SearchOptions<DomId, CodId, TabMor<CodId, CodId>>,
+        _ob_inv: &HashColumn<CodId, DomId>,
+    ) -> Option<SearchFrame<DomId, CodId, TabMor<CodId, CodId>>> {
+        let var = self.var_order.get(depth)?.clone();
+        let mut values: Vec<_> = match &var {
+            GraphElem::Vertex(x) => {
+                if let Some(y) = options.ob_init.apply(x) {
+                    vec![GraphElem::Vertex(y)]
+                } else {
+                    let ob_type = self.dom.ob_generator_type(x);
+                    self.cod.ob_generators_with_type(&ob_type).map(GraphElem::Vertex).collect()
+                }
+            }
+            GraphElem::Edge(m) => {
+                let w = self
+                    .map
+                    .apply_ob(&self.dom.mor_generator_dom(m))
+                    .expect("Domain should already be assigned");
+                let z = self
+                    .map
+                    .apply_ob(&self.dom.mor_generator_cod(m))
+                    .expect("Codomain should already be assigned");
+
+                if let Some(path) = options.mor_init.apply(m) {
+                    if self.cod.dom(&path) == w && self.cod.cod(&path) == z {
+                        vec![GraphElem::Edge(path)]
+                    } else {
+                        Vec::new()
+                    }
+                } else {
+                    let mor_type = self.dom.mor_generator_type(m);
+                    bounded_simple_paths(&self.cod_graph, &w, &z, options.max_path_len)
+                        .map(|path| path.map(|x| x, TabEdge::Basic))
+                        .filter(|path| {
+                            self.cod.mor_type(path) == mor_type
+                                && !(options.faithful && path.is_empty())
+                        })
+                        .map(GraphElem::Edge)
+                        .collect()
+                }
+            }
+        };
+        values.reverse();
+        Some(SearchFrame::new(var, values.into_iter()))
+    }
+
+    fn assign_ob(&mut self, x: DomId, y: CodId) {
+        self.map.assign_ob(x, TabOb::Basic(y));
+    }
+
+    fn unassign_ob(&mut self, x: &DomId) -> Option<CodId> {
+        match self.map.unassign_ob(x) {
+            Some(TabOb::Basic(y)) => Some(y),
+            _ => None,
+        }
+    }
+
+    fn assign_mor(
+        &mut self,
+        m: DomId,
+        path: TabMor<CodId, CodId>,
+        _options: &SearchOptions<DomId, CodId, TabMor<CodId, CodId>>,
+    ) -> bool {
+        self.map.assign_basic_mor(m.clone(), path);
+        if self.dom.equations().any(|(_, eq)| self.preserves_equation(eq) == Some(false)) {
+            self.map.unassign_basic_mor(&m);
+            false
+        } else {
+            true
+        }
+    }
+
+    fn unassign_mor(&mut self, m: &DomId) {
+        self.map.unassign_basic_mor(m);
+    }
+
+    fn accept(&self, options: &SearchOptions<DomId, CodId, TabMor<CodId, CodId>>) -> bool {
+        !self.is_circular
+            && self.dom.equations().all(|(_, eq)| self.preserves_equation(eq) == Some(true))
+            && (!options.faithful
+                || DblModelMorphism(&self.map, self.dom, self.cod).is_free_simple_faithful())
+    }
+}
+
+impl<DomId, CodId, ThId, S> Iterator for DiscreteTabModelMorphismFinder<'_, DomId, CodId, ThId, S>
+where
+    DomId: Clone + Eq + Hash,
+    CodId: Clone + Eq + Hash,
+    ThId: Clone + Eq + Hash,
+    S: BuildHasher,
+{
+    type Item = DiscreteTabModelMapping<DomId, CodId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance().then(|| self.search.map.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::dbl::model::{UstrDiscreteDblModel, UstrDiscreteTabModel};
+    use crate::dbl::theory::{
+        DblTheory, TabMorType, TabObType, UstrDiscreteDblTheory, UstrDiscreteTabTheory,
+    };
+    use crate::one::fin_category::FinMor;
+    use crate::stdlib::*;
+    use crate::validate::Validate;
+
+    use std::collections::HashMap;
+    use ustr::ustr;
+
+    #[test]
+    fn discrete_model_mapping() {
+        let mut f: DiscreteDblModelMapping<_, _> = Default::default();
+        f.assign_ob('a', 'x');
+        f.assign_ob('b', 'y');
+        assert!(f.is_ob_assigned(&'a'));
+        assert_eq!(f.apply_ob(&'b'), Some('y'));
+        f.assign_basic_mor('f', Path::pair('p', 'q'));
+        f.assign_basic_mor('g', Path::pair('r', 's'));
+        assert!(f.is_mor_assigned(&Path::single('f')));
+        assert_eq!(f.apply_mor(&Path::pair('f', 'g')), Path::from_vec(vec!['p', 'q', 'r', 's']));
+    }
+
+    /// Chain of `n` composable morphisms `f0`, `f1`, ... through objects `x0`,
+    /// `x1`, ....
+    fn chain(th: Arc<UstrDiscreteDblTheory>, n: usize) -> UstrDiscreteDblModel {
+        let mut model = UstrDiscreteDblModel::new(th);
+        let x = |i: usize| ustr(&format!("x{i}"));
+        for i in 0..=n {
+            model.add_ob(x(i), ustr("Object"));
+        }
+        for i in 0..n {
+            model.add_mor(ustr(&format!("f{i}")), x(i), x(i + 1), FinMor::Id(ustr("Object")));
+        }
+        model
+    }
+
+    #[test]
+    fn compose_model_mappings() {
+        let th = Arc::new(th_signed_category());
+        let models = DiscreteDblModelCategory::new(th.clone());
+        let a = Arc::new(chain(th.clone(), 1));
+        let b = Arc::new(chain(th.clone(), 2));
+        let c = Arc::new(chain(th, 3));
+        let x = |i: usize| ustr(&format!("x{i}"));
+        let f = |i: usize| ustr(&format!("f{i}"));
+
+        // Refine a morphism into a pair, then the second morphism of the pair
+        // into another pair.
+        let mut mapping: DiscreteDblModelMapping<_, _> = Default::default();
+        mapping.assign_ob(x(0), x(0));
+        mapping.assign_ob(x(1), x(2));
+        mapping.assign_basic_mor(f(0), Path::pair(f(0), f(1)));
+        let first = DiscreteDblModelMor::new(mapping, a.clone(), b.clone());
+        let mut mapping = DiscreteDblModelMapping::identity(&b);
+        mapping.assign_ob(x(2), x(3));
+        mapping.assign_basic_mor(f(1), Path::pair(f(1), f(2)));
+        let second = DiscreteDblModelMor::new(mapping, b.clone(), c.clone());
+        assert!(models.has_mor(&first) && models.has_mor(&second));
+
+        let composite = models.compose2(first.clone(), second);
+        assert!(models.has_mor(&composite));
+        assert_eq!(composite.mapping.apply_ob(&x(1)), Some(x(3)));
+        assert_eq!(
+            composite.mapping.apply_basic_mor(&f(0)),
+            Path::from_vec(vec![f(0), f(1), f(2)])
+        );
+
+        assert_eq!(models.compose2(models.id(a), first.clone()), first);
+        assert_eq!(models.compose2(first.clone(), models.id(b.clone())), first);
+        assert!(first.inverse().is_none());
+
+        // The images of a path of generators need not be composable.
+        let mut mapping = DiscreteDblModelMapping::identity(&b);
+        mapping.assign_basic_mor(f(1), Path::single(f(2)));
+        assert!(first.mapping.compose(&mapping, &c).is_none());
+    }
+
+    #[test]
+    fn invert_model_mappings() {
+        let th = Arc::new(th_signed_category());
+        let models = DiscreteDblModelCategory::new(th.clone());
+        let feedback = Arc::new(positive_feedback(th));
+        let (x, y) = (ustr("x"), ustr("y"));
+        let (f, g) = (ustr("positive1"), ustr("positive2"));
+
+        let mut mapping: DiscreteDblModelMapping<_, _> = Default::default();
+        mapping.assign_ob(x, y);
+        mapping.assign_ob(y, x);
+        mapping.assign_basic_mor(f, Path::single(g));
+        mapping.assign_basic_mor(g, Path::single(f));
+        let swap = DiscreteDblModelMor::new(mapping, feedback.clone(), feedback.clone());
+        assert!(models.has_mor(&swap));
+
+        let inv = swap.inverse().unwrap();
+        assert_eq!(inv, swap);
+        assert_eq!(models.compose2(swap, inv), models.id(feedback.clone()));
+
+        // A generator sent to a composite has no inverse image.
+        let mut mapping = DiscreteDblModelMapping::identity(&feedback);
+        mapping.assign_basic_mor(f, Path::from_vec(vec![f, g, f]).unwrap());
+        assert!(DblModelMorphism(&mapping, &*feedback, &*feedback).validate().is_ok());
+        assert!(mapping.inverse(&feedback, &feedback).is_none());
+    }
+
+    #[test]
+    fn find_positive_loops() {
+        let th = Arc::new(th_signed_category());
+        let positive_loop = positive_loop(th.clone());
+        let pos = positive_loop.mor_generators().next().unwrap().into();
+
+        let maps = DiscreteDblModelMapping::morphisms(&positive_loop, &positive_loop).find_all();
+        assert_eq!(maps.len(), 2);
+        let mors: Vec<_> = maps.into_iter().map(|mor| mor.apply_mor(&pos)).collect();
+        assert!(mors.iter().any(|mor| matches!(mor, Some(Path::Id(_)))));
+        assert!(mors.iter().any(|mor| matches!(mor, Some(Path::Seq(_)))));
+
+        let maps = DiscreteDblModelMapping::morphisms(&positive_loop, &positive_loop)
+            .monic()
+            .find_all();
+        assert_eq!(maps.len(), 1);
+        assert!(matches!(maps[0].apply_mor(&pos), Some(Path::Seq(_))));
+    }
+
+    /// The [simple path](crate::one::graph_algorithms::simple_paths) should
+    /// give identical results to hom search from a walking morphism (assuming
+    /// all the object/morphism types are the same).   
+    #[test]
+    fn find_simple_paths() {
+        let th = Arc::new(th_signed_category());
+
+        let mut walking = UstrDiscreteDblModel::new(th.clone());
+        let (a, b) = (ustr("A"), ustr("B"));
+        walking.add_ob(a, ustr("Object"));
+        walking.add_ob(b, ustr("Object"));
+        walking.add_mor(ustr("f"), a, b, FinMor::Id(ustr("Object")));
+        let w = Path::single(ustr("f"));
+
+        //     y         Graph with lots of cyclic paths.
+        //   ↗  ↘
+        // ↻x ⇆ z
+        let mut model = UstrDiscreteDblModel::new(th);
+        let (x, y, z) = (ustr("X"), ustr("Y"), ustr("Z"));
+        model.add_ob(x, ustr("Object"));
+        model.add_ob(y, ustr("Object"));
+        model.add_ob(z, ustr("Object"));
+        model.add_mor(ustr("xy"), x, y, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("yz"), y, z, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("zx"), z, x, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("xz"), x, z, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("xx"), x, x, FinMor::Id(ustr("Object")));
+
+        for i in model.ob_generators() {
+            for j in model.ob_generators() {
                 let maps: HashSet<_> = DiscreteDblModelMapping::morphisms(&walking, &model)
                     .initialize_ob(ustr("A"), i)
                     .initialize_ob(ustr("B"), j)
@@ -693,6 +2153,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn find_with_initialized_morphism() {
+        let th = Arc::new(th_signed_category());
+        let (arrow, model) = (chain(th.clone(), 1), chain(th, 3));
+        let path = Path::from_vec(["f0", "f1", "f2"].map(ustr).to_vec()).unwrap();
+
+        // The initial value is kept even when longer than the maximum length.
+        let maps = DiscreteDblModelMapping::morphisms(&arrow, &model)
+            .max_path_len(1)
+            .initialize_mor(ustr("f0"), path.clone())
+            .find_all();
+        assert_eq!(maps.len(), 1);
+        assert_eq!(maps[0].apply_ob(&ustr("x1")), Some(ustr("x3")));
+        assert_eq!(maps[0].apply_basic_mor(&ustr("f0")), Some(path.clone()));
+
+        // Objects are constrained by the initial value.
+        let maps = DiscreteDblModelMapping::morphisms(&arrow, &model)
+            .initialize_ob(ustr("x0"), ustr("x1"))
+            .initialize_mor(ustr("f0"), path)
+            .find_all();
+        assert!(maps.is_empty());
+    }
+
     #[test]
     fn find_negative_loops() {
         let th = Arc::new(th_signed_category());
@@ -801,6 +2284,93 @@ mod tests {
         assert!(!dmm.is_free_simple_monic());
     }
 
+    /// Triangle with composable morphisms `f`, `g` and a diagonal `h`.
+    fn triangle(th: Arc<UstrDiscreteDblTheory>, commutes: bool) -> UstrDiscreteDblModel {
+        let mut model = UstrDiscreteDblModel::new(th);
+        let (a, b, c) = (ustr("a"), ustr("b"), ustr("c"));
+        for x in [a, b, c] {
+            model.add_ob(x, ustr("Object"));
+        }
+        model.add_mor(ustr("f"), a, b, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("g"), b, c, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("h"), a, c, FinMor::Id(ustr("Object")));
+        if commutes {
+            let eq = PathEq::new(Path::pair(ustr("f"), ustr("g")), Path::single(ustr("h")));
+            model.add_equation(ustr("comm"), eq);
+        }
+        model
+    }
+
+    /// Composable pair `p`, `q` with two diagonals, only `r1` commuting.
+    fn two_diagonals(th: Arc<UstrDiscreteDblTheory>) -> UstrDiscreteDblModel {
+        let mut model = UstrDiscreteDblModel::new(th);
+        let (x, y, z) = (ustr("x"), ustr("y"), ustr("z"));
+        for w in [x, y, z] {
+            model.add_ob(w, ustr("Object"));
+        }
+        model.add_mor(ustr("p"), x, y, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("q"), y, z, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("r1"), x, z, FinMor::Id(ustr("Object")));
+        model.add_mor(ustr("r2"), x, z, FinMor::Id(ustr("Object")));
+        let eq = PathEq::new(Path::pair(ustr("p"), ustr("q")), Path::single(ustr("r1")));
+        model.add_equation(ustr("pq"), eq);
+        model
+    }
+
+    #[test]
+    fn find_morphisms_out_of_non_free_model() {
+        let th = Arc::new(th_signed_category());
+        let model = two_diagonals(th.clone());
+        let h = Path::single(ustr("h"));
+
+        // The diagonal must go to a morphism equal to the composite.
+        let commuting = triangle(th.clone(), true);
+        let maps = DiscreteDblModelMapping::morphisms(&commuting, &model).monic().find_all();
+        let images: HashSet<_> = maps.iter().map(|f| f.apply_mor(&h).unwrap()).collect();
+        assert_eq!(
+            images,
+            HashSet::from([Path::single(ustr("r1")), Path::pair(ustr("p"), ustr("q"))])
+        );
+        for f in maps.iter() {
+            assert!(DblModelMorphism(f, &commuting, &model).validate().is_ok());
+        }
+
+        // In a free triangle, the diagonal cannot go to the composite, even up
+        // to the equation in the codomain, without the map being unfaithful.
+        let free = triangle(th, false);
+        let maps = DiscreteDblModelMapping::morphisms(&free, &model).monic().find_all();
+        assert_eq!(maps.len(), 1);
+        assert_eq!(maps[0].apply_mor(&h), Some(Path::single(ustr("r2"))));
+    }
+
+    #[test]
+    fn validate_morphism_out_of_non_free_model() {
+        let th = Arc::new(th_signed_category());
+        let (commuting, model) = (triangle(th.clone(), true), two_diagonals(th));
+
+        let mut f: DiscreteDblModelMapping<_, _> = Default::default();
+        f.assign_ob(ustr("a"), ustr("x"));
+        f.assign_ob(ustr("b"), ustr("y"));
+        f.assign_ob(ustr("c"), ustr("z"));
+        f.assign_basic_mor(ustr("f"), Path::single(ustr("p")));
+        f.assign_basic_mor(ustr("g"), Path::single(ustr("q")));
+        f.assign_basic_mor(ustr("h"), Path::single(ustr("r1")));
+        assert!(DblModelMorphism(&f, &commuting, &model).validate().is_ok());
+
+        // The image keeps the equation between the basic morphisms in it.
+        let im = f.syntactic_image(&model);
+        assert!(!im.is_free());
+        assert!(im.validate().is_ok());
+
+        f.assign_basic_mor(ustr("h"), Path::single(ustr("r2")));
+        let errs: Vec<_> = DblModelMorphism(&f, &commuting, &model)
+            .validate()
+            .expect_err("should be invalid")
+            .into();
+        assert_eq!(errs, vec![InvalidDblModelMorphism::Eq(ustr("comm"))]);
+        assert!(f.syntactic_image(&model).is_free());
+    }
+
     #[test]
     fn monic_constraint() {
         // The number of endomonomorphisms of a set |N| is N!.
@@ -851,4 +2421,292 @@ mod tests {
             1
         );
     }
+
+    #[test]
+    fn bounded_search() {
+        let theory = Arc::new(th_signed_category());
+        let mut model = UstrDiscreteDblModel::new(theory);
+        for x in ["Q", "X", "Y", "Z"] {
+            model.add_ob(ustr(x), ustr("Object"));
+        }
+
+        // The search is lazy.
+        let mut finder = DiscreteDblModelMapping::morphisms(&model, &model);
+        assert!(finder.monic().next().is_some());
+        assert_eq!(finder.count(), 4 * 3 * 2 - 1);
+
+        let mut finder = DiscreteDblModelMapping::morphisms(&model, &model);
+        assert_eq!(finder.monic().find_all().len(), 4 * 3 * 2);
+        assert!(!finder.is_truncated());
+
+        let mut finder = DiscreteDblModelMapping::morphisms(&model, &model);
+        assert_eq!(finder.monic().max_results(5).find_all().len(), 5);
+        assert!(finder.is_truncated());
+
+        // Four assignments are needed to find the first morphism.
+        let mut finder = DiscreteDblModelMapping::morphisms(&model, &model);
+        assert_eq!(finder.max_expansions(3).find_all().len(), 0);
+        assert!(finder.is_truncated());
+        let mut finder = DiscreteDblModelMapping::morphisms(&model, &model);
+        assert_eq!(finder.max_expansions(4).find_all().len(), 1);
+        assert!(finder.is_truncated());
+
+        // Ten assignments find six morphisms: four to reach the first one and
+        // three more to try the other values of the last object, then one to
+        // reassign the third object and two more for the last object.
+        let mut polls = 0;
+        let mut finder = DiscreteDblModelMapping::morphisms(&model, &model);
+        let maps = finder
+            .cancel_when(|| {
+                polls += 1;
+                polls > 10
+            })
+            .find_all();
+        assert_eq!(maps.len(), 6);
+        assert!(finder.is_truncated());
+    }
+
+    /// Random signed graph, generated by a linear congruential generator so
+    /// that it is reproducible.
+    fn random_signed_graph(
+        th: Arc<UstrDiscreteDblTheory>,
+        n_obs: usize,
+        n_mors: usize,
+        seed: u64,
+    ) -> UstrDiscreteDblModel {
+        let mut state = seed;
+        let mut random = move |n: usize| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) as usize % n
+        };
+        let mut model = UstrDiscreteDblModel::new(th);
+        let obs: Vec<_> = (0..n_obs).map(|i| ustr(&format!("x{i}"))).collect();
+        for x in obs.iter() {
+            model.add_ob(*x, ustr("Object"));
+        }
+        for i in 0..n_mors {
+            let (x, y) = (obs[random(n_obs)], obs[random(n_obs)]);
+            let mor_type = if random(2) == 0 {
+                FinMor::Id(ustr("Object"))
+            } else {
+                FinMor::Generator(ustr("Negative"))
+            };
+            model.add_mor(ustr(&format!("f{i}")), x, y, mor_type);
+        }
+        model
+    }
+
+    #[test]
+    fn find_loops_in_random_graphs() {
+        let th = Arc::new(th_signed_category());
+        let negative_loop = negative_loop(th.clone());
+        let negative = FinMor::Generator(ustr("Negative"));
+        for seed in 0..3 {
+            let model = random_signed_graph(th.clone(), 300, 600, seed);
+
+            // Monic maps from the negative loop are the negative simple cycles
+            // at each object.
+            let mut finder = DiscreteDblModelMapping::morphisms(&negative_loop, &model);
+            let maps = finder.monic().max_path_len(4).max_expansions(5_000).find_all();
+            assert!(!finder.is_truncated());
+            let cycles: usize = model
+                .ob_generators()
+                .map(|x| {
+                    bounded_simple_paths(model.generating_graph(), &x, &x, Some(4))
+                        .filter(|path| !path.is_empty() && model.mor_type(path) == negative)
+                        .count()
+                })
+                .sum();
+            assert!(cycles > 0);
+            assert_eq!(maps.len(), cycles);
+        }
+    }
+
+    #[test]
+    fn prune_faithful_search_in_random_graphs() {
+        let th = Arc::new(th_signed_category());
+        let motif = positive_feedback(th.clone());
+        for seed in 0..3 {
+            let model = random_signed_graph(th.clone(), 200, 400, seed);
+
+            // Pruning during the search gives the same morphisms as filtering
+            // afterwards. Without arc consistency, the search would try every
+            // pair of objects, exceeding the budget on expansions.
+            let mut finder = DiscreteDblModelMapping::morphisms(&motif, &model);
+            let monic = finder.monic().max_path_len(3).max_expansions(5_000).find_all();
+            assert!(!finder.is_truncated());
+            let filtered: Vec<_> = DiscreteDblModelMapping::morphisms(&motif, &model)
+                .injective_ob()
+                .max_path_len(3)
+                .filter(|f| DblModelMorphism(f, &motif, &model).is_free_simple_faithful())
+                .collect();
+            assert!(!monic.is_empty());
+            assert_eq!(monic.len(), filtered.len());
+            assert!(monic.iter().all(|f| filtered.contains(f)));
+        }
+    }
+
+    /// Stock-flow diagram for the SIR model, with links for the rates of
+    /// infection and recovery.
+    fn sir_stock_flow(th: Arc<UstrDiscreteTabTheory>) -> UstrDiscreteTabModel {
+        let mut model = UstrDiscreteTabModel::new(th.clone());
+        let ob_type = TabObType::Basic(ustr("Object"));
+        let (s, i, r) = (ustr("S"), ustr("I"), ustr("R"));
+        for x in [s, i, r] {
+            model.add_ob(x, ob_type.clone());
+        }
+        let (inf, rec) = (ustr("infection"), ustr("recovery"));
+        model.add_mor(inf, s.into(), i.into(), th.hom_type(ob_type.clone()));
+        model.add_mor(rec, i.into(), r.into(), th.hom_type(ob_type));
+        let link_type = TabMorType::Basic(ustr("Link"));
+        model.add_mor(ustr("susceptible"), s.into(), model.tabulated_gen(inf), link_type.clone());
+        model.add_mor(ustr("infective"), i.into(), model.tabulated_gen(inf), link_type.clone());
+        model.add_mor(ustr("recovering"), i.into(), model.tabulated_gen(rec), link_type);
+        model
+    }
+
+    #[test]
+    fn find_reinforcing_loops() {
+        let th = Arc::new(th_category_links());
+        let motif = backward_link(th.clone());
+        let model = sir_stock_flow(th);
+        assert!(model.validate().is_ok());
+
+        let maps = DiscreteTabModelMapping::morphisms(&motif, &model).find_all();
+        assert_eq!(maps.len(), 1);
+        let f = &maps[0];
+        assert_eq!(f.apply_ob(&ustr("x").into()), Some(ustr("S").into()));
+        assert_eq!(f.apply_ob(&ustr("y").into()), Some(ustr("I").into()));
+        assert_eq!(f.apply_basic_mor(&ustr("link")), Some(ustr("infective").into()));
+        assert_eq!(
+            f.apply_ob(&motif.tabulated_gen(ustr("f"))),
+            Some(model.tabulated_gen(ustr("infection")))
+        );
+        assert!(DblModelMorphism(f, &motif, &model).validate().is_ok());
+
+        let maps = DiscreteTabModelMapping::morphisms(&motif, &model)
+            .monic()
+            .initialize_ob(ustr("x"), ustr("I"))
+            .find_all();
+        assert!(maps.is_empty());
+
+        let maps = DiscreteTabModelMapping::morphisms(&motif, &motif).monic().find_all();
+        assert_eq!(maps.len(), 1);
+    }
+
+    #[test]
+    fn validate_tab_model_morphism() {
+        let th = Arc::new(th_category_links());
+        let motif = backward_link(th.clone());
+        let model = sir_stock_flow(th);
+
+        // Send the link to the forward link, whose domain is wrong.
+        let mut f: DiscreteTabModelMapping<_, _> = Default::default();
+        f.assign_ob(ustr("x"), ustr("S").into());
+        f.assign_ob(ustr("y"), ustr("I").into());
+        f.assign_basic_mor(ustr("f"), ustr("infection").into());
+        f.assign_basic_mor(ustr("link"), ustr("susceptible").into());
+        let errs: Vec<_> = DblModelMorphism(&f, &motif, &model)
+            .validate()
+            .expect_err("should be invalid")
+            .into();
+        assert_eq!(errs, vec![InvalidDblModelMorphism::Dom(ustr("link"))]);
+
+        f.unassign_basic_mor(&ustr("link"));
+        let errs: Vec<_> = DblModelMorphism(&f, &motif, &model)
+            .validate()
+            .expect_err("should be invalid")
+            .into();
+        assert_eq!(errs, vec![InvalidDblModelMorphism::MissingMor(ustr("link"))]);
+    }
+
+    /// Two links from an object to a tabulated morphism, possibly made equal.
+    fn parallel_links(th: Arc<UstrDiscreteTabTheory>, equal: bool) -> UstrDiscreteTabModel {
+        let mut model = UstrDiscreteTabModel::new(th.clone());
+        let ob_type = TabObType::Basic(ustr("Object"));
+        let (x, y, f) = (ustr("x"), ustr("y"), ustr("f"));
+        model.add_ob(x, ob_type.clone());
+        model.add_ob(y, ob_type.clone());
+        model.add_mor(f, x.into(), y.into(), th.hom_type(ob_type));
+        let link_type = TabMorType::Basic(ustr("Link"));
+        let (l1, l2) = (ustr("l1"), ustr("l2"));
+        model.add_mor(l1, x.into(), model.tabulated_gen(f), link_type.clone());
+        model.add_mor(l2, x.into(), model.tabulated_gen(f), link_type);
+        if equal {
+            model.add_equation(ustr("same"), PathEq::new(l1.into(), l2.into()));
+        }
+        model
+    }
+
+    #[test]
+    fn validate_tab_morphism_out_of_non_free_model() {
+        let th = Arc::new(th_category_links());
+        let (quotient, free) = (parallel_links(th.clone(), true), parallel_links(th, false));
+        assert!(quotient.validate().is_ok());
+
+        let mut f: DiscreteTabModelMapping<_, _> = Default::default();
+        for x in ["x", "y"].map(ustr) {
+            f.assign_ob(x, x.into());
+        }
+        for m in ["f", "l1", "l2"].map(ustr) {
+            f.assign_basic_mor(m, m.into());
+        }
+        assert!(DblModelMorphism(&f, &quotient, &quotient).validate().is_ok());
+        let errs: Vec<_> = DblModelMorphism(&f, &quotient, &free).validate().unwrap_err().into();
+        assert_eq!(errs, vec![InvalidDblModelMorphism::Eq(ustr("same"))]);
+
+        // Identifying the links is always allowed.
+        f.assign_basic_mor(ustr("l2"), ustr("l1").into());
+        assert!(DblModelMorphism(&f, &quotient, &free).validate().is_ok());
+
+        // Equations in the codomain are used transitively.
+        let mut chain = quotient.clone();
+        let l3 = ustr("l3");
+        chain.add_mor(
+            l3,
+            ustr("x").into(),
+            chain.tabulated_gen(ustr("f")),
+            TabMorType::Basic(ustr("Link")),
+        );
+        chain.add_equation(ustr("next"), PathEq::new(ustr("l2").into(), l3.into()));
+        f.assign_basic_mor(ustr("l2"), l3.into());
+        assert!(DblModelMorphism(&f, &quotient, &chain).validate().is_ok());
+    }
+
+    #[test]
+    fn find_tab_morphisms_out_of_non_free_model() {
+        let th = Arc::new(th_category_links());
+        let (quotient, free) = (parallel_links(th.clone(), true), parallel_links(th, false));
+
+        // The links must be sent to links known to be equal.
+        let maps = DiscreteTabModelMapping::morphisms(&quotient, &free).find_all();
+        assert_eq!(maps.len(), 2);
+        assert!(maps.iter().all(|f| DblModelMorphism(f, &quotient, &free).validate().is_ok()));
+        let maps = DiscreteTabModelMapping::morphisms(&quotient, &quotient).find_all();
+        assert_eq!(maps.len(), 4);
+
+        // Distinct links cannot be identified by a faithful morphism.
+        let maps = DiscreteTabModelMapping::morphisms(&free, &free).faithful().find_all();
+        assert_eq!(maps.len(), 2);
+        let maps = DiscreteTabModelMapping::morphisms(&free, &quotient).find_all();
+        assert_eq!(maps.len(), 4);
+        assert!(
+            maps.iter()
+                .all(|f| !DblModelMorphism(f, &free, &quotient).is_free_simple_faithful())
+        );
+        let maps = DiscreteTabModelMapping::morphisms(&quotient, &quotient).monic().find_all();
+        assert_eq!(maps.len(), 4);
+    }
+
+    #[test]
+    fn find_tab_morphisms_out_of_circular_model() {
+        let th = Arc::new(th_category_links());
+        let mut model = UstrDiscreteTabModel::new(th.clone());
+        let (x, l) = (ustr("x"), ustr("l"));
+        model.add_ob(x, TabObType::Basic(ustr("Object")));
+        model.add_mor(l, x.into(), model.tabulated_gen(l), TabMorType::Basic(ustr("Link")));
+
+        let maps = DiscreteTabModelMapping::morphisms(&model, &model).find_all();
+        assert!(maps.is_empty());
+    }
 }
@@ -0,0 +1,697 @@
+/*! Pushouts of models of double theories.
+
+Models are often built in pieces and then glued together along shared
+sub-models. Given an *interface* model `Z` and two model mappings `f: Z → X`
+and `g: Z → Y`, the **pushout** of `X` and `Y` along `Z` is the model obtained
+by taking the disjoint union of `X` and `Y` and then identifying the images of
+each object and morphism in `Z`. The pushout comes with *coprojection* mappings
+from `X` and `Y` into it, which can be used to trace analyses of the parts to
+the whole.
+
+Generators in the pushout are named after generators in the parts: each glued
+generator takes the least name among those in `X` identified with it, or, if
+there are none, among those in `Y`. Generators that are not glued keep their
+names, so the parts should not share names except along the interface. Gluing
+is only well defined when the identified generators have the same type and
+their names do not clash, so these conditions are reported as errors.
+
+A basic morphism in the interface that is sent to basic morphisms by both legs
+simply identifies those morphisms. Otherwise, the images of the morphism must
+be equated in the pushout, which is possible only for models with equations.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
+
+use derivative::Derivative;
+use nonempty::NonEmpty;
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-wasm")]
+use tsify_next::Tsify;
+
+use super::{model::*, model_morphism::*};
+use crate::one::*;
+use crate::zero::{Column, FinSet, HashColumn, Mapping, limits};
+
+/// A pushout of models along a common interface, with its coprojections.
+#[derive(Clone)]
+pub struct DblModelPushout<Map, Model> {
+    /// The glued model, the apex of the pushout.
+    pub model: Model,
+
+    /// Coprojection from the left model into the glued model.
+    pub left: Map,
+
+    /// Coprojection from the right model into the glued model.
+    pub right: Map,
+}
+
+/// A pushout of models whose coprojections are mappings between discrete models.
+pub type GluedDblModel<Id, Model> = DblModelPushout<DiscreteDblModelMapping<Id, Id>, Model>;
+
+/// A pushout of models of a discrete double theory.
+pub type DiscreteDblModelPushout<Id, Cat> = GluedDblModel<Id, DiscreteDblModel<Id, Cat>>;
+
+/// A pushout of models of a discrete tabulator theory.
+pub type DiscreteTabModelPushout<Id, ThId, S> = GluedDblModel<Id, DiscreteTabModel<Id, ThId, S>>;
+
+/// A pair of parallel paths to be identified, keyed by a name for the pair.
+pub type PathPair<Id> = (Id, Path<Id, Id>, Path<Id, Id>);
+
+/// A failure to glue models of a double theory.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidDblModelPushout<Id> {
+    /// Object in the interface that is not mapped by both legs.
+    #[error("Object `{0}` in the interface is not mapped by both legs")]
+    Ob(Id),
+
+    /// Morphism in the interface that is not mapped by both legs.
+    #[error("Morphism `{0}` in the interface is not mapped by both legs")]
+    Mor(Id),
+
+    /// Glued object whose identified objects have different types.
+    #[error("Object `{0}` is glued from objects of different types")]
+    ObType(Id),
+
+    /// Glued morphism whose identified morphisms have different types.
+    #[error("Morphism `{0}` is glued from morphisms of different types")]
+    MorType(Id),
+
+    /// Name shared by distinct objects in the pushout.
+    #[error("Name `{0}` is shared by distinct objects")]
+    ObName(Id),
+
+    /// Name shared by distinct morphisms in the pushout.
+    #[error("Name `{0}` is shared by distinct morphisms")]
+    MorName(Id),
+
+    /// Key shared by distinct equations in the pushout.
+    #[error("Key `{0}` is shared by distinct equations")]
+    EqName(Id),
+}
+
+type Invalid<Id> = InvalidDblModelPushout<Id>;
+
+/** Generators of two models to be identified in a pushout.
+
+This is an interface presented elementwise, by the pairs of generators that it
+identifies, rather than by a model and two model mappings. It is convenient
+when the interface has no natural names of its own.
+ */
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+pub struct Gluing<Id> {
+    /// Pairs of objects to be identified.
+    pub obs: Vec<(Id, Id)>,
+
+    /// Pairs of morphisms to be identified, each with a key naming the pair.
+    pub mors: Vec<PathPair<Id>>,
+}
+
+impl<Id: Clone> Gluing<Id> {
+    /** Computes the generators identified by a span of model mappings.
+
+    Also returns errors for the generators in the interface at which either
+    mapping is undefined.
+     */
+    fn from_span<Model>(
+        z: &Model,
+        f: &DiscreteDblModelMapping<Id, Id>,
+        g: &DiscreteDblModelMapping<Id, Id>,
+    ) -> (Self, Vec<InvalidDblModelPushout<Id>>)
+    where
+        Id: Eq + Hash,
+        Model: FgCategory<ObGen = Id, MorGen = Id>,
+    {
+        let (mut gluing, mut errors) = (Self::default(), Vec::new());
+        for ob in z.ob_generators() {
+            match (f.apply_ob(&ob), g.apply_ob(&ob)) {
+                (Some(x), Some(y)) => gluing.obs.push((x, y)),
+                _ => errors.push(Invalid::Ob(ob)),
+            }
+        }
+        for e in z.mor_generators() {
+            match (f.apply_basic_mor(&e), g.apply_basic_mor(&e)) {
+                (Some(fe), Some(ge)) => gluing.mors.push((e, fe, ge)),
+                _ => errors.push(Invalid::Mor(e)),
+            }
+        }
+        (gluing, errors)
+    }
+}
+
+/// A model that can be glued to another model of the same kind.
+pub trait GlueDblModel<Id>: Sized {
+    /// Glues the model to another along the given identifications.
+    fn glue(
+        &self,
+        other: &Self,
+        gluing: &Gluing<Id>,
+    ) -> Result<GluedDblModel<Id, Self>, NonEmpty<InvalidDblModelPushout<Id>>>;
+
+    /// Constructs the discrete model on the given objects of this model.
+    fn discrete_submodel(&self, obs: &[Id]) -> Self;
+}
+
+/** Glues two models of a discrete double theory along an interface.
+
+The legs `f: Z → X` and `g: Z → Y` are assumed to be valid model morphisms.
+Equations in `X` and `Y` are carried over to the pushout, where they keep their
+keys. The equation gluing the images of a basic morphism in `Z` that is not
+sent to basic morphisms by both legs is keyed by that morphism.
+ */
+pub fn discrete_dbl_pushout<Id, Cat>(
+    x: &DiscreteDblModel<Id, Cat>,
+    y: &DiscreteDblModel<Id, Cat>,
+    z: &DiscreteDblModel<Id, Cat>,
+    f: &DiscreteDblModelMapping<Id, Id>,
+    g: &DiscreteDblModelMapping<Id, Id>,
+) -> Result<DiscreteDblModelPushout<Id, Cat>, NonEmpty<InvalidDblModelPushout<Id>>>
+where
+    Id: Ord + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    let (gluing, errors) = Gluing::from_span(z, f, g);
+    glue_discrete_dbl(x, y, &gluing, errors)
+}
+
+/** Glues two models of a discrete tabulator theory along an interface.
+
+The legs `f: Z → X` and `g: Z → Y` are assumed to be valid model morphisms.
+As for [discrete double theories](discrete_dbl_pushout), equations in `X` and
+`Y` keep their keys in the pushout, and a basic morphism in `Z` that is not sent
+to basic morphisms by both legs is glued by an equation keyed by it.
+ */
+pub fn discrete_tab_pushout<Id, ThId, S>(
+    x: &DiscreteTabModel<Id, ThId, S>,
+    y: &DiscreteTabModel<Id, ThId, S>,
+    z: &DiscreteTabModel<Id, ThId, S>,
+    f: &DiscreteDblModelMapping<Id, Id>,
+    g: &DiscreteDblModelMapping<Id, Id>,
+) -> Result<DiscreteTabModelPushout<Id, ThId, S>, NonEmpty<InvalidDblModelPushout<Id>>>
+where
+    Id: Ord + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    let (gluing, errors) = Gluing::from_span(z, f, g);
+    glue_discrete_tab(x, y, &gluing, errors)
+}
+
+impl<Id, Cat> GlueDblModel<Id> for DiscreteDblModel<Id, Cat>
+where
+    Id: Ord + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    fn glue(
+        &self,
+        other: &Self,
+        gluing: &Gluing<Id>,
+    ) -> Result<DiscreteDblModelPushout<Id, Cat>, NonEmpty<InvalidDblModelPushout<Id>>> {
+        glue_discrete_dbl(self, other, gluing, Vec::new())
+    }
+
+    fn discrete_submodel(&self, obs: &[Id]) -> Self {
+        let mut model = DiscreteDblModel::new(self.theory_arc());
+        for ob in obs {
+            model.add_ob(ob.clone(), self.ob_generator_type(ob));
+        }
+        model
+    }
+}
+
+impl<Id, ThId, S> GlueDblModel<Id> for DiscreteTabModel<Id, ThId, S>
+where
+    Id: Ord + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    fn glue(
+        &self,
+        other: &Self,
+        gluing: &Gluing<Id>,
+    ) -> Result<DiscreteTabModelPushout<Id, ThId, S>, NonEmpty<InvalidDblModelPushout<Id>>> {
+        glue_discrete_tab(self, other, gluing, Vec::new())
+    }
+
+    fn discrete_submodel(&self, obs: &[Id]) -> Self {
+        let mut model = DiscreteTabModel::new(self.theory_arc());
+        for ob in obs {
+            model.add_ob(ob.clone(), self.ob_generator_type(ob));
+        }
+        model
+    }
+}
+
+fn glue_discrete_dbl<Id, Cat>(
+    x: &DiscreteDblModel<Id, Cat>,
+    y: &DiscreteDblModel<Id, Cat>,
+    gluing: &Gluing<Id>,
+    mut errors: Vec<InvalidDblModelPushout<Id>>,
+) -> Result<DiscreteDblModelPushout<Id, Cat>, NonEmpty<InvalidDblModelPushout<Id>>>
+where
+    Id: Ord + Clone + Hash,
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+{
+    let Identification { obs, mors, unglued } = Identification::new(x, y, gluing, &mut errors);
+
+    let left_path = |path: &Path<Id, Id>| path.clone().map(|x| obs.left(&x), |e| mors.left(&e));
+    let right_path = |path: &Path<Id, Id>| path.clone().map(|y| obs.right(&y), |e| mors.right(&e));
+    let mut equations = Vec::new();
+    for (key, eq) in x.equations() {
+        equations.push((key, PathEq::new(left_path(&eq.lhs), left_path(&eq.rhs))));
+    }
+    for (key, eq) in y.equations() {
+        equations.push((key, PathEq::new(right_path(&eq.lhs), right_path(&eq.rhs))));
+    }
+    for (key, fe, ge) in unglued {
+        equations.push((key, PathEq::new(left_path(&fe), right_path(&ge))));
+    }
+    errors.extend(duplicates(equations.iter().map(|(key, _)| key.clone())).map(Invalid::EqName));
+    if let Some(errors) = NonEmpty::from_vec(errors) {
+        return Err(errors);
+    }
+
+    let mut model = DiscreteDblModel::new(x.theory_arc());
+    for (ob, name) in obs.left.iter() {
+        model.add_ob(name.clone(), x.ob_generator_type(&ob));
+    }
+    for (ob, name) in obs.right.iter() {
+        model.add_ob(name.clone(), y.ob_generator_type(&ob));
+    }
+    for (e, name) in mors.left.iter() {
+        let (dom, cod) = (x.mor_generator_dom(&e), x.mor_generator_cod(&e));
+        model.add_mor(name.clone(), obs.left(&dom), obs.left(&cod), x.mor_generator_type(&e));
+    }
+    for (e, name) in mors.right.iter() {
+        let (dom, cod) = (y.mor_generator_dom(&e), y.mor_generator_cod(&e));
+        model.add_mor(name.clone(), obs.right(&dom), obs.right(&cod), y.mor_generator_type(&e));
+    }
+    for (key, eq) in equations {
+        model.add_equation(key, eq);
+    }
+    Ok(DblModelPushout {
+        model,
+        left: obs.coprojection(&mors, true),
+        right: obs.coprojection(&mors, false),
+    })
+}
+
+fn glue_discrete_tab<Id, ThId, S>(
+    x: &DiscreteTabModel<Id, ThId, S>,
+    y: &DiscreteTabModel<Id, ThId, S>,
+    gluing: &Gluing<Id>,
+    mut errors: Vec<InvalidDblModelPushout<Id>>,
+) -> Result<DiscreteTabModelPushout<Id, ThId, S>, NonEmpty<InvalidDblModelPushout<Id>>>
+where
+    Id: Ord + Clone + Hash,
+    ThId: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    let Identification { obs, mors, unglued } = Identification::new(x, y, gluing, &mut errors);
+
+    let (left, right) = ((&obs.left, &mors.left), (&obs.right, &mors.right));
+    let rename_eq = |eq: &PathEq<_, _>, names| {
+        PathEq::new(rename_tab_mor(&eq.lhs, names), rename_tab_mor(&eq.rhs, names))
+    };
+    let mut equations = Vec::new();
+    for (key, eq) in x.equations() {
+        equations.push((key, rename_eq(eq, left)));
+    }
+    for (key, eq) in y.equations() {
+        equations.push((key, rename_eq(eq, right)));
+    }
+    for (key, fe, ge) in unglued {
+        let (fe, ge) = (fe.map(TabOb::Basic, TabEdge::Basic), ge.map(TabOb::Basic, TabEdge::Basic));
+        equations.push((key, PathEq::new(rename_tab_mor(&fe, left), rename_tab_mor(&ge, right))));
+    }
+    errors.extend(duplicates(equations.iter().map(|(key, _)| key.clone())).map(Invalid::EqName));
+    if let Some(errors) = NonEmpty::from_vec(errors) {
+        return Err(errors);
+    }
+
+    let mut model = DiscreteTabModel::new(x.theory_arc());
+    for (ob, name) in obs.left.iter() {
+        model.add_ob(name.clone(), x.ob_generator_type(&ob));
+    }
+    for (ob, name) in obs.right.iter() {
+        model.add_ob(name.clone(), y.ob_generator_type(&ob));
+    }
+    for (e, name) in mors.left.iter() {
+        let (dom, cod) = (x.mor_generator_dom(&e), x.mor_generator_cod(&e));
+        let (dom, cod) = (rename_tab_ob(&dom, left), rename_tab_ob(&cod, left));
+        model.add_mor(name.clone(), dom, cod, x.mor_generator_type(&e));
+    }
+    for (e, name) in mors.right.iter() {
+        let (dom, cod) = (y.mor_generator_dom(&e), y.mor_generator_cod(&e));
+        let (dom, cod) = (rename_tab_ob(&dom, right), rename_tab_ob(&cod, right));
+        model.add_mor(name.clone(), dom, cod, y.mor_generator_type(&e));
+    }
+    for (key, eq) in equations {
+        model.add_equation(key, eq);
+    }
+    Ok(DblModelPushout {
+        model,
+        left: obs.coprojection(&mors, true),
+        right: obs.coprojection(&mors, false),
+    })
+}
+
+/// Generators of two models identified by a gluing, with their new names.
+struct Identification<Id> {
+    obs: Renaming<Id>,
+    mors: Renaming<Id>,
+    unglued: Vec<PathPair<Id>>,
+}
+
+impl<Id: Ord + Clone + Hash> Identification<Id> {
+    fn new<Model>(
+        x: &Model,
+        y: &Model,
+        gluing: &Gluing<Id>,
+        errors: &mut Vec<InvalidDblModelPushout<Id>>,
+    ) -> Self
+    where
+        Model: FgDblModel<ObGen = Id, MorGen = Id>,
+    {
+        let (glued, unglued): (Vec<_>, Vec<_>) = gluing
+            .mors
+            .iter()
+            .cloned()
+            .partition(|(_, fe, ge)| fe.len() == 1 && ge.len() == 1);
+        let obs = Renaming::new(
+            x.ob_generators(),
+            y.ob_generators(),
+            gluing.obs.iter(),
+            |(x, _)| Some(x.clone()),
+            |(_, y)| Some(y.clone()),
+        );
+        let mors = Renaming::new(
+            x.mor_generators(),
+            y.mor_generators(),
+            glued.into_iter(),
+            |(_, fe, _)| fe.clone().only(),
+            |(_, _, ge)| ge.clone().only(),
+        );
+        errors.extend(obs.clashes.iter().cloned().map(Invalid::ObName));
+        errors.extend(mors.clashes.iter().cloned().map(Invalid::MorName));
+
+        let ob_types = obs.typed(x, y, |model, ob| model.ob_generator_type(ob));
+        errors.extend(type_clashes(ob_types.into_iter()).map(Invalid::ObType));
+        let mor_types = mors.typed(x, y, |model, e| model.mor_generator_type(e));
+        errors.extend(type_clashes(mor_types.into_iter()).map(Invalid::MorType));
+
+        Self { obs, mors, unglued }
+    }
+}
+
+/// Renaming of generators of one kind, objects or morphisms, in the pushout.
+struct Renaming<Id> {
+    left: HashColumn<Id, Id>,
+    right: HashColumn<Id, Id>,
+    clashes: Vec<Id>,
+}
+
+impl<Id: Ord + Clone + Hash> Renaming<Id> {
+    /// Identifies generators in two models along pairs of generators.
+    fn new<Z>(
+        x: impl Iterator<Item = Id>,
+        y: impl Iterator<Item = Id>,
+        z: impl Iterator<Item = Z>,
+        f: impl Fn(&Z) -> Option<Id>,
+        g: impl Fn(&Z) -> Option<Id>,
+    ) -> Self {
+        let cospan = limits::pushout_by(x, y, z, f, g);
+        let n = cospan.apex().len();
+        let least = |names: &mut Vec<Option<Id>>, id: Id, i: usize| {
+            if names[i].as_ref().is_none_or(|name| id < *name) {
+                names[i] = Some(id);
+            }
+        };
+        let (mut names, mut right_names) = (vec![None; n], vec![None; n]);
+        for (x, i) in cospan.left().iter() {
+            least(&mut names, x, *i);
+        }
+        for (y, i) in cospan.right().iter() {
+            least(&mut right_names, y, *i);
+        }
+        let names: Vec<_> = std::iter::zip(names, right_names)
+            .map(|(name, right_name)| name.or(right_name).expect("Class should be nonempty"))
+            .collect();
+        Self {
+            left: cospan.left().iter().map(|(x, i)| (x, names[*i].clone())).collect(),
+            right: cospan.right().iter().map(|(y, i)| (y, names[*i].clone())).collect(),
+            clashes: duplicates(names.into_iter()).collect(),
+        }
+    }
+
+    fn left(&self, x: &Id) -> Id {
+        self.left.apply(x).expect("Generator should belong to left model")
+    }
+
+    fn right(&self, y: &Id) -> Id {
+        self.right.apply(y).expect("Generator should belong to right model")
+    }
+
+    /// Pairs the new names of generators with their types.
+    fn typed<Model, T>(
+        &self,
+        x: &Model,
+        y: &Model,
+        typ: impl Fn(&Model, &Id) -> T,
+    ) -> Vec<(Id, T)> {
+        let left = self.left.iter().map(|(v, name)| (name.clone(), typ(x, &v)));
+        let right = self.right.iter().map(|(v, name)| (name.clone(), typ(y, &v)));
+        left.chain(right).collect()
+    }
+
+    /// Coprojection from one of the models into the pushout.
+    fn coprojection(&self, mors: &Self, is_left: bool) -> DiscreteDblModelMapping<Id, Id> {
+        let (obs, mors) = if is_left {
+            (&self.left, &mors.left)
+        } else {
+            (&self.right, &mors.right)
+        };
+        let mut mapping = DiscreteDblModelMapping::default();
+        for (x, name) in obs.iter() {
+            mapping.assign_ob(x, name.clone());
+        }
+        for (e, name) in mors.iter() {
+            mapping.assign_basic_mor(e, Path::single(name.clone()));
+        }
+        mapping
+    }
+}
+
+/// Iterates over the values that occur more than once, each reported once.
+fn duplicates<Id: Eq + Clone + Hash>(ids: impl Iterator<Item = Id>) -> impl Iterator<Item = Id> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+    ids.filter(move |id| !seen.insert(id.clone()) && reported.insert(id.clone()))
+}
+
+/// Iterates over the names assigned more than one type, each reported once.
+fn type_clashes<Id: Eq + Clone + Hash, T: Eq>(
+    typed: impl Iterator<Item = (Id, T)>,
+) -> impl Iterator<Item = Id> {
+    let mut types = HashMap::new();
+    let mut reported = HashSet::new();
+    typed.filter_map(move |(id, typ)| match types.get(&id) {
+        Some(other) if *other != typ && reported.insert(id.clone()) => Some(id),
+        Some(_) => None,
+        None => {
+            types.insert(id, typ);
+            None
+        }
+    })
+}
+
+/// Renames the generators in an object of a model of a discrete tabulator theory.
+fn rename_tab_ob<Id>(
+    ob: &TabOb<Id, Id>,
+    (obs, mors): (&HashColumn<Id, Id>, &HashColumn<Id, Id>),
+) -> TabOb<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    match ob {
+        TabOb::Basic(x) => TabOb::Basic(obs.apply(x).expect("Object should be renamed")),
+        TabOb::Tabulated(m) => TabOb::Tabulated(Box::new(rename_tab_mor(m, (obs, mors)))),
+    }
+}
+
+/// Renames the generators in a morphism of a model of a discrete tabulator theory.
+fn rename_tab_mor<Id>(
+    mor: &TabMor<Id, Id>,
+    names: (&HashColumn<Id, Id>, &HashColumn<Id, Id>),
+) -> TabMor<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    mor.clone()
+        .map(|x| rename_tab_ob(&x, names), |edge| rename_tab_edge(&edge, names))
+}
+
+/// Renames the generators in an edge of a model of a discrete tabulator theory.
+fn rename_tab_edge<Id>(
+    edge: &TabEdge<Id, Id>,
+    names: (&HashColumn<Id, Id>, &HashColumn<Id, Id>),
+) -> TabEdge<Id, Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    match edge {
+        TabEdge::Basic(e) => TabEdge::Basic(names.1.apply(e).expect("Morphism should be renamed")),
+        TabEdge::Square {
+            dom,
+            cod,
+            pre,
+            post,
+        } => TabEdge::Square {
+            dom: Box::new(rename_tab_mor(dom, names)),
+            cod: Box::new(rename_tab_mor(cod, names)),
+            pre: Box::new(rename_tab_edge(pre, names)),
+            post: Box::new(rename_tab_edge(post, names)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use ustr::{Ustr, ustr};
+
+    use super::*;
+    use crate::dbl::theory::*;
+    use crate::one::fin_category::FinMor;
+    use crate::stdlib::{models::*, theories::*};
+    use crate::validate::Validate;
+
+    type Mapping = DiscreteDblModelMapping<Ustr, Ustr>;
+
+    fn ob_mapping(pairs: &[(&str, &str)]) -> Mapping {
+        let mut mapping = Mapping::default();
+        for (x, y) in pairs {
+            mapping.assign_ob(ustr(x), ustr(y));
+        }
+        mapping
+    }
+
+    #[test]
+    fn glue_discrete_dbl_models() {
+        let th = Arc::new(th_signed_category());
+        let x = negative_feedback(th.clone());
+        let mut y = UstrDiscreteDblModel::new(th.clone());
+        let (u, v) = (ustr("u"), ustr("v"));
+        y.add_ob(u, ustr("Object"));
+        y.add_ob(v, ustr("Object"));
+        y.add_mor(ustr("inhibits"), u, v, FinMor::Generator(ustr("Negative")));
+        let mut z = UstrDiscreteDblModel::new(th.clone());
+        z.add_ob(ustr("shared"), ustr("Object"));
+
+        let f = ob_mapping(&[("shared", "y")]);
+        let g = ob_mapping(&[("shared", "u")]);
+        let pushout = discrete_dbl_pushout(&x, &y, &z, &f, &g).unwrap();
+        let model = &pushout.model;
+        assert!(model.validate().is_ok());
+        assert_eq!(model.ob_generators().count(), 3);
+        assert_eq!(model.mor_generators().count(), 3);
+        assert_eq!(pushout.right.apply_ob(&u), Some(ustr("y")));
+        assert_eq!(model.mor_generator_dom(&ustr("inhibits")), ustr("y"));
+        assert!(model.is_free());
+
+        // Gluing a morphism to a path of length two adds an equation.
+        z.add_ob(ustr("other"), ustr("Object"));
+        z.add_mor(ustr("path"), ustr("shared"), ustr("other"), FinMor::Generator(ustr("Negative")));
+        let mut f = ob_mapping(&[("shared", "x"), ("other", "x")]);
+        f.assign_basic_mor(ustr("path"), Path::pair(ustr("positive"), ustr("negative")));
+        let mut g = ob_mapping(&[("shared", "u"), ("other", "v")]);
+        g.assign_basic_mor(ustr("path"), Path::single(ustr("inhibits")));
+        let pushout = discrete_dbl_pushout(&x, &y, &z, &f, &g).unwrap();
+        assert!(pushout.model.validate().is_ok());
+        assert_eq!(pushout.model.ob_generators().count(), 2);
+        assert!(!pushout.model.is_free());
+
+        // Glued objects must have the same type.
+        let th = Arc::new(th_schema());
+        let x = walking_attr(th.clone());
+        let mut y = UstrDiscreteDblModel::new(th.clone());
+        y.add_ob(u, ustr("AttrType"));
+        let mut z = UstrDiscreteDblModel::new(th.clone());
+        z.add_ob(ustr("shared"), ustr("Entity"));
+        let f = ob_mapping(&[("shared", "entity")]);
+        let g = ob_mapping(&[("shared", "u")]);
+        let errors: Vec<_> = discrete_dbl_pushout(&x, &y, &z, &f, &g).err().unwrap().into();
+        assert_eq!(errors, vec![InvalidDblModelPushout::ObType(ustr("entity"))]);
+
+        // Generators that are not glued must have distinct names.
+        let z = UstrDiscreteDblModel::new(th);
+        let f = Mapping::default();
+        let errors: Vec<_> = discrete_dbl_pushout(&x, &x, &z, &f, &f).err().unwrap().into();
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&InvalidDblModelPushout::MorName(ustr("attr"))));
+    }
+
+    #[test]
+    fn glue_discrete_tab_models() {
+        let th = Arc::new(th_category_links());
+        let x = backward_link(th.clone());
+        let mut y = UstrDiscreteTabModel::new(th.clone());
+        let ob_type = TabObType::Basic(ustr("Object"));
+        let (u, v, h) = (ustr("u"), ustr("v"), ustr("h"));
+        y.add_ob(u, ob_type.clone());
+        y.add_ob(v, ob_type.clone());
+        y.add_mor(h, TabOb::Basic(u), TabOb::Basic(v), th.hom_type(ob_type.clone()));
+        y.add_mor(
+            ustr("link2"),
+            TabOb::Basic(u),
+            y.tabulated_gen(h),
+            TabMorType::Basic(ustr("Link")),
+        );
+        y.add_equation(ustr("eq"), PathEq::new(h.into(), h.into()));
+        let mut z = UstrDiscreteTabModel::new(th.clone());
+        z.add_ob(ustr("shared"), ob_type.clone());
+
+        let f = ob_mapping(&[("shared", "y")]);
+        let g = ob_mapping(&[("shared", "u")]);
+        let pushout = discrete_tab_pushout(&x, &y, &z, &f, &g).unwrap();
+        let model = &pushout.model;
+        assert!(model.validate().is_ok());
+        assert_eq!(model.ob_generators().count(), 3);
+        assert_eq!(model.mor_generators().count(), 4);
+        assert!(model.mor_generator_dom(&ustr("link2")) == TabOb::Basic(ustr("y")));
+        assert_eq!(model.equations().count(), 1);
+
+        // Morphisms not sent to basic morphisms are glued by equations.
+        z.add_ob(ustr("other"), ob_type.clone());
+        z.add_mor(
+            ustr("id"),
+            TabOb::Basic(ustr("shared")),
+            TabOb::Basic(ustr("other")),
+            th.hom_type(ob_type),
+        );
+        let mut f = ob_mapping(&[("shared", "y"), ("other", "y")]);
+        f.assign_basic_mor(ustr("id"), Path::Id(ustr("y")));
+        let mut g = ob_mapping(&[("shared", "u"), ("other", "v")]);
+        g.assign_basic_mor(ustr("id"), Path::single(h));
+        let pushout = discrete_tab_pushout(&x, &y, &z, &f, &g).unwrap();
+        let model = &pushout.model;
+        assert!(model.validate().is_ok());
+        assert_eq!(model.equations().count(), 2);
+        let id_y = Path::Id(TabOb::Basic(ustr("y")));
+        assert_eq!(model.decide_eq(&h.into(), &id_y), Some(true));
+    }
+}
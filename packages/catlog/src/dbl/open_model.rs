@@ -0,0 +1,302 @@
+/*! Open models of double theories.
+
+An **open model** is a model together with two lists of objects in it, its
+*inputs* and its *outputs*, through which it can be connected to other open
+models. Mathematically, an open model is a [structured
+cospan](https://ncatlab.org/nlab/show/structured+cospan) whose feet are discrete
+models: the lists of inputs and outputs are the legs `L(a) → M ← L(b)`, where
+`L(a)` is the discrete model on a list `a` of object types.
+
+Open models compose in *series*, by gluing the outputs of one model to the
+inputs of the next, and in *parallel*, by placing two models side by side. Both
+operations are computed by [pushouts](super::model_pushout) and so are subject
+to the same conditions on names: generators in different open models should
+have different names, except possibly for objects that are glued together.
+ */
+
+use std::hash::Hash;
+
+use nonempty::NonEmpty;
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-wasm")]
+use tsify_next::Tsify;
+
+use super::{model::*, model_morphism::*, model_pushout::*};
+use crate::validate::{self, Validate};
+
+/// A model with designated input and output objects.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenDblModel<Model, Id> {
+    model: Model,
+    inputs: Vec<Id>,
+    outputs: Vec<Id>,
+}
+
+impl<Model, Id> OpenDblModel<Model, Id>
+where
+    Model: FgDblModel<ObGen = Id> + GlueDblModel<Id>,
+    Id: Eq + Clone + Hash + Into<Model::Ob>,
+{
+    /// Creates an open model from a model and its input and output objects.
+    pub fn new(model: Model, inputs: Vec<Id>, outputs: Vec<Id>) -> Self {
+        Self {
+            model,
+            inputs,
+            outputs,
+        }
+    }
+
+    /** Creates the identity open model on a list of objects in a model.
+
+    The identity is the discrete model on the objects, which are both its
+    inputs and its outputs. The objects should be distinct.
+     */
+    pub fn identity(model: &Model, obs: Vec<Id>) -> Self {
+        Self::new(model.discrete_submodel(&obs), obs.clone(), obs)
+    }
+
+    /// Gets the underlying model.
+    pub fn model(&self) -> &Model {
+        &self.model
+    }
+
+    /// Gets the input objects.
+    pub fn inputs(&self) -> &[Id] {
+        &self.inputs
+    }
+
+    /// Gets the output objects.
+    pub fn outputs(&self) -> &[Id] {
+        &self.outputs
+    }
+
+    /// Consumes the open model, returning the underlying model.
+    pub fn into_model(self) -> Model {
+        self.model
+    }
+
+    /// Gets the types of the input objects.
+    pub fn input_types(&self) -> Vec<Model::ObType> {
+        self.inputs.iter().map(|x| self.model.ob_generator_type(x)).collect()
+    }
+
+    /// Gets the types of the output objects.
+    pub fn output_types(&self) -> Vec<Model::ObType> {
+        self.outputs.iter().map(|x| self.model.ob_generator_type(x)).collect()
+    }
+
+    /** Composes with another open model in series.
+
+    The outputs of this model are glued to the inputs of the other, which must
+    have the same number of objects with the same types.
+     */
+    pub fn compose(&self, other: &Self) -> Result<Self, NonEmpty<InvalidOpenDblModel<Id>>> {
+        if self.outputs.len() != other.inputs.len() {
+            return Err(NonEmpty::new(InvalidOpenDblModel::Boundary(
+                self.outputs.len(),
+                other.inputs.len(),
+            )));
+        }
+        let gluing = Gluing {
+            obs: std::iter::zip(self.outputs.iter().cloned(), other.inputs.iter().cloned())
+                .collect(),
+            mors: Vec::new(),
+        };
+        let pushout = self.glue(other, &gluing)?;
+        Ok(Self::new(
+            pushout.model,
+            rename(&pushout.left, &self.inputs),
+            rename(&pushout.right, &other.outputs),
+        ))
+    }
+
+    /** Composes with another open model in parallel.
+
+    The resulting model is the disjoint union of the two models, with inputs
+    and outputs concatenated.
+     */
+    pub fn tensor(&self, other: &Self) -> Result<Self, NonEmpty<InvalidOpenDblModel<Id>>> {
+        let pushout = self.glue(other, &Gluing::default())?;
+        let (left, right) = (&pushout.left, &pushout.right);
+        let inputs = [rename(left, &self.inputs), rename(right, &other.inputs)].concat();
+        let outputs = [rename(left, &self.outputs), rename(right, &other.outputs)].concat();
+        Ok(Self::new(pushout.model, inputs, outputs))
+    }
+
+    fn glue(
+        &self,
+        other: &Self,
+        gluing: &Gluing<Id>,
+    ) -> Result<GluedDblModel<Id, Model>, NonEmpty<InvalidOpenDblModel<Id>>> {
+        self.model
+            .glue(&other.model, gluing)
+            .map_err(|errs| errs.map(InvalidOpenDblModel::Glue))
+    }
+
+    /// Iterates over failures of the open model to be well defined.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidOpenDblModel<Id>> + '_ {
+        let has_ob = |x: &&Id| self.model.has_ob(&(*x).clone().into());
+        let inputs = self.inputs.iter().filter(move |x| !has_ob(x));
+        let outputs = self.outputs.iter().filter(move |x| !has_ob(x));
+        let inputs = inputs.map(|x| InvalidOpenDblModel::Input(x.clone()));
+        inputs.chain(outputs.map(|x| InvalidOpenDblModel::Output(x.clone())))
+    }
+}
+
+impl<Model, Id> Validate for OpenDblModel<Model, Id>
+where
+    Model: FgDblModel<ObGen = Id> + GlueDblModel<Id>,
+    Id: Eq + Clone + Hash + Into<Model::Ob>,
+{
+    type ValidationError = InvalidOpenDblModel<Id>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+/// Renames objects along a coprojection into a pushout.
+fn rename<Id>(mapping: &DiscreteDblModelMapping<Id, Id>, obs: &[Id]) -> Vec<Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    obs.iter()
+        .map(|x| mapping.apply_ob(x).expect("Object should be mapped into pushout"))
+        .collect()
+}
+
+/// An open model of a discrete double theory.
+pub type OpenDiscreteDblModel<Id, Cat> = OpenDblModel<DiscreteDblModel<Id, Cat>, Id>;
+
+/// An open model of a discrete tabulator theory.
+pub type OpenDiscreteTabModel<Id, ThId, S> = OpenDblModel<DiscreteTabModel<Id, ThId, S>, Id>;
+
+/// A failure of an open model to be well defined or to compose.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidOpenDblModel<Id> {
+    /// Input that is not an object in the model.
+    #[error("Input `{0}` is not an object in the model")]
+    Input(Id),
+
+    /// Output that is not an object in the model.
+    #[error("Output `{0}` is not an object in the model")]
+    Output(Id),
+
+    /// Numbers of outputs and inputs that do not match in a composite.
+    #[error("Cannot glue {0} outputs to {1} inputs")]
+    Boundary(usize, usize),
+
+    /// Failure to glue the underlying models.
+    #[error("Cannot glue models: {0}")]
+    Glue(InvalidDblModelPushout<Id>),
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use ustr::{Ustr, ustr};
+
+    use super::*;
+    use crate::dbl::theory::*;
+    use crate::one::FgCategory;
+    use crate::stdlib::theories::*;
+
+    type OpenModel = OpenDblModel<UstrDiscreteDblModel, Ustr>;
+
+    fn arrow(th: &Arc<UstrDiscreteDblTheory>, f: &str, x: &str, y: &str) -> OpenModel {
+        let mut model = UstrDiscreteDblModel::new(th.clone());
+        let (x, y) = (ustr(x), ustr(y));
+        model.add_ob(x, ustr("Object"));
+        model.add_ob(y, ustr("Object"));
+        model.add_mor(ustr(f), x, y, th.hom_type(ustr("Object")));
+        OpenModel::new(model, vec![x], vec![y])
+    }
+
+    #[test]
+    fn series_composition() {
+        let th = Arc::new(th_category());
+        let (f, g, h) =
+            (arrow(&th, "f", "a", "b"), arrow(&th, "g", "c", "d"), arrow(&th, "h", "e", "f"));
+        let fg = f.compose(&g).unwrap();
+        assert!(fg.validate().is_ok());
+        assert_eq!(fg.inputs(), &[ustr("a")]);
+        assert_eq!(fg.outputs(), &[ustr("d")]);
+        assert_eq!(fg.model().ob_generators().count(), 3);
+        assert_eq!(fg.model().mor_generator_dom(&ustr("g")), ustr("b"));
+
+        // Identity and associativity laws.
+        let id_a = OpenModel::identity(f.model(), f.inputs().to_vec());
+        let id_b = OpenModel::identity(f.model(), f.outputs().to_vec());
+        assert_eq!(id_a.compose(&f).unwrap(), f);
+        assert_eq!(f.compose(&id_b).unwrap(), f);
+        assert_eq!(fg.compose(&h).unwrap(), f.compose(&g.compose(&h).unwrap()).unwrap());
+
+        let errors: Vec<_> = fg.compose(&fg.tensor(&h).unwrap()).err().unwrap().into();
+        assert_eq!(errors, vec![InvalidOpenDblModel::Boundary(1, 2)]);
+    }
+
+    #[test]
+    fn parallel_composition() {
+        let th = Arc::new(th_category());
+        let (f, g, h) =
+            (arrow(&th, "f", "a", "b"), arrow(&th, "g", "c", "d"), arrow(&th, "h", "e", "f"));
+        let fg = f.tensor(&g).unwrap();
+        assert_eq!(fg.inputs(), &[ustr("a"), ustr("c")]);
+        assert_eq!(fg.outputs(), &[ustr("b"), ustr("d")]);
+        assert_eq!(fg.model().mor_generators().count(), 2);
+        assert_eq!(fg.tensor(&h).unwrap(), f.tensor(&g.tensor(&h).unwrap()).unwrap());
+
+        // Unit laws for the tensor product.
+        let unit = OpenModel::identity(f.model(), Vec::new());
+        assert_eq!(unit.tensor(&f).unwrap(), f);
+        let id = OpenModel::identity(fg.model(), fg.outputs().to_vec());
+        assert_eq!(fg.compose(&id).unwrap(), fg);
+
+        // Interchange law: (a∘b)⊗(c∘d) = (a⊗c)∘(b⊗d).
+        let (a, b) = (arrow(&th, "a", "w", "x"), arrow(&th, "b", "x2", "y"));
+        let (c, d) = (arrow(&th, "c", "z", "u"), arrow(&th, "d", "u2", "v"));
+        let lhs = a.compose(&b).unwrap().tensor(&c.compose(&d).unwrap()).unwrap();
+        let rhs = a.tensor(&c).unwrap().compose(&b.tensor(&d).unwrap()).unwrap();
+        assert!(lhs.validate().is_ok());
+        assert_eq!(lhs.outputs(), &[ustr("y"), ustr("v")]);
+        assert_eq!(lhs.model().mor_generators().count(), 4);
+        assert_eq!(lhs, rhs);
+
+        // Names must be distinct outside the boundary.
+        let errors: Vec<_> = f.tensor(&f).err().unwrap().into();
+        assert_eq!(errors.len(), 3);
+    }
+
+    #[test]
+    fn compose_stock_flow() {
+        let th = Arc::new(th_category_links());
+        let ob_type = TabObType::Basic(ustr("Object"));
+        let flow = |x: &str, y: &str, f: &str, link: Option<&str>| {
+            let mut model = UstrDiscreteTabModel::new(th.clone());
+            let (x, y, f) = (ustr(x), ustr(y), ustr(f));
+            model.add_ob(x, ob_type.clone());
+            model.add_ob(y, ob_type.clone());
+            model.add_mor(f, TabOb::Basic(x), TabOb::Basic(y), th.hom_type(ob_type.clone()));
+            if let Some(link) = link {
+                let tab = model.tabulated_gen(f);
+                model.add_mor(ustr(link), TabOb::Basic(y), tab, TabMorType::Basic(ustr("Link")));
+            }
+            OpenDblModel::new(model, vec![x], vec![y])
+        };
+        let infection = flow("S", "I", "infection", Some("contact"));
+        let recovery = flow("I2", "R", "recovery", None);
+        let sir = infection.compose(&recovery).unwrap();
+        assert!(sir.validate().is_ok());
+        assert!(sir.model().validate().is_ok());
+        assert_eq!(sir.model().ob_generators().count(), 3);
+        assert_eq!(sir.model().mor_generators().count(), 3);
+        assert!(sir.model().mor_generator_dom(&ustr("recovery")) == TabOb::Basic(ustr("I")));
+    }
+}
@@ -48,7 +48,7 @@ by the table:
 | Method                                      | Double theory          | Double category        |
 |---------------------------------------------|------------------------|------------------------|
 | [`hom_type`](DblTheory::hom_type)           | Hom type               | Identity proarrow      |
-| `hom_op`                                    | Hom operation          | Identity cell on arrow |
+| [`hom_op`](DblTheory::hom_op)               | Hom operation          | Identity cell on arrow |
 | [`compose_types`](DblTheory::compose_types) | Compose morphism types | Compose proarrows      |
 
 Finally, operations on both objects and morphisms have identities and can be
@@ -72,14 +72,27 @@ use std::hash::{BuildHasher, BuildHasherDefault, Hash, RandomState};
 
 use derivative::Derivative;
 use derive_more::From;
+use nonempty::NonEmpty;
 use ref_cast::RefCast;
+use thiserror::Error;
 use ustr::{IdentityHasher, Ustr};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-wasm")]
+use tsify_next::Tsify;
+
 use super::graph::ProedgeGraph;
-use super::{category::*, tree::DblTree};
-use crate::one::{Graph, path::Path};
-use crate::one::{category::*, fin_category::UstrFinCategory};
-use crate::validate::Validate;
+use super::{category::*, tree::*};
+use crate::one::fin_category::{
+    FinCategory, FinMor, FpCategory, InvalidFinCategory, InvalidFpCategory, UstrFinCategory,
+};
+use crate::one::{
+    Graph,
+    category::*,
+    path::{Path, PathEq},
+};
+use crate::validate::{self, Validate};
 use crate::zero::*;
 
 /** A double theory.
@@ -180,15 +193,27 @@ pub trait DblTheory {
         self.compose_ob_ops(Path::Id(x))
     }
 
-    /* Hom morphism operation on an object operation.
+    /** Hom morphism operation on an object operation.
 
     Viewing the double theory as a virtual double category, this is the unit
-    cell on an arrow.
-
-    TODO: Implementing this requires the VDCs have an interface for the
-    universal property of a unit.
+    cell on an arrow. The unit cell on an arrow `f: x -> y` is the factorization
+    through the unit on `x` of the unit extension on `y` precomposed with `f`,
+    so this method is available only when the theory is given as a VDC.
     */
-    //fn hom_op(&self, f: Self::ObOp) -> Self::MorOp;
+    fn hom_op(&self, f: Self::ObOp) -> Self::MorOp
+    where
+        Self: VDblCategory<
+                Ob = Self::ObType,
+                Arr = Self::ObOp,
+                Pro = Self::MorType,
+                Cell = Self::MorOp,
+            >,
+    {
+        let ext = self.unit_ext(self.cod(&f)).expect("A double theory should have all hom types");
+        let tree = DblTree::from_nodes([DblNode::Spine(f), DblNode::Cell(ext)]).unwrap();
+        self.through_unit(self.compose_cells(tree), 0)
+            .expect("A double theory should have hom operations")
+    }
 
     /// Compose operations on morphisms.
     fn compose_mor_ops(&self, tree: DblTree<Self::ObOp, Self::MorType, Self::MorOp>)
@@ -290,6 +315,13 @@ pub struct DiscreteDblTheory<Cat: FgCategory>(Cat);
 /// A discrete double theory with keys of type `Ustr`.
 pub type UstrDiscreteDblTheory = DiscreteDblTheory<UstrFinCategory>;
 
+impl<C: FgCategory> DiscreteDblTheory<C> {
+    /// Gets the category of object and morphism types in the theory.
+    pub fn category(&self) -> &C {
+        &self.0
+    }
+}
+
 impl<C: FgCategory> VDblCategory for DiscreteDblTheory<C>
 where
     C::Ob: Clone,
@@ -354,6 +386,13 @@ where
     fn compose_cells(&self, tree: DblTree<Self::Arr, Self::Pro, Self::Cell>) -> Self::Cell {
         tree.dom(UnderlyingDblGraph::ref_cast(self))
     }
+    fn through_composite(
+        &self,
+        path: Self::Cell,
+        range: std::ops::Range<usize>,
+    ) -> Option<Self::Cell> {
+        path.splice_in(UnderlyingGraph::ref_cast(&self.0), range, |sub| self.composite(sub))
+    }
 }
 
 impl<C: FgCategory + Validate> Validate for DiscreteDblTheory<C> {
@@ -366,6 +405,10 @@ impl<C: FgCategory + Validate> Validate for DiscreteDblTheory<C> {
 
 /// Object type in a discrete tabulator theory.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum TabObType<V, E> {
     /// Basic or generating object type.
     Basic(V),
@@ -376,6 +419,10 @@ pub enum TabObType<V, E> {
 
 /// Morphism type in a discrete tabulator theory.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum TabMorType<V, E> {
     /// Basic or generating morphism type.
     Basic(E),
@@ -386,6 +433,10 @@ pub enum TabMorType<V, E> {
 
 /// Projection onto object type in a discrete tabulator theory.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum TabObProj<V, E> {
     /// Projection from tabulator onto source of morphism type.
     Src(TabMorType<V, E>),
@@ -451,6 +502,13 @@ pub struct TabMorOp<V, E> {
     projections: Vec<TabMorProj<V, E>>,
 }
 
+impl<V, E> TabMorOp<V, E> {
+    /// Projections applied by the operation, in order of application.
+    pub fn projections(&self) -> &[TabMorProj<V, E>] {
+        &self.projections
+    }
+}
+
 /** A discrete tabulator theory.
 
 Loosely speaking, a discrete tabulator theory is a [discrete double
@@ -517,6 +575,152 @@ where
     pub fn make_mor_type(&mut self, e: E) -> bool {
         self.mor_types.insert(e)
     }
+
+    /// Sets the composite of two basic morphism types.
+    pub fn set_composite(&mut self, d: E, e: E, m: TabMorType<V, E>) {
+        self.compose_map.set((d, e), m);
+    }
+
+    /** Composes a pair of morphism types, if they are composable.
+
+    Unlike [`composite2`](VDblCategory::composite2), which returns nothing in
+    this case, an error describing the failure is returned.
+     */
+    pub fn try_composite2(
+        &self,
+        m: TabMorType<V, E>,
+        n: TabMorType<V, E>,
+    ) -> Result<TabMorType<V, E>, InvalidTabComposition<V, E>> {
+        if self.try_tgt(&m) != self.try_src(&n) || self.try_tgt(&m).is_none() {
+            return Err(InvalidTabComposition::NotComposable(m, n));
+        }
+        match (m, n) {
+            (m, TabMorType::Hom(_)) => Ok(m),
+            (TabMorType::Hom(_), n) => Ok(n),
+            (TabMorType::Basic(d), TabMorType::Basic(e)) => self
+                .compose_map
+                .apply(&(d.clone(), e.clone()))
+                .ok_or(InvalidTabComposition::Undefined(d, e)),
+        }
+    }
+
+    /// Composes a path of morphism types, if the composite is defined.
+    pub fn try_composite(
+        &self,
+        path: Path<TabObType<V, E>, TabMorType<V, E>>,
+    ) -> Result<TabMorType<V, E>, InvalidTabComposition<V, E>> {
+        match path {
+            Path::Id(x) => Ok(TabMorType::Hom(Box::new(x))),
+            Path::Seq(ms) => {
+                let NonEmpty { head, tail } = ms;
+                tail.into_iter().try_fold(head, |m, n| self.try_composite2(m, n))
+            }
+        }
+    }
+
+    /** Composes a tree of morphism operations, if their boundaries match.
+
+    Unlike [`compose_cells`](VDblCategory::compose_cells), which panics on an
+    ill-typed tree, an error describing the failure is returned.
+     */
+    pub fn try_compose_cells(
+        &self,
+        tree: DblTree<TabObOp<V, E>, TabMorType<V, E>, TabMorOp<V, E>>,
+    ) -> Result<TabMorOp<V, E>, InvalidTabComposition<V, E>> {
+        let graph = UnderlyingDblGraph::ref_cast(self);
+        let dom = tree.dom(graph);
+        let src = self.compose(tree.src(graph));
+        let tgt = self.compose(tree.tgt(graph));
+        if src.len() != tgt.len() {
+            return Err(InvalidTabComposition::BoundaryLength(src.len(), tgt.len()));
+        }
+        let projections = std::iter::zip(src, tgt)
+            .map(|pair| match pair {
+                (TabObProj::Src(m), TabObProj::Tgt(n)) if m == n => Ok(TabMorProj::Cone(m)),
+                (TabObProj::Src(m), TabObProj::Src(n)) if m == n => Ok(TabMorProj::Src(m)),
+                (TabObProj::Tgt(m), TabObProj::Tgt(n)) if m == n => Ok(TabMorProj::Tgt(m)),
+                (p, q) => Err(InvalidTabComposition::Projections(p, q)),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(TabMorOp { dom, projections })
+    }
+
+    /// Iterates over failures to be a well-defined discrete tabulator theory.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidDiscreteTabTheory<E>> + '_ {
+        let type_errors = self.mor_types.iter().flat_map(move |e| {
+            let mut errs = Vec::new();
+            match self.src.get(&e) {
+                Some(x) if !self.has_ob(x) => errs.push(InvalidDiscreteTabTheory::Src(e.clone())),
+                None => errs.push(InvalidDiscreteTabTheory::MissingSrc(e.clone())),
+                _ => {}
+            }
+            match self.tgt.get(&e) {
+                Some(x) if !self.has_ob(x) => errs.push(InvalidDiscreteTabTheory::Tgt(e.clone())),
+                None => errs.push(InvalidDiscreteTabTheory::MissingTgt(e)),
+                _ => {}
+            }
+            errs
+        });
+        let missing_errors = self.mor_types.iter().flat_map(move |d| {
+            self.mor_types.iter().filter_map(move |e| {
+                let composable = self.tgt.get(&d).is_some_and(|x| Some(x) == self.src.get(&e));
+                let pair = (d.clone(), e);
+                (composable && !self.compose_map.is_set(&pair))
+                    .then(|| InvalidDiscreteTabTheory::Composite(pair.0, pair.1))
+            })
+        });
+        let compose_errors = self.compose_map.iter().filter_map(move |((d, e), m)| {
+            let (d_mor, e_mor) = (TabMorType::Basic(d.clone()), TabMorType::Basic(e.clone()));
+            let composable = self.has_proarrow(&d_mor)
+                && self.has_proarrow(&e_mor)
+                && self.try_tgt(&d_mor).is_some()
+                && self.try_tgt(&d_mor) == self.try_src(&e_mor);
+            if !composable {
+                Some(InvalidDiscreteTabTheory::NotComposable(d, e))
+            } else if !self.has_proarrow(m)
+                || self.try_src(m).is_none()
+                || self.try_tgt(m).is_none()
+            {
+                Some(InvalidDiscreteTabTheory::CompositeType(d, e))
+            } else if self.try_src(m) != self.try_src(&d_mor) {
+                Some(InvalidDiscreteTabTheory::CompositeSrc(d, e))
+            } else if self.try_tgt(m) != self.try_tgt(&e_mor) {
+                Some(InvalidDiscreteTabTheory::CompositeTgt(d, e))
+            } else {
+                None
+            }
+        });
+        type_errors.chain(missing_errors).chain(compose_errors)
+    }
+
+    /// Gets the source of a morphism type, if it is defined.
+    fn try_src(&self, m: &TabMorType<V, E>) -> Option<TabObType<V, E>> {
+        match m {
+            TabMorType::Basic(e) => self.src.apply(e),
+            TabMorType::Hom(x) => Some((**x).clone()),
+        }
+    }
+
+    /// Gets the target of a morphism type, if it is defined.
+    fn try_tgt(&self, m: &TabMorType<V, E>) -> Option<TabObType<V, E>> {
+        match m {
+            TabMorType::Basic(e) => self.tgt.apply(e),
+            TabMorType::Hom(x) => Some((**x).clone()),
+        }
+    }
+}
+
+impl<V, E, S> Validate for DiscreteTabTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type ValidationError = InvalidDiscreteTabTheory<E>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
 }
 
 /// Graph of objects and projection arrows in discrete tabulator theory.
@@ -634,21 +838,13 @@ where
     }
 
     fn composite2(&self, m: Self::Pro, n: Self::Pro) -> Option<Self::Pro> {
-        let mn = match (m, n) {
-            (m, TabMorType::Hom(y)) if self.tgt(&m) == *y => m,
-            (TabMorType::Hom(x), n) if self.src(&n) == *x => n,
-            (TabMorType::Basic(d), TabMorType::Basic(e)) => {
-                self.compose_map.apply(&(d, e)).expect("Composition should be defined")
-            }
-            _ => panic!("Ill-typed composite of morphism types in discrete tabulator theory"),
-        };
-        Some(mn)
+        self.try_composite2(m, n).ok()
     }
     fn unit(&self, x: Self::Ob) -> Option<Self::Pro> {
         Some(TabMorType::Hom(Box::new(x)))
     }
     fn composite(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Pro> {
-        Some(path.reduce(|x| self.unit(x).unwrap(), |m, n| self.composite2(m, n).unwrap()))
+        self.try_composite(path).ok()
     }
     fn composite_ext(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Cell> {
         Some(TabMorOp {
@@ -657,24 +853,1117 @@ where
         })
     }
 
+    fn through_composite(
+        &self,
+        cell: Self::Cell,
+        range: std::ops::Range<usize>,
+    ) -> Option<Self::Cell> {
+        let graph = ProedgeGraph::ref_cast(UnderlyingDblGraph::ref_cast(self));
+        let TabMorOp { dom, projections } = cell;
+        let dom = dom.splice_in(graph, range, |sub| self.try_composite(sub).ok())?;
+        Some(TabMorOp { dom, projections })
+    }
+
+    /// Composes a tree of cells, panicking if it is ill-typed.
+    ///
+    /// Use [`try_compose_cells`](DiscreteTabTheory::try_compose_cells) to
+    /// recover from an ill-typed tree.
     fn compose_cells(&self, tree: DblTree<Self::Arr, Self::Pro, Self::Cell>) -> Self::Cell {
-        let graph = UnderlyingDblGraph::ref_cast(self);
-        let dom = tree.dom(graph);
-        let src = self.compose(tree.src(graph));
-        let tgt = self.compose(tree.tgt(graph));
-        assert_eq!(src.len(), tgt.len(), "Source/target boundaries should have equal length");
-        let projections = std::iter::zip(src, tgt)
-            .map(|pair| match pair {
-                (TabObProj::Src(m), TabObProj::Tgt(n)) if m == n => TabMorProj::Cone(m),
-                (TabObProj::Src(m), TabObProj::Src(n)) if m == n => TabMorProj::Src(m),
-                (TabObProj::Tgt(m), TabObProj::Tgt(n)) if m == n => TabMorProj::Tgt(m),
-                _ => panic!("Projection cells should have compatible source/target boundaries"),
+        self.try_compose_cells(tree)
+            .unwrap_or_else(|_| panic!("Tree of cells should be composable"))
+    }
+}
+
+/// A failure of a discrete tabulator theory to be well defined.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidDiscreteTabTheory<E> {
+    /// Morphism type whose source is not set.
+    #[error("Source of morphism type `{0}` is not defined")]
+    MissingSrc(E),
+
+    /// Morphism type whose target is not set.
+    #[error("Target of morphism type `{0}` is not defined")]
+    MissingTgt(E),
+
+    /// Morphism type whose source is not an object type in the theory.
+    #[error("Source of morphism type `{0}` is not an object type in the theory")]
+    Src(E),
+
+    /// Morphism type whose target is not an object type in the theory.
+    #[error("Target of morphism type `{0}` is not an object type in the theory")]
+    Tgt(E),
+
+    /// Composite of a composable pair of morphism types is not defined.
+    #[error("Composite of morphism types `{0}` and `{1}` is not defined")]
+    Composite(E, E),
+
+    /// Composite assigned to a pair of morphism types that are not composable.
+    #[error("Morphism types `{0}` and `{1}` are assigned a composite but are not composable")]
+    NotComposable(E, E),
+
+    /// Composite of a pair of morphism types is not a morphism type in the theory.
+    #[error("Composite of morphism types `{0}` and `{1}` is not a morphism type in the theory")]
+    CompositeType(E, E),
+
+    /// Composite of a pair of morphism types has incompatible source.
+    #[error("Composite of morphism types `{0}` and `{1}` has incompatible source")]
+    CompositeSrc(E, E),
+
+    /// Composite of a pair of morphism types has incompatible target.
+    #[error("Composite of morphism types `{0}` and `{1}` has incompatible target")]
+    CompositeTgt(E, E),
+}
+
+/// A failure to compose in a discrete tabulator theory.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidTabComposition<V, E> {
+    /// Pair of morphism types that are not composable.
+    #[error("Morphism types are not composable")]
+    NotComposable(TabMorType<V, E>, TabMorType<V, E>),
+
+    /// Pair of basic morphism types whose composite is not defined.
+    #[error("Composite of morphism types `{0}` and `{1}` is not defined")]
+    Undefined(E, E),
+
+    /// Source and target boundaries of different lengths.
+    #[error("Source boundary has length {0} but target boundary has length {1}")]
+    BoundaryLength(usize, usize),
+
+    /// Source and target projections that do not form a projection cell.
+    #[error("Projections do not form a projection cell")]
+    Projections(TabObProj<V, E>, TabObProj<V, E>),
+}
+
+/** Morphism operation in a simple double theory, as a unary term.
+
+In a double category, every cell factors uniquely through the composite of its
+domain, so morphism operations in a [simple double theory](SimpleDblTheory) are
+determined by unary cells. Such cells are represented syntactically, as terms
+built from generators, identities, and Hom operations by vertical and horizontal
+composition. The smart constructors used by the theory reduce terms using the
+unit laws, but no attempt is made to normalize with respect to the interchange
+law.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SimpleMorOpTerm<V, E> {
+    /// Generating morphism operation.
+    Generator(E),
+
+    /// Identity operation on a morphism type.
+    Id(FinMor<V, E>),
+
+    /// Hom operation on an object operation.
+    Hom(Path<V, E>),
+
+    /// Vertical composite of morphism operations, in diagrammatic order.
+    Vertical(Vec<SimpleMorOpTerm<V, E>>),
+
+    /// Horizontal composite of morphism operations, from source to target.
+    Horizontal(Vec<SimpleMorOpTerm<V, E>>),
+}
+
+/// Morphism operation in a simple double theory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimpleMorOp<V, E> {
+    dom: Path<V, FinMor<V, E>>,
+    term: SimpleMorOpTerm<V, E>,
+}
+
+impl<V, E> SimpleMorOp<V, E> {
+    /// Unary term from the composite of the domain of the operation.
+    pub fn term(&self) -> &SimpleMorOpTerm<V, E> {
+        &self.term
+    }
+}
+
+/// Boundary of a unary morphism operation in a simple double theory.
+pub(crate) struct SimpleMorOpBoundary<V, E> {
+    pub(crate) dom: FinMor<V, E>,
+    pub(crate) cod: FinMor<V, E>,
+    pub(crate) src: Path<V, E>,
+    pub(crate) tgt: Path<V, E>,
+}
+
+/// Node in a double tree of morphism operations in a simple double theory.
+type SimpleDblNode<V, E> = DblNode<Path<V, E>, FinMor<V, E>, SimpleMorOp<V, E>>;
+
+/** A finitely presented simple double theory.
+
+A *simple* double theory is a small strict double category. Here such a theory
+is presented by generating object types, object operations, morphism types, and
+morphism operations:
+
+- object types and object operations form a [finitely presented
+  category](FpCategory), whose equations are decided by completing them to a
+  rewriting system, so that object operations are represented by paths in
+  normal form;
+- morphism types form a [finite category](FinCategory), as in a [discrete double
+  theory](DiscreteDblTheory), whose composition law must be given explicitly;
+- morphism operations are freely generated by cells with a single morphism type
+  as domain and codomain and with arbitrary object operations as source and
+  target.
+
+Thus the only equations imposed are those between object operations and the
+composition law of morphism types; morphism operations cannot be related. See
+[`SimpleMorOpTerm`] for how morphism operations are represented.
+ */
+#[derive(Clone, Derivative)]
+#[derivative(Default(bound = "S: Default"))]
+pub struct SimpleDblTheory<V, E, S = RandomState> {
+    ob_ops: FpCategory<V, E, E, S>,
+    mor_types: FinCategory<V, E, S>,
+    mor_ops: HashFinSet<E, S>,
+    mor_op_dom: HashColumn<E, FinMor<V, E>, S>,
+    mor_op_cod: HashColumn<E, FinMor<V, E>, S>,
+    mor_op_src: HashColumn<E, Path<V, E>, S>,
+    mor_op_tgt: HashColumn<E, Path<V, E>, S>,
+}
+
+/// Simple double theory with names of type `Ustr`.
+pub type UstrSimpleDblTheory = SimpleDblTheory<Ustr, Ustr, BuildHasherDefault<IdentityHasher>>;
+
+impl<V, E, S> SimpleDblTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    /// Creates an empty simple double theory.
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Default::default()
+    }
+
+    /// Adds a basic object type to the theory.
+    pub fn add_ob_type(&mut self, v: V) -> bool {
+        self.mor_types.add_ob_generator(v.clone());
+        self.ob_ops.add_ob_generator(v)
+    }
+
+    /// Adds a basic object operation to the theory.
+    pub fn add_ob_op(&mut self, e: E, dom: V, cod: V) -> bool {
+        self.ob_ops.add_mor_generator(e, dom, cod)
+    }
+
+    /// Adds an equation between object operations to the theory.
+    pub fn add_ob_op_equation(&mut self, key: E, eq: PathEq<V, E>) {
+        self.ob_ops.add_equation(key, eq);
+    }
+
+    /// Adds a basic morphism type to the theory.
+    pub fn add_mor_type(&mut self, e: E, src: V, tgt: V) -> bool {
+        self.mor_types.add_mor_generator(e, src, tgt)
+    }
+
+    /// Sets the composite of two basic morphism types.
+    pub fn set_composite(&mut self, d: E, e: E, m: FinMor<V, E>) {
+        self.mor_types.set_composite(d, e, m);
+    }
+
+    /// Adds a basic morphism operation to the theory.
+    pub fn add_mor_op(
+        &mut self,
+        e: E,
+        dom: FinMor<V, E>,
+        cod: FinMor<V, E>,
+        src: Path<V, E>,
+        tgt: Path<V, E>,
+    ) -> bool {
+        self.mor_op_dom.set(e.clone(), dom);
+        self.mor_op_cod.set(e.clone(), cod);
+        self.mor_op_src.set(e.clone(), src);
+        self.mor_op_tgt.set(e.clone(), tgt);
+        self.mor_ops.insert(e)
+    }
+
+    /// Gets a basic morphism operation as a cell in the theory.
+    pub fn basic_mor_op(&self, e: E) -> SimpleMorOp<V, E> {
+        let dom = self.mor_op_dom.apply(&e).expect("Domain of morphism operation should be set");
+        SimpleMorOp {
+            dom: Path::single(dom),
+            term: SimpleMorOpTerm::Generator(e),
+        }
+    }
+
+    /** Factors a morphism operation through the composite of its domain.
+
+    The result is a unary morphism operation acting in the same way on
+    morphisms, as in [`through_composite`](VDblCategory::through_composite).
+     */
+    pub fn unary_mor_op(&self, op: SimpleMorOp<V, E>) -> SimpleMorOp<V, E> {
+        let dom = self.composite(op.dom).expect("Morphism types should have composite");
+        SimpleMorOp {
+            dom: Path::single(dom),
+            term: op.term,
+        }
+    }
+
+    /** Reduces an object operation to normal form.
+
+    Falls back to the given path if the equations between object operations
+    cannot be completed to a rewriting system within a fixed bound.
+     */
+    fn normalize_ob_op(&self, path: Path<V, E>) -> Path<V, E> {
+        if self.ob_ops.is_free() {
+            return path;
+        }
+        self.ob_ops.compose_normal(Path::single(path.clone())).unwrap_or(path)
+    }
+
+    /// Composes a path of morphism types, if the composite is defined.
+    fn try_composite(&self, path: Path<V, FinMor<V, E>>) -> Option<FinMor<V, E>> {
+        match path {
+            Path::Id(x) => self.mor_types.has_ob(&x).then_some(FinMor::Id(x)),
+            Path::Seq(ms) => {
+                let (head, tail) = ms.split_first();
+                let head = self.mor_types.has_mor(head).then(|| head.clone())?;
+                tail.iter().try_fold(head, |m, n| self.mor_types.try_compose2(m, n.clone()))
+            }
+        }
+    }
+
+    /// Computes the boundary of a term, if it is well typed.
+    pub(crate) fn boundary(
+        &self,
+        term: &SimpleMorOpTerm<V, E>,
+    ) -> Option<SimpleMorOpBoundary<V, E>> {
+        type Boundary<V, E> = SimpleMorOpBoundary<V, E>;
+        match term {
+            SimpleMorOpTerm::Generator(e) => {
+                if !self.mor_ops.contains(e) {
+                    return None;
+                }
+                Some(Boundary {
+                    dom: self.mor_op_dom.apply(e)?,
+                    cod: self.mor_op_cod.apply(e)?,
+                    src: self.mor_op_src.apply(e)?,
+                    tgt: self.mor_op_tgt.apply(e)?,
+                })
+            }
+            SimpleMorOpTerm::Id(m) => self.mor_types.has_mor(m).then(|| Boundary {
+                dom: m.clone(),
+                cod: m.clone(),
+                src: Path::Id(self.mor_types.dom(m)),
+                tgt: Path::Id(self.mor_types.cod(m)),
+            }),
+            SimpleMorOpTerm::Hom(f) => self.ob_ops.has_mor(f).then(|| Boundary {
+                dom: FinMor::Id(self.ob_ops.dom(f)),
+                cod: FinMor::Id(self.ob_ops.cod(f)),
+                src: f.clone(),
+                tgt: f.clone(),
+            }),
+            SimpleMorOpTerm::Vertical(terms) => {
+                let mut bounds = terms.iter().map(|t| self.boundary(t));
+                let first = bounds.next()??;
+                bounds.try_fold(first, |b, next| {
+                    let next = next?;
+                    let graph = self.ob_ops.generators();
+                    (b.cod == next.dom).then_some(())?;
+                    Some(Boundary {
+                        dom: b.dom,
+                        cod: next.cod,
+                        src: b.src.concat_in(graph, next.src)?,
+                        tgt: b.tgt.concat_in(graph, next.tgt)?,
+                    })
+                })
+            }
+            SimpleMorOpTerm::Horizontal(terms) => {
+                let mut bounds = terms.iter().map(|t| self.boundary(t));
+                let first = bounds.next()??;
+                bounds.try_fold(first, |b, next| {
+                    let next = next?;
+                    let tgt = self.normalize_ob_op(b.tgt);
+                    (tgt == self.normalize_ob_op(next.src)).then_some(())?;
+                    Some(Boundary {
+                        dom: self.mor_types.try_compose2(b.dom, next.dom)?,
+                        cod: self.mor_types.try_compose2(b.cod, next.cod)?,
+                        src: b.src,
+                        tgt: next.tgt,
+                    })
+                })
+            }
+        }
+    }
+
+    /// Computes the boundary of a term, or panics if it is ill typed.
+    pub(crate) fn unwrap_boundary(
+        &self,
+        term: &SimpleMorOpTerm<V, E>,
+    ) -> SimpleMorOpBoundary<V, E> {
+        self.boundary(term).expect("Morphism operation should be well typed")
+    }
+
+    /// Constructs the Hom operation on an object operation.
+    fn hom_term(&self, f: Path<V, E>) -> SimpleMorOpTerm<V, E> {
+        match self.normalize_ob_op(f) {
+            Path::Id(x) => SimpleMorOpTerm::Id(FinMor::Id(x)),
+            f => SimpleMorOpTerm::Hom(f),
+        }
+    }
+
+    /// Composes terms vertically, reducing by the unit laws.
+    fn vertical(
+        &self,
+        terms: impl IntoIterator<Item = SimpleMorOpTerm<V, E>>,
+    ) -> SimpleMorOpTerm<V, E> {
+        let mut result = Vec::new();
+        let mut identity = None;
+        let mut stack: Vec<_> = terms.into_iter().collect();
+        stack.reverse();
+        while let Some(term) = stack.pop() {
+            match term {
+                SimpleMorOpTerm::Vertical(terms) => stack.extend(terms.into_iter().rev()),
+                SimpleMorOpTerm::Id(m) => {
+                    identity.get_or_insert(SimpleMorOpTerm::Id(m));
+                }
+                SimpleMorOpTerm::Hom(g) => match result.pop() {
+                    Some(SimpleMorOpTerm::Hom(f)) => {
+                        let fg = f.concat_in(self.ob_ops.generators(), g);
+                        match self.hom_term(fg.expect("Object operations should be composable")) {
+                            SimpleMorOpTerm::Id(m) => {
+                                identity.get_or_insert(SimpleMorOpTerm::Id(m));
+                            }
+                            term => result.push(term),
+                        }
+                    }
+                    prev => {
+                        result.extend(prev);
+                        result.push(SimpleMorOpTerm::Hom(g));
+                    }
+                },
+                term => result.push(term),
+            }
+        }
+        match result.len() {
+            0 => identity.expect("Vertical composite should have at least one term"),
+            1 => result.pop().unwrap(),
+            _ => SimpleMorOpTerm::Vertical(result),
+        }
+    }
+
+    /// Composes terms horizontally, reducing by the unit laws.
+    fn horizontal(
+        &self,
+        terms: impl IntoIterator<Item = SimpleMorOpTerm<V, E>>,
+    ) -> SimpleMorOpTerm<V, E> {
+        let mut result = Vec::new();
+        let mut unit = None;
+        let mut stack: Vec<_> = terms.into_iter().collect();
+        stack.reverse();
+        while let Some(term) = stack.pop() {
+            match term {
+                SimpleMorOpTerm::Horizontal(terms) => stack.extend(terms.into_iter().rev()),
+                SimpleMorOpTerm::Hom(_) | SimpleMorOpTerm::Id(FinMor::Id(_)) => {
+                    unit.get_or_insert(term);
+                }
+                SimpleMorOpTerm::Id(n) => match result.pop() {
+                    Some(SimpleMorOpTerm::Id(m)) => {
+                        let mn = self.mor_types.try_compose2(m, n);
+                        result.push(SimpleMorOpTerm::Id(
+                            mn.expect("Morphism types should have composite"),
+                        ));
+                    }
+                    prev => {
+                        result.extend(prev);
+                        result.push(SimpleMorOpTerm::Id(n));
+                    }
+                },
+                term => result.push(term),
+            }
+        }
+        match result.len() {
+            0 => unit.expect("Horizontal composite should have at least one term"),
+            1 => result.pop().unwrap(),
+            _ => SimpleMorOpTerm::Horizontal(result),
+        }
+    }
+
+    /// Computes the unary term for a subtree of a double tree.
+    fn tree_term(&self, node: ego_tree::NodeRef<'_, SimpleDblNode<V, E>>) -> SimpleMorOpTerm<V, E> {
+        let term = match node.value() {
+            DblNode::Cell(op) => op.term.clone(),
+            DblNode::Id(m) => SimpleMorOpTerm::Id(m.clone()),
+            DblNode::Spine(f) => self.hom_term(f.clone()),
+        };
+        if node.has_children() {
+            let children = self.horizontal(node.children().map(|child| self.tree_term(child)));
+            self.vertical([children, term])
+        } else {
+            term
+        }
+    }
+
+    /// Iterates over failures to be a well-defined simple double theory.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidSimpleDblTheory<E>> + '_ {
+        type Invalid<E> = InvalidSimpleDblTheory<E>;
+        let ob_op_errors = self.ob_ops.iter_invalid().map(|err| match err {
+            InvalidFpCategory::Dom(e) => Invalid::ObOpDom(e),
+            InvalidFpCategory::Cod(e) => Invalid::ObOpCod(e),
+            InvalidFpCategory::EqLhs(key)
+            | InvalidFpCategory::EqRhs(key)
+            | InvalidFpCategory::EqSrc(key)
+            | InvalidFpCategory::EqTgt(key) => Invalid::ObOpEq(key),
+        });
+        let mor_type_errors = self.mor_types.iter_invalid().map(Invalid::MorType);
+        let mor_op_errors = self.mor_ops.iter().flat_map(move |e| {
+            let mut errs = Vec::new();
+            let dom = self.mor_op_dom.get(&e).filter(|m| self.mor_types.has_mor(m));
+            let cod = self.mor_op_cod.get(&e).filter(|m| self.mor_types.has_mor(m));
+            let src = self.mor_op_src.get(&e).filter(|f| self.ob_ops.has_mor(f));
+            let tgt = self.mor_op_tgt.get(&e).filter(|f| self.ob_ops.has_mor(f));
+            if dom.is_none() {
+                errs.push(Invalid::MorOpDom(e.clone()));
+            }
+            if cod.is_none() {
+                errs.push(Invalid::MorOpCod(e.clone()));
+            }
+            let (src_ok, tgt_ok) = match (dom, cod) {
+                (Some(m), Some(n)) => (
+                    src.is_some_and(|f| {
+                        self.ob_ops.dom(f) == self.mor_types.dom(m)
+                            && self.ob_ops.cod(f) == self.mor_types.dom(n)
+                    }),
+                    tgt.is_some_and(|f| {
+                        self.ob_ops.dom(f) == self.mor_types.cod(m)
+                            && self.ob_ops.cod(f) == self.mor_types.cod(n)
+                    }),
+                ),
+                _ => (src.is_some(), tgt.is_some()),
+            };
+            if !src_ok {
+                errs.push(Invalid::MorOpSrc(e.clone()));
+            }
+            if !tgt_ok {
+                errs.push(Invalid::MorOpTgt(e));
+            }
+            errs
+        });
+        ob_op_errors.chain(mor_type_errors).chain(mor_op_errors)
+    }
+}
+
+impl<V, E, S> Validate for SimpleDblTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type ValidationError = InvalidSimpleDblTheory<E>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+impl<V, E, S> VDblCategory for SimpleDblTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Ord + Clone + Hash,
+    S: BuildHasher,
+{
+    type Ob = V;
+    type Arr = Path<V, E>;
+    type Pro = FinMor<V, E>;
+    type Cell = SimpleMorOp<V, E>;
+
+    fn has_ob(&self, x: &Self::Ob) -> bool {
+        self.ob_ops.has_ob(x)
+    }
+    fn has_arrow(&self, f: &Self::Arr) -> bool {
+        self.ob_ops.has_mor(f)
+    }
+    fn has_proarrow(&self, m: &Self::Pro) -> bool {
+        self.mor_types.has_mor(m)
+    }
+    fn has_cell(&self, op: &Self::Cell) -> bool {
+        let Some(boundary) = self.boundary(&op.term) else {
+            return false;
+        };
+        self.try_composite(op.dom.clone()) == Some(boundary.dom)
+    }
+
+    fn dom(&self, f: &Self::Arr) -> Self::Ob {
+        self.ob_ops.dom(f)
+    }
+    fn cod(&self, f: &Self::Arr) -> Self::Ob {
+        self.ob_ops.cod(f)
+    }
+    fn src(&self, m: &Self::Pro) -> Self::Ob {
+        self.mor_types.dom(m)
+    }
+    fn tgt(&self, m: &Self::Pro) -> Self::Ob {
+        self.mor_types.cod(m)
+    }
+
+    fn cell_dom(&self, op: &Self::Cell) -> Path<Self::Ob, Self::Pro> {
+        op.dom.clone()
+    }
+    fn cell_cod(&self, op: &Self::Cell) -> Self::Pro {
+        self.unwrap_boundary(&op.term).cod
+    }
+    fn cell_src(&self, op: &Self::Cell) -> Self::Arr {
+        self.normalize_ob_op(self.unwrap_boundary(&op.term).src)
+    }
+    fn cell_tgt(&self, op: &Self::Cell) -> Self::Arr {
+        self.normalize_ob_op(self.unwrap_boundary(&op.term).tgt)
+    }
+
+    fn compose(&self, path: Path<Self::Ob, Self::Arr>) -> Self::Arr {
+        self.normalize_ob_op(self.ob_ops.compose(path))
+    }
+
+    fn composite(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Pro> {
+        self.try_composite(path)
+    }
+    fn composite_ext(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Cell> {
+        let m = self.try_composite(path.clone())?;
+        Some(SimpleMorOp {
+            dom: path,
+            term: SimpleMorOpTerm::Id(m),
+        })
+    }
+
+    fn through_composite(
+        &self,
+        op: Self::Cell,
+        range: std::ops::Range<usize>,
+    ) -> Option<Self::Cell> {
+        let graph = ProedgeGraph::ref_cast(UnderlyingDblGraph::ref_cast(self));
+        let SimpleMorOp { dom, term } = op;
+        let dom = dom.splice_in(graph, range, |sub| self.try_composite(sub))?;
+        Some(SimpleMorOp { dom, term })
+    }
+
+    fn compose_cells(&self, tree: DblTree<Self::Arr, Self::Pro, Self::Cell>) -> Self::Cell {
+        let dom = tree.dom(UnderlyingDblGraph::ref_cast(self));
+        let term = self.tree_term(tree.0.root());
+        SimpleMorOp { dom, term }
+    }
+}
+
+/// A failure of a simple double theory to be well defined.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidSimpleDblTheory<E> {
+    /// Object operation whose domain is not an object type in the theory.
+    #[error("Domain of object operation `{0}` is not an object type in the theory")]
+    ObOpDom(E),
+
+    /// Object operation whose codomain is not an object type in the theory.
+    #[error("Codomain of object operation `{0}` is not an object type in the theory")]
+    ObOpCod(E),
+
+    /// Equation between object operations that is not well defined.
+    #[error("Equation `{0}` between object operations is not well defined")]
+    ObOpEq(E),
+
+    /// Failure of the morphism types to form a finite category.
+    #[error("Morphism types do not form a category: {0}")]
+    MorType(InvalidFinCategory<E>),
+
+    /// Morphism operation whose domain is not a morphism type in the theory.
+    #[error("Domain of morphism operation `{0}` is not a morphism type in the theory")]
+    MorOpDom(E),
+
+    /// Morphism operation whose codomain is not a morphism type in the theory.
+    #[error("Codomain of morphism operation `{0}` is not a morphism type in the theory")]
+    MorOpCod(E),
+
+    /// Morphism operation with missing or incompatible source operation.
+    #[error("Source of morphism operation `{0}` is not a compatible object operation")]
+    MorOpSrc(E),
+
+    /// Morphism operation with missing or incompatible target operation.
+    #[error("Target of morphism operation `{0}` is not a compatible object operation")]
+    MorOpTgt(E),
+}
+
+/** A modality on object and morphism types.
+
+Currently the only modalities are list modalities, whose models take a category
+to its free monoidal or free symmetric monoidal category. Applying a list
+modality to an object type yields a type whose objects in models are lists of
+objects of the original type. Symmetric lists are equal up to permutation, but
+models do not yet have symmetry morphisms, such as a braiding.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum Modality {
+    /// Lists, generating a free monoidal category.
+    List,
+
+    /// Symmetric lists, meant to generate a free symmetric monoidal category.
+    SymList,
+}
+
+/** A basic entity with a sequence of modalities applied to it.
+
+The modalities are stored in order of application, so that the last modality is
+the outermost one.
+ */
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ModeApp<T> {
+    /// The basic entity.
+    pub arg: T,
+
+    /// The modalities applied to the entity, from innermost to outermost.
+    pub modalities: Vec<Modality>,
+}
+
+impl<T> ModeApp<T> {
+    /// Constructs a basic entity with no modalities applied.
+    pub fn new(arg: T) -> Self {
+        Self {
+            arg,
+            modalities: Vec::new(),
+        }
+    }
+
+    /// Applies a modality, which becomes the outermost one.
+    pub fn apply(mut self, modality: Modality) -> Self {
+        self.modalities.push(modality);
+        self
+    }
+
+    /// Applies a sequence of modalities, from innermost to outermost.
+    pub fn apply_all(mut self, modalities: impl IntoIterator<Item = Modality>) -> Self {
+        self.modalities.extend(modalities);
+        self
+    }
+
+    /// Removes the outermost modality, returning it if there is one.
+    pub fn unapply(mut self) -> (Self, Option<Modality>) {
+        let modality = self.modalities.pop();
+        (self, modality)
+    }
+}
+
+impl<T> From<T> for ModeApp<T> {
+    fn from(value: T) -> Self {
+        ModeApp::new(value)
+    }
+}
+
+/// Object type in a modal double theory.
+pub type ModalObType<V> = ModeApp<V>;
+
+/// Object operation in a modal double theory.
+pub type ModalObOp<V, E> = Path<ModalObType<V>, ModeApp<E>>;
+
+/// Morphism type in a modal double theory.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum ModalMorType<V, E> {
+    /// Basic morphism type with modalities applied.
+    Basic(ModeApp<E>),
+
+    /// Hom type on an object type.
+    Hom(ModalObType<V>),
+}
+
+/** Morphism operation in a modal double theory.
+
+Besides the extension cells witnessing composites of morphism types, the only
+morphism operations are the Hom operations on object operations. A morphism
+operation is thus determined by its domain along with its source and target
+operations, which are both identities or else are equal.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ModalMorOp<V, E> {
+    dom: Path<ModalObType<V>, ModalMorType<V, E>>,
+    src: ModalObOp<V, E>,
+    tgt: ModalObOp<V, E>,
+}
+
+impl<V, E> ModalMorOp<V, E> {
+    /// Object operation along which the Hom operation acts, if any.
+    pub fn hom_op(&self) -> Option<&ModalObOp<V, E>> {
+        (!self.src.is_empty()).then_some(&self.src)
+    }
+}
+
+/** A modal double theory with list modalities.
+
+A **modal double theory** is a double theory whose object and morphism types
+can have modalities applied to them. Here the modalities are the [list
+modalities](Modality), so that a model of the theory can have morphisms from
+lists of objects to lists of objects, as in monoidal categories, multicategories,
+and Petri nets. Basic object operations and basic morphism types can have
+arbitrary modal object types as their domains and codomains, while modalities
+act on object operations and morphism types by functoriality. Composites of
+basic morphism types must be specified explicitly, as in a [discrete tabulator
+theory](DiscreteTabTheory).
+ */
+#[derive(Clone, Derivative)]
+#[derivative(Default(bound = "S: Default"))]
+pub struct ModalDblTheory<V, E, S = RandomState> {
+    ob_types: HashFinSet<V, S>,
+    ob_ops: HashFinSet<E, S>,
+    ob_op_dom: HashColumn<E, ModalObType<V>, S>,
+    ob_op_cod: HashColumn<E, ModalObType<V>, S>,
+    mor_types: HashFinSet<E, S>,
+    src: HashColumn<E, ModalObType<V>, S>,
+    tgt: HashColumn<E, ModalObType<V>, S>,
+    compose_map: HashColumn<(E, E), ModalMorType<V, E>>,
+}
+
+/// Modal double theory with names of type `Ustr`.
+pub type UstrModalDblTheory = ModalDblTheory<Ustr, Ustr, BuildHasherDefault<IdentityHasher>>;
+
+impl<V, E, S> ModalDblTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    /// Creates an empty modal double theory.
+    pub fn new() -> Self
+    where
+        S: Default,
+    {
+        Default::default()
+    }
+
+    /// Adds a basic object type to the theory.
+    pub fn add_ob_type(&mut self, v: V) -> bool {
+        self.ob_types.insert(v)
+    }
+
+    /// Adds a basic object operation to the theory.
+    pub fn add_ob_op(&mut self, e: E, dom: ModalObType<V>, cod: ModalObType<V>) -> bool {
+        self.ob_op_dom.set(e.clone(), dom);
+        self.ob_op_cod.set(e.clone(), cod);
+        self.ob_ops.insert(e)
+    }
+
+    /// Adds a basic morphism type to the theory.
+    pub fn add_mor_type(&mut self, e: E, src: ModalObType<V>, tgt: ModalObType<V>) -> bool {
+        self.src.set(e.clone(), src);
+        self.tgt.set(e.clone(), tgt);
+        self.mor_types.insert(e)
+    }
+
+    /// Sets the composite of two basic morphism types.
+    pub fn set_composite(&mut self, d: E, e: E, m: ModalMorType<V, E>) {
+        self.compose_map.set((d, e), m);
+    }
+
+    /// Constructs a basic object operation with no modalities applied.
+    pub fn basic_ob_op(&self, e: E) -> ModalObOp<V, E> {
+        Path::single(ModeApp::new(e))
+    }
+
+    /// Composes a pair of morphism types, if they have a composite.
+    fn composite2_types(
+        &self,
+        m: ModalMorType<V, E>,
+        n: ModalMorType<V, E>,
+    ) -> Option<ModalMorType<V, E>> {
+        if !(self.has_proarrow(&m) && self.has_proarrow(&n) && self.tgt(&m) == self.src(&n)) {
+            return None;
+        }
+        match (m, n) {
+            (ModalMorType::Hom(_), n) => Some(n),
+            (m, ModalMorType::Hom(_)) => Some(m),
+            (ModalMorType::Basic(d), ModalMorType::Basic(e)) => {
+                if d.modalities != e.modalities {
+                    return None;
+                }
+                let composite = self.compose_map.apply(&(d.arg, e.arg))?;
+                Some(match composite {
+                    ModalMorType::Basic(m) => ModalMorType::Basic(m.apply_all(d.modalities)),
+                    ModalMorType::Hom(x) => ModalMorType::Hom(x.apply_all(d.modalities)),
+                })
+            }
+        }
+    }
+
+    /// Iterates over failures to be a well-defined modal double theory.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidModalDblTheory<E>> + '_ {
+        type Invalid<E> = InvalidModalDblTheory<E>;
+        let ob_op_errors = self.ob_ops.iter().flat_map(move |e| {
+            let mut errs = Vec::new();
+            if !self.ob_op_dom.get(&e).is_some_and(|x| self.has_ob(x)) {
+                errs.push(Invalid::ObOpDom(e.clone()));
+            }
+            if !self.ob_op_cod.get(&e).is_some_and(|x| self.has_ob(x)) {
+                errs.push(Invalid::ObOpCod(e));
+            }
+            errs
+        });
+        let mor_type_errors = self.mor_types.iter().flat_map(move |e| {
+            let mut errs = Vec::new();
+            if !self.src.get(&e).is_some_and(|x| self.has_ob(x)) {
+                errs.push(Invalid::Src(e.clone()));
+            }
+            if !self.tgt.get(&e).is_some_and(|x| self.has_ob(x)) {
+                errs.push(Invalid::Tgt(e));
+            }
+            errs
+        });
+        let missing_errors = self.mor_types.iter().flat_map(move |d| {
+            self.mor_types.iter().filter_map(move |e| {
+                let composable = self.tgt.get(&d).is_some_and(|x| Some(x) == self.src.get(&e));
+                let pair = (d.clone(), e);
+                (composable && !self.compose_map.is_set(&pair))
+                    .then(|| Invalid::Composite(pair.0, pair.1))
             })
-            .collect();
-        TabMorOp { dom, projections }
+        });
+        let compose_errors = self.compose_map.iter().filter_map(move |((d, e), m)| {
+            let (src, tgt) = (self.src.get(&d), self.tgt.get(&e));
+            let valid = src.is_some()
+                && tgt.is_some()
+                && self.tgt.get(&d).is_some_and(|x| Some(x) == self.src.get(&e))
+                && self.has_proarrow(m)
+                && src == Some(&self.src(m))
+                && tgt == Some(&self.tgt(m));
+            (!valid).then_some(Invalid::CompositeType(d, e))
+        });
+        ob_op_errors.chain(mor_type_errors).chain(missing_errors).chain(compose_errors)
+    }
+}
+
+impl<V, E, S> Validate for ModalDblTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type ValidationError = InvalidModalDblTheory<E>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+/// Graph of basic object operations in a modal double theory.
+#[derive(RefCast)]
+#[repr(transparent)]
+struct ModalObOpGraph<V, E, S>(ModalDblTheory<V, E, S>);
+
+impl<V, E, S> Graph for ModalObOpGraph<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type V = ModalObType<V>;
+    type E = ModeApp<E>;
+
+    fn has_vertex(&self, x: &Self::V) -> bool {
+        self.0.ob_types.contains(&x.arg)
+    }
+    fn has_edge(&self, f: &Self::E) -> bool {
+        self.0.ob_ops.contains(&f.arg)
+            && self.0.ob_op_dom.is_set(&f.arg)
+            && self.0.ob_op_cod.is_set(&f.arg)
+    }
+
+    fn src(&self, f: &Self::E) -> Self::V {
+        let x = self
+            .0
+            .ob_op_dom
+            .apply(&f.arg)
+            .expect("Domain of object operation should be defined");
+        x.apply_all(f.modalities.iter().copied())
+    }
+    fn tgt(&self, f: &Self::E) -> Self::V {
+        let x = self
+            .0
+            .ob_op_cod
+            .apply(&f.arg)
+            .expect("Codomain of object operation should be defined");
+        x.apply_all(f.modalities.iter().copied())
+    }
+}
+
+impl<V, E, S> VDblCategory for ModalDblTheory<V, E, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    type Ob = ModalObType<V>;
+    type Arr = ModalObOp<V, E>;
+    type Pro = ModalMorType<V, E>;
+    type Cell = ModalMorOp<V, E>;
+
+    fn has_ob(&self, x: &Self::Ob) -> bool {
+        ModalObOpGraph::ref_cast(self).has_vertex(x)
+    }
+    fn has_arrow(&self, path: &Self::Arr) -> bool {
+        path.contained_in(ModalObOpGraph::ref_cast(self))
+    }
+    fn has_proarrow(&self, m: &Self::Pro) -> bool {
+        match m {
+            ModalMorType::Basic(e) => {
+                self.mor_types.contains(&e.arg)
+                    && self.src.is_set(&e.arg)
+                    && self.tgt.is_set(&e.arg)
+            }
+            ModalMorType::Hom(x) => self.has_ob(x),
+        }
+    }
+    fn has_cell(&self, op: &Self::Cell) -> bool {
+        let graph = ProedgeGraph::ref_cast(UnderlyingDblGraph::ref_cast(self));
+        if !(op.dom.contained_in(graph) && self.has_arrow(&op.src) && self.has_arrow(&op.tgt)) {
+            return false;
+        }
+        if op.src.is_empty() && op.tgt.is_empty() {
+            op.dom.src(graph) == self.dom(&op.src)
+                && op.dom.tgt(graph) == self.dom(&op.tgt)
+                && self.has_composite(&op.dom)
+        } else {
+            op.src == op.tgt
+                && self.composite(op.dom.clone()) == Some(ModalMorType::Hom(self.dom(&op.src)))
+        }
+    }
+
+    fn dom(&self, path: &Self::Arr) -> Self::Ob {
+        path.src(ModalObOpGraph::ref_cast(self))
+    }
+    fn cod(&self, path: &Self::Arr) -> Self::Ob {
+        path.tgt(ModalObOpGraph::ref_cast(self))
+    }
+    fn src(&self, m: &Self::Pro) -> Self::Ob {
+        match m {
+            ModalMorType::Basic(e) => {
+                let x = self.src.apply(&e.arg).expect("Source of morphism type should be defined");
+                x.apply_all(e.modalities.iter().copied())
+            }
+            ModalMorType::Hom(x) => x.clone(),
+        }
+    }
+    fn tgt(&self, m: &Self::Pro) -> Self::Ob {
+        match m {
+            ModalMorType::Basic(e) => {
+                let x = self.tgt.apply(&e.arg).expect("Target of morphism type should be defined");
+                x.apply_all(e.modalities.iter().copied())
+            }
+            ModalMorType::Hom(x) => x.clone(),
+        }
+    }
+
+    fn cell_dom(&self, op: &Self::Cell) -> Path<Self::Ob, Self::Pro> {
+        op.dom.clone()
+    }
+    fn cell_cod(&self, op: &Self::Cell) -> Self::Pro {
+        if op.src.is_empty() && op.tgt.is_empty() {
+            self.composite(op.dom.clone()).expect("Path should have a composite")
+        } else {
+            ModalMorType::Hom(self.cod(&op.src))
+        }
+    }
+    fn cell_src(&self, op: &Self::Cell) -> Self::Arr {
+        op.src.clone()
+    }
+    fn cell_tgt(&self, op: &Self::Cell) -> Self::Arr {
+        op.tgt.clone()
+    }
+
+    fn compose(&self, path: Path<Self::Ob, Self::Arr>) -> Self::Arr {
+        path.flatten()
+    }
+
+    fn composite2(&self, m: Self::Pro, n: Self::Pro) -> Option<Self::Pro> {
+        self.composite2_types(m, n)
+    }
+    fn unit(&self, x: Self::Ob) -> Option<Self::Pro> {
+        Some(ModalMorType::Hom(x))
+    }
+    fn composite(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Pro> {
+        match path {
+            Path::Id(x) => self.has_ob(&x).then_some(ModalMorType::Hom(x)),
+            Path::Seq(ms) => {
+                let (head, tail) = ms.split_first();
+                let head = self.has_proarrow(head).then(|| head.clone())?;
+                tail.iter().try_fold(head, |m, n| self.composite2_types(m, n.clone()))
+            }
+        }
+    }
+    fn composite_ext(&self, path: Path<Self::Ob, Self::Pro>) -> Option<Self::Cell> {
+        let m = self.composite(path.clone())?;
+        Some(ModalMorOp {
+            dom: path,
+            src: Path::Id(self.src(&m)),
+            tgt: Path::Id(self.tgt(&m)),
+        })
+    }
+
+    fn through_composite(
+        &self,
+        op: Self::Cell,
+        range: std::ops::Range<usize>,
+    ) -> Option<Self::Cell> {
+        let graph = ProedgeGraph::ref_cast(UnderlyingDblGraph::ref_cast(self));
+        let ModalMorOp { dom, src, tgt } = op;
+        let dom = dom.splice_in(graph, range, |sub| self.composite(sub))?;
+        Some(ModalMorOp { dom, src, tgt })
+    }
+
+    fn compose_cells(&self, tree: DblTree<Self::Arr, Self::Pro, Self::Cell>) -> Self::Cell {
+        let graph = UnderlyingDblGraph::ref_cast(self);
+        ModalMorOp {
+            dom: tree.dom(graph),
+            src: self.compose(tree.src(graph)),
+            tgt: self.compose(tree.tgt(graph)),
+        }
     }
 }
 
+/// A failure of a modal double theory to be well defined.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidModalDblTheory<E> {
+    /// Object operation with missing or invalid domain.
+    #[error("Domain of object operation `{0}` is not an object type in the theory")]
+    ObOpDom(E),
+
+    /// Object operation with missing or invalid codomain.
+    #[error("Codomain of object operation `{0}` is not an object type in the theory")]
+    ObOpCod(E),
+
+    /// Morphism type with missing or invalid source.
+    #[error("Source of morphism type `{0}` is not an object type in the theory")]
+    Src(E),
+
+    /// Morphism type with missing or invalid target.
+    #[error("Target of morphism type `{0}` is not an object type in the theory")]
+    Tgt(E),
+
+    /// Composite of a composable pair of morphism types is not defined.
+    #[error("Composite of morphism types `{0}` and `{1}` is not defined")]
+    Composite(E, E),
+
+    /// Composite of a pair of morphism types is not a compatible morphism type.
+    #[error("Composite of morphism types `{0}` and `{1}` is not a compatible morphism type")]
+    CompositeType(E, E),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -693,7 +1982,10 @@ mod tests {
         assert!(th.has_ob_type(&'*'));
         assert!(th.has_mor_type(&Mor::Generator('n')));
         let path = Path::pair(Mor::Generator('n'), Mor::Generator('n'));
-        assert_eq!(th.compose_types(path), Some(Mor::Id('*')));
+        assert_eq!(th.compose_types(path.clone()), Some(Mor::Id('*')));
+
+        assert_eq!(th.hom_op('*'), Path::single(Mor::Id('*')));
+        assert_eq!(th.through_composite(path, 0..2), Some(Path::single(Mor::Id('*'))));
     }
 
     #[test]
@@ -728,5 +2020,198 @@ mod tests {
         );
         assert!(th.has_mor_op(&cell_alt));
         assert_eq!(cell, cell_alt);
+
+        let hom_x = th.hom_type(x.clone());
+        let src_op = Path::single(TabObProj::Src(hom_x.clone()));
+        let hom_op = th.hom_op(src_op.clone());
+        assert!(th.has_mor_op(&hom_op));
+        assert_eq!(hom_op, th.unary_projection(TabMorProj::Src(hom_x.clone())));
+        assert_eq!(th.src_op(&hom_op), src_op);
+
+        let pair = th.composite2_ext(th.hom_type(tab.clone()), th.hom_type(tab.clone())).unwrap();
+        let factored = th.through_composite(cell, 0..2).unwrap();
+        assert_eq!(th.mor_op_dom(&factored), Path::single(th.hom_type(tab.clone())));
+        assert_eq!(th.compose_mor_ops(DblTree::two_level([pair], factored.clone())), cell_alt);
+        assert_eq!(th.through_composite(factored, 0..2), None);
+    }
+
+    #[test]
+    fn validate_discrete_tabulator_theory() {
+        let mut th = DiscreteTabTheory::<char, char>::new();
+        th.add_ob_type('x');
+        let x = TabObType::Basic('x');
+        th.add_mor_type('f', x.clone(), x.clone());
+        assert_eq!(
+            th.validate().map_err(|errs| errs.into()),
+            Err(vec![InvalidDiscreteTabTheory::Composite('f', 'f')])
+        );
+        th.set_composite('f', 'f', th.hom_type(x.clone()));
+        assert!(th.validate().is_ok());
+
+        let tab = th.tabulator(TabMorType::Basic('g'));
+        th.add_mor_type('h', tab.clone(), TabObType::Basic('y'));
+        th.make_mor_type('k');
+        th.set_composite('f', 'h', TabMorType::Basic('f'));
+        let errs: Vec<_> = th.validate().unwrap_err().into();
+        assert_eq!(errs.len(), 5);
+        assert!(errs.contains(&InvalidDiscreteTabTheory::Src('h')));
+        assert!(errs.contains(&InvalidDiscreteTabTheory::Tgt('h')));
+        assert!(errs.contains(&InvalidDiscreteTabTheory::MissingSrc('k')));
+        assert!(errs.contains(&InvalidDiscreteTabTheory::MissingTgt('k')));
+        assert!(errs.contains(&InvalidDiscreteTabTheory::NotComposable('f', 'h')));
+    }
+
+    #[test]
+    fn recoverable_tabulator_composition() {
+        let mut th = DiscreteTabTheory::<char, char>::new();
+        th.add_ob_type('x');
+        th.add_ob_type('y');
+        let (x, y) = (TabObType::Basic('x'), TabObType::Basic('y'));
+        th.add_mor_type('f', x.clone(), y.clone());
+        let f = TabMorType::Basic('f');
+        assert_eq!(th.try_composite2(th.hom_type(x.clone()), f.clone()), Ok(f.clone()));
+        assert_eq!(
+            th.try_composite2(f.clone(), f.clone()),
+            Err(InvalidTabComposition::NotComposable(f.clone(), f.clone()))
+        );
+        assert_eq!(th.composite2(f.clone(), th.hom_type(x.clone())), None);
+
+        th.add_mor_type('g', y.clone(), x.clone());
+        let g = TabMorType::Basic('g');
+        assert_eq!(
+            th.try_composite(Path::pair(f.clone(), g.clone())),
+            Err(InvalidTabComposition::Undefined('f', 'g'))
+        );
+        th.set_composite('f', 'g', th.hom_type(x.clone()));
+        assert_eq!(th.composite(Path::pair(f, g)), Some(th.hom_type(x)));
+    }
+
+    #[test]
+    fn simple_double_theory() {
+        let mut th = SimpleDblTheory::<char, char>::new();
+        th.add_ob_type('x');
+        th.add_ob_op('F', 'x', 'x');
+        th.add_ob_op_equation('i', PathEq::new(Path::pair('F', 'F'), Path::single('F')));
+        let hom = th.hom_type('x');
+        th.add_mor_op('η', hom.clone(), hom.clone(), Path::Id('x'), Path::single('F'));
+        assert!(th.validate().is_ok());
+
+        let f = Path::single('F');
+        assert_eq!(th.compose_ob_ops(Path::pair(f.clone(), f.clone())), f);
+        assert_eq!(th.hom_type('x'), FinMor::Id('x'));
+
+        let hom_op = th.hom_op(f.clone());
+        assert!(th.has_mor_op(&hom_op));
+        assert_eq!(hom_op.term(), &SimpleMorOpTerm::Hom(f.clone()));
+        assert_eq!(th.mor_op_dom(&hom_op), Path::single(hom.clone()));
+        assert_eq!((th.src_op(&hom_op), th.tgt_op(&hom_op)), (f.clone(), f.clone()));
+
+        let η = th.basic_mor_op('η');
+        assert!(th.has_mor_op(&η));
+        assert_eq!(
+            th.compose_mor_ops(DblTree::two_level([η.clone()], th.id_mor_op(hom.clone()))),
+            η
+        );
+        let ηη = th.compose_mor_ops(DblTree::two_level([η.clone()], η.clone()));
+        assert!(th.has_mor_op(&ηη));
+        assert_eq!((th.src_op(&ηη), th.tgt_op(&ηη)), (Path::Id('x'), f.clone()));
+        let ηf = th.compose_mor_ops(DblTree::two_level([η.clone()], hom_op.clone()));
+        assert_eq!(th.tgt_op(&ηf), f);
+        assert_eq!(
+            ηf.term(),
+            &SimpleMorOpTerm::Vertical(vec![
+                SimpleMorOpTerm::Generator('η'),
+                SimpleMorOpTerm::Hom(f.clone())
+            ])
+        );
+
+        let pair = th.composite2_ext(hom.clone(), hom.clone()).unwrap();
+        let pasted = th.compose_mor_ops(DblTree::two_level([η.clone(), hom_op], η.clone()));
+        assert!(th.has_mor_op(&pasted));
+        assert_eq!(th.mor_op_dom(&pasted).len(), 2);
+        let factored = th.through_composite(pasted.clone(), 0..2).unwrap();
+        assert_eq!(th.mor_op_dom(&factored), Path::single(hom.clone()));
+        assert_eq!(th.compose_mor_ops(DblTree::two_level([pair], factored.clone())), pasted);
+        assert_eq!(th.unary_mor_op(pasted), factored);
+    }
+
+    #[test]
+    fn validate_simple_double_theory() {
+        let mut th = SimpleDblTheory::<char, char>::new();
+        th.add_ob_type('x');
+        th.add_ob_type('y');
+        th.add_ob_op('F', 'x', 'y');
+        th.add_mor_type('m', 'x', 'y');
+        th.add_mor_op(
+            'α',
+            th.hom_type('x'),
+            FinMor::Generator('m'),
+            Path::Id('x'),
+            Path::single('F'),
+        );
+        assert!(th.validate().is_ok());
+
+        th.add_ob_op('G', 'x', 'z');
+        th.add_mor_op('β', th.hom_type('x'), th.hom_type('x'), Path::single('F'), Path::Id('x'));
+        th.add_mor_op('γ', FinMor::Generator('n'), th.hom_type('x'), Path::Id('x'), Path::Id('x'));
+        let errs: Vec<_> = th.validate().unwrap_err().into();
+        assert_eq!(errs.len(), 3);
+        assert_eq!(errs[0], InvalidSimpleDblTheory::ObOpCod('G'));
+        assert!(errs.contains(&InvalidSimpleDblTheory::MorOpSrc('β')));
+        assert!(errs.contains(&InvalidSimpleDblTheory::MorOpDom('γ')));
+    }
+
+    #[test]
+    fn modal_double_theory() {
+        let mut th = ModalDblTheory::<char, char>::new();
+        th.add_ob_type('x');
+        let x = ModeApp::new('x');
+        let xs = x.clone().apply(Modality::SymList);
+        th.add_ob_op('⊗', xs.clone(), x.clone());
+        th.add_mor_type('m', x.clone(), x.clone());
+        th.set_composite('m', 'm', ModalMorType::Basic('m'.into()));
+        assert!(th.validate().is_ok());
+        assert!(th.has_ob_type(&xs));
+
+        let tensor = th.basic_ob_op('⊗');
+        assert!(th.has_ob_op(&tensor));
+        assert_eq!((th.ob_op_dom(&tensor), th.ob_op_cod(&tensor)), (xs.clone(), x.clone()));
+
+        let list_m = ModalMorType::Basic(ModeApp::new('m').apply(Modality::SymList));
+        assert!(th.has_mor_type(&list_m));
+        assert_eq!((th.src_type(&list_m), th.tgt_type(&list_m)), (xs.clone(), xs.clone()));
+        assert_eq!(
+            th.compose_types(Path::pair(list_m.clone(), list_m.clone())),
+            Some(list_m.clone())
+        );
+        let m = ModalMorType::Basic('m'.into());
+        assert_eq!(th.compose_types(Path::pair(m, list_m.clone())), None);
+
+        let hom_op = th.hom_op(tensor.clone());
+        assert!(th.has_mor_op(&hom_op));
+        assert_eq!(th.mor_op_dom(&hom_op), Path::single(th.hom_type(xs.clone())));
+        assert_eq!(th.mor_op_cod(&hom_op), th.hom_type(x.clone()));
+        assert_eq!(hom_op.hom_op(), Some(&tensor));
+        let id_op = th.id_mor_op(list_m.clone());
+        assert!(th.has_mor_op(&id_op));
+        assert_eq!(id_op.hom_op(), None);
+        assert_eq!(th.mor_op_cod(&id_op), list_m);
+    }
+
+    #[test]
+    fn validate_modal_double_theory() {
+        let mut th = ModalDblTheory::<char, char>::new();
+        th.add_ob_type('x');
+        let x = ModeApp::new('x');
+        th.add_mor_type('m', x.clone(), x.clone().apply(Modality::List));
+        th.add_ob_op('F', x.clone(), ModeApp::new('y'));
+        th.set_composite('m', 'm', ModalMorType::Hom(x.clone()));
+        assert_eq!(
+            th.validate().map_err(|errs| errs.into()),
+            Err(vec![
+                InvalidModalDblTheory::ObOpCod('F'),
+                InvalidModalDblTheory::CompositeType('m', 'm')
+            ])
+        );
     }
 }
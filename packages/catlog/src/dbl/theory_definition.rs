@@ -0,0 +1,487 @@
+/*! Declarative definitions of double theories.
+
+The theories in the [standard library](crate::stdlib::theories) are constructed
+in Rust code. Theories can also be defined declaratively, as plain data listing
+their object types, morphism types, and, for discrete double theories, the
+composition law. With the `serde` feature enabled, such definitions can be read
+from JSON, TOML, or any other format supported by `serde`, so that new theories
+do not require new code.
+
+Each object and morphism type can carry a human-readable label, which is not
+part of the theory itself but is retained in the definition for display. A
+definition is checked before it is built into a theory, and all the problems
+found are reported together.
+ */
+
+use std::collections::HashSet;
+use std::hash::{BuildHasher, Hash};
+
+use nonempty::NonEmpty;
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-wasm")]
+use tsify_next::Tsify;
+
+use super::theory::*;
+use crate::one::Category;
+use crate::one::fin_category::{FinCategory, FinMor, InvalidFinCategory};
+use crate::validate::{self, Validate};
+
+/// Definition of an object type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct ObTypeDefinition<Id> {
+    /// Identifier of the object type.
+    pub id: Id,
+
+    /// Human-readable label for the object type.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
+}
+
+/// Definition of a morphism type, with domain and codomain of type `Ob`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct MorTypeDefinition<Id, Ob> {
+    /// Identifier of the morphism type.
+    pub id: Id,
+
+    /// Domain, or source, of the morphism type.
+    pub dom: Ob,
+
+    /// Codomain, or target, of the morphism type.
+    pub cod: Ob,
+
+    /// Human-readable label for the morphism type.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub label: Option<String>,
+}
+
+/// Entry in the composition table of a discrete double theory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct CompositeDefinition<Id> {
+    /// First morphism type to compose.
+    pub first: Id,
+
+    /// Second morphism type to compose.
+    pub second: Id,
+
+    /// Composite of the two morphism types, possibly a hom type.
+    pub composite: FinMor<Id, Id>,
+}
+
+/** Definition of a discrete double theory.
+
+The composition table must specify the composite of every composable pair of
+morphism types. Composites with hom types need not, and cannot, be specified.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct DiscreteDblTheoryDefinition<Id> {
+    /// Object types in the theory.
+    #[cfg_attr(feature = "serde", serde(rename = "obTypes"))]
+    pub ob_types: Vec<ObTypeDefinition<Id>>,
+
+    /// Basic morphism types in the theory.
+    #[cfg_attr(feature = "serde", serde(rename = "morTypes"))]
+    pub mor_types: Vec<MorTypeDefinition<Id, Id>>,
+
+    /// Composition table for the basic morphism types.
+    pub composites: Vec<CompositeDefinition<Id>>,
+}
+
+/** Definition of a discrete tabulator theory.
+
+The domain and codomain of a morphism type can be basic object types or
+tabulators of morphism types, which may be defined in any order.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub struct DiscreteTabTheoryDefinition<Id> {
+    /// Basic object types in the theory.
+    #[cfg_attr(feature = "serde", serde(rename = "obTypes"))]
+    pub ob_types: Vec<ObTypeDefinition<Id>>,
+
+    /// Basic morphism types in the theory.
+    #[cfg_attr(feature = "serde", serde(rename = "morTypes"))]
+    pub mor_types: Vec<MorTypeDefinition<Id, TabObType<Id, Id>>>,
+}
+
+/// Definition of a double theory of any supported kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum DblTheoryDefinition<Id> {
+    /// Definition of a discrete double theory.
+    Discrete(DiscreteDblTheoryDefinition<Id>),
+
+    /// Definition of a discrete tabulator theory.
+    DiscreteTab(DiscreteTabTheoryDefinition<Id>),
+}
+
+impl<Id: Eq> DblTheoryDefinition<Id> {
+    /// Gets the label of an object type, if it has one.
+    pub fn ob_type_label(&self, id: &Id) -> Option<&str> {
+        match self {
+            DblTheoryDefinition::Discrete(def) => find_label(&def.ob_types, id),
+            DblTheoryDefinition::DiscreteTab(def) => find_label(&def.ob_types, id),
+        }
+    }
+
+    /// Gets the label of a basic morphism type, if it has one.
+    pub fn mor_type_label(&self, id: &Id) -> Option<&str> {
+        match self {
+            DblTheoryDefinition::Discrete(def) => find_mor_label(&def.mor_types, id),
+            DblTheoryDefinition::DiscreteTab(def) => find_mor_label(&def.mor_types, id),
+        }
+    }
+}
+
+fn find_label<'a, Id: Eq>(defs: &'a [ObTypeDefinition<Id>], id: &Id) -> Option<&'a str> {
+    defs.iter().find(|def| def.id == *id)?.label.as_deref()
+}
+
+fn find_mor_label<'a, Id: Eq, Ob>(
+    defs: &'a [MorTypeDefinition<Id, Ob>],
+    id: &Id,
+) -> Option<&'a str> {
+    defs.iter().find(|def| def.id == *id)?.label.as_deref()
+}
+
+/// Nonempty list of errors in a double theory definition.
+pub type DefinitionErrors<Id> = NonEmpty<InvalidDblTheoryDefinition<Id>>;
+
+impl<Id> DiscreteDblTheoryDefinition<Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    /// Builds the discrete double theory, if the definition is valid.
+    pub fn build<S>(
+        &self,
+    ) -> Result<DiscreteDblTheory<FinCategory<Id, Id, S>>, DefinitionErrors<Id>>
+    where
+        S: BuildHasher + Default,
+    {
+        let mut errors = duplicate_ids(&self.ob_types, &self.mor_types);
+        let mut cat: FinCategory<Id, Id, S> = Default::default();
+        cat.add_ob_generators(self.ob_types.iter().map(|def| def.id.clone()));
+        for def in &self.mor_types {
+            if !cat.has_ob(&def.dom) {
+                errors.push(InvalidDblTheoryDefinition::Dom(def.id.clone()));
+            }
+            if !cat.has_ob(&def.cod) {
+                errors.push(InvalidDblTheoryDefinition::Cod(def.id.clone()));
+            }
+            cat.add_mor_generator(def.id.clone(), def.dom.clone(), def.cod.clone());
+        }
+        if !errors.is_empty() {
+            return Err(NonEmpty::from_vec(errors).unwrap());
+        }
+
+        let mut pairs = HashSet::new();
+        for def in &self.composites {
+            let pair = (def.first.clone(), def.second.clone());
+            let gens = [FinMor::Generator(pair.0.clone()), FinMor::Generator(pair.1.clone())];
+            if !(gens.iter().all(|f| cat.has_mor(f)) && cat.has_mor(&def.composite)) {
+                errors.push(InvalidDblTheoryDefinition::Composite(pair.0, pair.1));
+            } else if !pairs.insert(pair.clone()) {
+                errors.push(InvalidDblTheoryDefinition::DuplicateComposite(pair.0, pair.1));
+            } else {
+                cat.set_composite(pair.0, pair.1, def.composite.clone());
+            }
+        }
+        if errors.is_empty() {
+            errors.extend(cat.iter_invalid().map(InvalidDblTheoryDefinition::Category));
+        }
+        validate::wrap_errors(errors.into_iter()).map(|_| DiscreteDblTheory::from(cat))
+    }
+}
+
+impl<Id> DiscreteTabTheoryDefinition<Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    /// Builds the discrete tabulator theory, if the definition is valid.
+    pub fn build<S>(&self) -> Result<DiscreteTabTheory<Id, Id, S>, DefinitionErrors<Id>>
+    where
+        S: BuildHasher + Default,
+    {
+        let mut errors = duplicate_ids(&self.ob_types, &self.mor_types);
+        let ob_types: HashSet<_> = self.ob_types.iter().map(|def| &def.id).collect();
+        let mor_types: HashSet<_> = self.mor_types.iter().map(|def| &def.id).collect();
+        let is_defined = |x: &TabObType<Id, Id>| tab_ob_type_defined(x, &ob_types, &mor_types);
+
+        let mut theory = DiscreteTabTheory::new();
+        for def in &self.ob_types {
+            theory.add_ob_type(def.id.clone());
+        }
+        for def in &self.mor_types {
+            if !is_defined(&def.dom) {
+                errors.push(InvalidDblTheoryDefinition::Dom(def.id.clone()));
+            }
+            if !is_defined(&def.cod) {
+                errors.push(InvalidDblTheoryDefinition::Cod(def.id.clone()));
+            }
+            theory.add_mor_type(def.id.clone(), def.dom.clone(), def.cod.clone());
+        }
+        validate::wrap_errors(errors.into_iter()).map(|_| theory)
+    }
+}
+
+/// Is the object type in a tabulator theory built from defined types?
+fn tab_ob_type_defined<Id: Eq + Hash>(
+    x: &TabObType<Id, Id>,
+    ob_types: &HashSet<&Id>,
+    mor_types: &HashSet<&Id>,
+) -> bool {
+    match x {
+        TabObType::Basic(v) => ob_types.contains(v),
+        TabObType::Tabulator(m) => match m.as_ref() {
+            TabMorType::Basic(e) => mor_types.contains(e),
+            TabMorType::Hom(x) => tab_ob_type_defined(x, ob_types, mor_types),
+        },
+    }
+}
+
+/// Finds the identifiers of object and morphism types defined more than once.
+fn duplicate_ids<Id, Ob>(
+    ob_types: &[ObTypeDefinition<Id>],
+    mor_types: &[MorTypeDefinition<Id, Ob>],
+) -> Vec<InvalidDblTheoryDefinition<Id>>
+where
+    Id: Eq + Clone + Hash,
+{
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+    for def in ob_types {
+        if !seen.insert(&def.id) {
+            errors.push(InvalidDblTheoryDefinition::DuplicateObType(def.id.clone()));
+        }
+    }
+    let mut seen = HashSet::new();
+    for def in mor_types {
+        if !seen.insert(&def.id) {
+            errors.push(InvalidDblTheoryDefinition::DuplicateMorType(def.id.clone()));
+        }
+    }
+    errors
+}
+
+impl<Id> Validate for DblTheoryDefinition<Id>
+where
+    Id: Eq + Clone + Hash,
+{
+    type ValidationError = InvalidDblTheoryDefinition<Id>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        match self {
+            DblTheoryDefinition::Discrete(def) => def.build::<std::hash::RandomState>().map(|_| ()),
+            DblTheoryDefinition::DiscreteTab(def) => {
+                def.build::<std::hash::RandomState>().map(|_| ())
+            }
+        }
+    }
+}
+
+/// A failure of a definition to define a double theory.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidDblTheoryDefinition<Id> {
+    /// Object type that is defined more than once.
+    #[error("Object type `{0}` is defined more than once")]
+    DuplicateObType(Id),
+
+    /// Morphism type that is defined more than once.
+    #[error("Morphism type `{0}` is defined more than once")]
+    DuplicateMorType(Id),
+
+    /// Morphism type whose domain is not an object type in the theory.
+    #[error("Domain of morphism type `{0}` is not an object type in the theory")]
+    Dom(Id),
+
+    /// Morphism type whose codomain is not an object type in the theory.
+    #[error("Codomain of morphism type `{0}` is not an object type in the theory")]
+    Cod(Id),
+
+    /// Composite that does not involve morphism types in the theory.
+    #[error("Composite of `{0}` and `{1}` does not involve morphism types in the theory")]
+    Composite(Id, Id),
+
+    /// Composite that is defined more than once.
+    #[error("Composite of `{0}` and `{1}` is defined more than once")]
+    DuplicateComposite(Id, Id),
+
+    /// Failure of the composition law to define a category.
+    #[error("Composition of morphism types is invalid: {0}")]
+    Category(InvalidFinCategory<Id>),
+}
+
+#[cfg(test)]
+mod tests {
+    use ustr::{Ustr, ustr};
+
+    use super::*;
+    use crate::one::fin_category::UstrFinCategory;
+    use crate::stdlib::theories::*;
+
+    fn ob_type(id: &str, label: &str) -> ObTypeDefinition<Ustr> {
+        ObTypeDefinition {
+            id: ustr(id),
+            label: Some(label.into()),
+        }
+    }
+
+    fn signed_category() -> DiscreteDblTheoryDefinition<Ustr> {
+        let x = ustr("Object");
+        DiscreteDblTheoryDefinition {
+            ob_types: vec![ob_type("Object", "Variable")],
+            mor_types: vec![MorTypeDefinition {
+                id: ustr("Negative"),
+                dom: x,
+                cod: x,
+                label: Some("Negative link".into()),
+            }],
+            composites: vec![CompositeDefinition {
+                first: ustr("Negative"),
+                second: ustr("Negative"),
+                composite: FinMor::Id(x),
+            }],
+        }
+    }
+
+    #[test]
+    fn discrete_dbl_theory_definition() {
+        let def = signed_category();
+        let th: DiscreteDblTheory<UstrFinCategory> = def.build().unwrap();
+        assert_eq!(th.category(), th_signed_category().category());
+
+        let def = DblTheoryDefinition::Discrete(def);
+        assert!(def.validate().is_ok());
+        assert_eq!(def.ob_type_label(&ustr("Object")), Some("Variable"));
+        assert_eq!(def.mor_type_label(&ustr("Negative")), Some("Negative link"));
+        assert_eq!(def.mor_type_label(&ustr("Positive")), None);
+
+        let mut def = signed_category();
+        def.composites.clear();
+        let errors: Vec<_> = def.build::<std::hash::RandomState>().unwrap_err().into();
+        let (n, x) = (ustr("Negative"), ustr("Object"));
+        assert_eq!(
+            errors,
+            vec![InvalidDblTheoryDefinition::Category(InvalidFinCategory::Composite(n, n))]
+        );
+
+        let mut def = signed_category();
+        def.ob_types.push(ob_type("Object", "Duplicate"));
+        def.mor_types[0].cod = ustr("Other");
+        let errors: Vec<_> = def.build::<std::hash::RandomState>().unwrap_err().into();
+        assert_eq!(
+            errors,
+            vec![
+                InvalidDblTheoryDefinition::DuplicateObType(x),
+                InvalidDblTheoryDefinition::Cod(n)
+            ]
+        );
+    }
+
+    #[test]
+    fn discrete_tab_theory_definition() {
+        let x = TabObType::Basic(ustr("Object"));
+        let link = MorTypeDefinition {
+            id: ustr("Link"),
+            dom: x.clone(),
+            cod: TabObType::Tabulator(Box::new(TabMorType::Hom(Box::new(x.clone())))),
+            label: Some("Link".into()),
+        };
+        let def = DiscreteTabTheoryDefinition {
+            ob_types: vec![ob_type("Object", "Stock")],
+            mor_types: vec![link.clone()],
+        };
+        let th: UstrDiscreteTabTheory = def.build().unwrap();
+        let expected = th_category_links();
+        let m = TabMorType::Basic(ustr("Link"));
+        assert_eq!(th.src_type(&m), expected.src_type(&m));
+        assert_eq!(th.tgt_type(&m), expected.tgt_type(&m));
+
+        let bad_link = MorTypeDefinition {
+            cod: TabObType::Tabulator(Box::new(TabMorType::Basic(ustr("Flow")))),
+            ..link
+        };
+        let def = DiscreteTabTheoryDefinition {
+            ob_types: vec![ob_type("Object", "Stock")],
+            mor_types: vec![bad_link],
+        };
+        let errors: Vec<_> = def.build::<std::hash::RandomState>().err().unwrap().into();
+        assert_eq!(errors, vec![InvalidDblTheoryDefinition::Cod(ustr("Link"))]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_theory_definitions() {
+        let json = r#"{
+            "kind": "Discrete",
+            "obTypes": [{"id": "Object", "label": "Variable"}],
+            "morTypes": [
+                {"id": "Negative", "dom": "Object", "cod": "Object", "label": "Negative link"}
+            ],
+            "composites": [
+                {"first": "Negative", "second": "Negative",
+                 "composite": {"tag": "Id", "content": "Object"}}
+            ]
+        }"#;
+        let def: DblTheoryDefinition<Ustr> = serde_json::from_str(json).unwrap();
+        assert!(def.validate().is_ok());
+        assert_eq!(def.mor_type_label(&ustr("Negative")), Some("Negative link"));
+        let DblTheoryDefinition::Discrete(def) = def else {
+            panic!("Definition should be of a discrete double theory");
+        };
+        assert_eq!(def, signed_category());
+        let th: DiscreteDblTheory<UstrFinCategory> = def.build().unwrap();
+        assert_eq!(th.category(), th_signed_category().category());
+
+        let json = r#"{
+            "kind": "DiscreteTab",
+            "obTypes": [{"id": "Object"}],
+            "morTypes": [{
+                "id": "Link",
+                "dom": {"tag": "Basic", "content": "Object"},
+                "cod": {"tag": "Tabulator", "content":
+                    {"tag": "Hom", "content": {"tag": "Basic", "content": "Object"}}}
+            }]
+        }"#;
+        let def: DblTheoryDefinition<Ustr> = serde_json::from_str(json).unwrap();
+        assert!(def.validate().is_ok());
+        assert_eq!(def.ob_type_label(&ustr("Object")), None);
+        let DblTheoryDefinition::DiscreteTab(def) = def else {
+            panic!("Definition should be of a discrete tabulator theory");
+        };
+        let th: UstrDiscreteTabTheory = def.build().unwrap();
+        let (expected, m) = (th_category_links(), TabMorType::Basic(ustr("Link")));
+        assert_eq!(th.src_type(&m), expected.src_type(&m));
+        assert_eq!(th.tgt_type(&m), expected.tgt_type(&m));
+
+        // Malformed documents: unknown kind, missing field, and invalid JSON.
+        let parse = serde_json::from_str::<DblTheoryDefinition<Ustr>>;
+        assert!(parse(r#"{"kind": "Modal", "obTypes": [], "morTypes": []}"#).is_err());
+        assert!(parse(r#"{"obTypes": [], "morTypes": [], "composites": []}"#).is_err());
+        assert!(parse(r#"{"kind": "Discrete", "obTypes": [], "morTypes": []}"#).is_err());
+        assert!(parse(r#"{"kind": "DiscreteTab", "obTypes": [{"id": "Object"}"#).is_err());
+    }
+}
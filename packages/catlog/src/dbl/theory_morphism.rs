@@ -0,0 +1,486 @@
+/*! Morphisms between double theories and migration of models along them.
+
+A morphism of [discrete double theories](super::theory::DiscreteDblTheory) is
+simply a functor between their underlying categories of types. Such morphisms
+are how one theory is interpreted in another. Typically they forget structure,
+such as the functor from [signed categories](crate::stdlib::th_signed_category)
+to [categories](crate::stdlib::th_category) that forgets the signs, or include
+one theory into a richer one, such as the inclusion of signed categories into
+[nullable signed categories](crate::stdlib::th_nullable_signed_category).
+
+Models migrate along a theory morphism `F: T → T'` in two directions. The
+*pushforward* of a model of `T` is the model of `T'` with the same generators,
+each retyped by `F`. Viewing a model of a discrete theory as a category sliced
+over the theory, this is just postcomposition with `F`. The *pullback* of a
+model of `T'` is the model of `T` having a copy of each generator for each lift
+of its type along `F`. For the inclusion of signed into nullable signed
+categories, this discards the generators of type zero, whereas for the functor
+forgetting delays, each signed morphism becomes a fast one and a slow one.
+ */
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Arc;
+
+use derivative::Derivative;
+use nonempty::NonEmpty;
+use thiserror::Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-wasm")]
+use tsify_next::Tsify;
+
+use super::{model::*, theory::*};
+use crate::one::functor::{FinFunctor, InvalidFinFunctor};
+use crate::one::{FgCategory, Path, PathEq, PresentedCategory};
+use crate::validate::{self, Validate};
+
+/** A morphism between discrete double theories.
+
+The morphism is given by a [functor](FinFunctor) between the underlying
+categories of the theories, which are kept alongside it so that models can be
+migrated.
+ */
+#[derive(Derivative)]
+#[derivative(Clone(bound = ""))]
+pub struct DblTheoryMorphism<Dom: FgCategory, Cod: FgCategory> {
+    dom: Arc<DiscreteDblTheory<Dom>>,
+    cod: Arc<DiscreteDblTheory<Cod>>,
+    functor: FinFunctor<Dom::Ob, Dom::MorGen, Cod::Ob, Cod::Mor>,
+}
+
+impl<Dom, Cod> DblTheoryMorphism<Dom, Cod>
+where
+    Dom: PresentedCategory,
+    Dom::Ob: Hash,
+    Dom::Mor: Hash,
+    Dom::MorGen: Hash,
+    Cod: FgCategory,
+    Cod::Ob: Hash,
+    Cod::Mor: Hash,
+{
+    /// Constructs a theory morphism from a functor between the theories.
+    pub fn new(
+        dom: Arc<DiscreteDblTheory<Dom>>,
+        cod: Arc<DiscreteDblTheory<Cod>>,
+        functor: FinFunctor<Dom::Ob, Dom::MorGen, Cod::Ob, Cod::Mor>,
+    ) -> Self {
+        Self { dom, cod, functor }
+    }
+
+    /// Gets the domain theory.
+    pub fn dom(&self) -> &Arc<DiscreteDblTheory<Dom>> {
+        &self.dom
+    }
+
+    /// Gets the codomain theory.
+    pub fn cod(&self) -> &Arc<DiscreteDblTheory<Cod>> {
+        &self.cod
+    }
+
+    /// Gets the functor between the underlying categories.
+    pub fn functor(&self) -> &FinFunctor<Dom::Ob, Dom::MorGen, Cod::Ob, Cod::Mor> {
+        &self.functor
+    }
+
+    /// Applies the morphism to an object type.
+    pub fn apply_ob_type(&self, x: &Dom::Ob) -> Option<Cod::Ob> {
+        self.functor.apply_ob(x)
+    }
+
+    /// Applies the morphism to a morphism type.
+    pub fn apply_mor_type(&self, m: &Dom::Mor) -> Option<Cod::Mor> {
+        self.functor.apply_mor(self.dom.category(), self.cod.category(), m)
+    }
+
+    /** Composes this theory morphism with another one.
+
+    The composite is defined wherever both morphisms are.
+     */
+    pub fn compose<Cod2>(
+        &self,
+        other: &DblTheoryMorphism<Cod, Cod2>,
+    ) -> DblTheoryMorphism<Dom, Cod2>
+    where
+        Cod: PresentedCategory,
+        Cod::MorGen: Hash,
+        Cod2: FgCategory,
+        Cod2::Ob: Hash,
+        Cod2::Mor: Hash,
+    {
+        let functor =
+            self.functor.compose(&other.functor, self.cod.category(), other.cod.category());
+        DblTheoryMorphism::new(self.dom.clone(), other.cod.clone(), functor)
+    }
+
+    /** Pushes a model of the domain theory forward to the codomain theory.
+
+    The pushed forward model has the same generators and equations, with
+    types mapped by the theory morphism. Generators whose types are not
+    mapped are reported as errors.
+     */
+    pub fn pushforward<Id>(
+        &self,
+        model: &DiscreteDblModel<Id, Dom>,
+    ) -> Result<DiscreteDblModel<Id, Cod>, NonEmpty<InvalidModelMigration<Id>>>
+    where
+        Id: Eq + Clone + Hash,
+    {
+        let mut pushed = DiscreteDblModel::new(self.cod.clone());
+        let mut errors = Vec::new();
+        for x in model.ob_generators() {
+            match self.apply_ob_type(&model.ob_generator_type(&x)) {
+                Some(typ) => {
+                    pushed.add_ob(x, typ);
+                }
+                None => errors.push(InvalidModelMigration::ObType(x)),
+            }
+        }
+        for f in model.mor_generators() {
+            let Some(typ) = self.apply_mor_type(&model.mor_generator_type(&f)) else {
+                errors.push(InvalidModelMigration::MorType(f));
+                continue;
+            };
+            pushed.make_mor(f.clone(), typ);
+            if let Some(x) = model.get_dom(&f) {
+                pushed.set_dom(f.clone(), x.clone());
+            }
+            if let Some(y) = model.get_cod(&f) {
+                pushed.set_cod(f, y.clone());
+            }
+        }
+        for (key, eq) in model.equations() {
+            pushed.add_equation(key, eq.clone());
+        }
+        validate::wrap_errors(errors.into_iter()).map(|_| pushed)
+    }
+
+    /** Pulls a model of the codomain theory back to the domain theory.
+
+    The pullback is computed generator by generator: it has one generator for
+    each generator of the model and each lift of its type along the theory
+    morphism, keyed by the pair of the two. Object generators are paired with
+    the identity on their lifted type, so that objects and morphisms have keys
+    of the same type. Lifts of morphism types are sought among the identities
+    and generators of the domain theory, which exhaust its morphisms when it is
+    a finite category.
+
+    Viewing models as categories sliced over their theories, this is *not* in
+    general the fiber product of the model with the domain theory. Composites
+    of lifts that are equal only because they have the same type, such as two
+    ways of delaying one of two signed morphisms, are not identified, so the
+    pullback of a free model is itself free. Likewise, each equation of the
+    model is lifted to an equation between lifts of its two sides only when
+    both sides have exactly one lift of a given composite type. A lift of one
+    side whose type is not the type of any lift of the other side is left
+    unconstrained, and an equation with more than one lift of the same type on
+    either side is reported as ambiguous rather than identifying all the lifts.
+     */
+    #[allow(clippy::type_complexity)]
+    pub fn pullback<Id>(
+        &self,
+        model: &DiscreteDblModel<Id, Cod>,
+    ) -> Result<DiscreteDblModel<(Id, Dom::Mor), Dom>, NonEmpty<InvalidModelMigration<Id>>>
+    where
+        Id: Eq + Clone + Hash,
+    {
+        let dom = self.dom.category();
+        let ob_types: Vec<_> = dom.objects().collect();
+        let mor_types: Vec<_> =
+            ob_types.iter().map(|x| dom.id(x.clone())).chain(dom.morphisms()).collect();
+
+        let mut pulled = DiscreteDblModel::new(self.dom.clone());
+        for x in model.ob_generators() {
+            let typ = model.ob_generator_type(&x);
+            for a in ob_types.iter().filter(|a| self.apply_ob_type(a).as_ref() == Some(&typ)) {
+                pulled.add_ob((x.clone(), dom.id(a.clone())), a.clone());
+            }
+        }
+        for f in model.mor_generators() {
+            let (Some(x), Some(y)) = (model.get_dom(&f), model.get_cod(&f)) else {
+                continue;
+            };
+            for m in self.mor_lifts(&mor_types, &model.mor_generator_type(&f)) {
+                let (a, b) = (dom.id(dom.dom(m)), dom.id(dom.cod(m)));
+                pulled.add_mor((f.clone(), m.clone()), (x.clone(), a), (y.clone(), b), m.clone());
+            }
+        }
+
+        let mut errors = Vec::new();
+        for (key, eq) in model.equations() {
+            let mut lifts: HashMap<Dom::Mor, (Vec<_>, Vec<_>)> = HashMap::new();
+            for (path, m) in self.path_lifts(model, &ob_types, &mor_types, &eq.lhs) {
+                lifts.entry(m).or_default().0.push(path);
+            }
+            for (path, m) in self.path_lifts(model, &ob_types, &mor_types, &eq.rhs) {
+                lifts.entry(m).or_default().1.push(path);
+            }
+            let mut is_ambiguous = false;
+            for (m, (mut lhs, mut rhs)) in lifts {
+                match (lhs.len(), rhs.len()) {
+                    (1, 1) => pulled.add_equation(
+                        (key.clone(), m),
+                        PathEq::new(lhs.pop().unwrap(), rhs.pop().unwrap()),
+                    ),
+                    (0, _) | (_, 0) => {}
+                    _ => is_ambiguous = true,
+                }
+            }
+            if is_ambiguous {
+                errors.push(InvalidModelMigration::AmbiguousEquation(key));
+            }
+        }
+        validate::wrap_errors(errors.into_iter()).map(|_| pulled)
+    }
+
+    /// Iterates over the lifts of a morphism type among the given types.
+    fn mor_lifts<'b>(
+        &'b self,
+        mor_types: &'b [Dom::Mor],
+        typ: &'b Cod::Mor,
+    ) -> impl Iterator<Item = &'b Dom::Mor> + 'b {
+        mor_types.iter().filter(move |m| self.apply_mor_type(m).as_ref() == Some(typ))
+    }
+
+    /// Lifts a path in a model of the codomain theory to all paths in the
+    /// pullback, each paired with its composite type.
+    #[allow(clippy::type_complexity)]
+    fn path_lifts<Id>(
+        &self,
+        model: &DiscreteDblModel<Id, Cod>,
+        ob_types: &[Dom::Ob],
+        mor_types: &[Dom::Mor],
+        path: &Path<Id, Id>,
+    ) -> Vec<(Path<(Id, Dom::Mor), (Id, Dom::Mor)>, Dom::Mor)>
+    where
+        Id: Eq + Clone + Hash,
+    {
+        let dom = self.dom.category();
+        let edges = match path {
+            Path::Id(x) => {
+                let typ = model.ob_generator_type(x);
+                return ob_types
+                    .iter()
+                    .filter(|a| self.apply_ob_type(a).as_ref() == Some(&typ))
+                    .map(|a| {
+                        let m = dom.id(a.clone());
+                        (Path::Id((x.clone(), m.clone())), m)
+                    })
+                    .collect();
+            }
+            Path::Seq(edges) => edges,
+        };
+        let mut lifts: Vec<(Vec<(Id, Dom::Mor)>, Option<Dom::Mor>)> = vec![(Vec::new(), None)];
+        for f in edges.iter() {
+            let typ = model.mor_generator_type(f);
+            let mut extended = Vec::new();
+            for (path, composite) in lifts {
+                for n in self.mor_lifts(mor_types, &typ) {
+                    let composite = match &composite {
+                        None => n.clone(),
+                        Some(m) if dom.cod(m) == dom.dom(n) => dom.compose2(m.clone(), n.clone()),
+                        Some(_) => continue,
+                    };
+                    let mut path = path.clone();
+                    path.push((f.clone(), n.clone()));
+                    extended.push((path, Some(composite)));
+                }
+            }
+            lifts = extended;
+        }
+        lifts
+            .into_iter()
+            .map(|(path, m)| (Path::from_vec(path).unwrap(), m.unwrap()))
+            .collect()
+    }
+}
+
+impl<Cat> DblTheoryMorphism<Cat, Cat>
+where
+    Cat: PresentedCategory,
+    Cat::Ob: Hash,
+    Cat::Mor: Hash,
+    Cat::MorGen: Hash,
+{
+    /// Constructs the identity morphism on a theory.
+    pub fn identity(theory: Arc<DiscreteDblTheory<Cat>>) -> Self {
+        let functor = FinFunctor::identity(theory.category());
+        Self::new(theory.clone(), theory, functor)
+    }
+}
+
+impl<Dom, Cod> Validate for DblTheoryMorphism<Dom, Cod>
+where
+    Dom: PresentedCategory,
+    Dom::Ob: Hash,
+    Dom::Mor: Hash,
+    Dom::MorGen: Hash,
+    Cod: PresentedCategory,
+    Cod::Ob: Hash,
+    Cod::Mor: Hash,
+{
+    type ValidationError = InvalidFinFunctor<Dom::Ob, Dom::MorGen>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        self.functor.validate_in(self.dom.category(), self.cod.category())
+    }
+}
+
+/// A failure to migrate a model along a theory morphism.
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
+pub enum InvalidModelMigration<Id> {
+    /// Object whose type is not mapped by the theory morphism.
+    #[error("Type of object `{0}` is not mapped by the theory morphism")]
+    ObType(Id),
+
+    /// Morphism whose type is not mapped by the theory morphism.
+    #[error("Type of morphism `{0}` is not mapped by the theory morphism")]
+    MorType(Id),
+
+    /// Equation whose sides have more than one lift of the same type along the
+    /// theory morphism, which the pullback does not identify.
+    #[error("Equation `{0}` has more than one lift of the same type along the theory morphism")]
+    AmbiguousEquation(Id),
+}
+
+#[cfg(test)]
+mod tests {
+    use ustr::{Ustr, ustr};
+
+    use super::*;
+    use crate::one::fin_category::{FinMor, UstrFinCategory};
+    use crate::stdlib::{models::*, theories::*};
+
+    type UstrDblTheoryMorphism = DblTheoryMorphism<UstrFinCategory, UstrFinCategory>;
+
+    fn theory_morphism(
+        dom: UstrDiscreteDblTheory,
+        cod: UstrDiscreteDblTheory,
+        mors: &[(&str, FinMor<Ustr, Ustr>)],
+    ) -> UstrDblTheoryMorphism {
+        let x = ustr("Object");
+        let mut functor = FinFunctor::default();
+        functor.assign_ob(x, x);
+        for (e, f) in mors {
+            functor.assign_mor_generator(ustr(e), f.clone());
+        }
+        DblTheoryMorphism::new(Arc::new(dom), Arc::new(cod), functor)
+    }
+
+    #[test]
+    fn validate_theory_morphism() {
+        let id = FinMor::Id(ustr("Object"));
+        let forget = theory_morphism(th_signed_category(), th_category(), &[("Negative", id)]);
+        assert!(forget.validate().is_ok());
+
+        let th = Arc::new(th_delayable_signed_category());
+        assert!(DblTheoryMorphism::identity(th).validate().is_ok());
+
+        // Zero is idempotent, whereas the negative sign squares to the identity.
+        let zero = FinMor::Generator(ustr("Zero"));
+        let bad = theory_morphism(
+            th_signed_category(),
+            th_nullable_signed_category(),
+            &[("Negative", zero)],
+        );
+        let errors: Vec<_> = bad.validate().unwrap_err().into();
+        assert!(matches!(errors.as_slice(), [InvalidFinFunctor::Equation(_)]));
+    }
+
+    #[test]
+    fn pushforward_models() {
+        let (neg, id) = (FinMor::Generator(ustr("Negative")), FinMor::Id(ustr("Object")));
+        let undelay = theory_morphism(
+            th_delayable_signed_category(),
+            th_signed_category(),
+            &[("Negative", neg.clone()), ("PositiveSlow", id.clone()), ("NegativeSlow", neg)],
+        );
+        let forget = theory_morphism(th_signed_category(), th_category(), &[("Negative", id)]);
+        assert!(undelay.validate().is_ok());
+
+        let delayed = delayed_negative_loop(undelay.dom().clone());
+        let signed = undelay.pushforward(&delayed).unwrap();
+        assert!(signed.validate().is_ok());
+        assert_eq!(signed, negative_loop(undelay.cod().clone()));
+
+        let forget_delays = undelay.compose(&forget);
+        assert!(forget_delays.validate().is_ok());
+        let model = forget_delays.pushforward(&delayed).unwrap();
+        assert_eq!(model.mor_generator_type(&ustr("loop")), FinMor::Id(ustr("Object")));
+
+        // The theory morphism is defined only on the negative sign.
+        let partial = theory_morphism(
+            th_delayable_signed_category(),
+            th_signed_category(),
+            &[("Negative", FinMor::Generator(ustr("Negative")))],
+        );
+        let errors: Vec<_> = partial.pushforward(&delayed).unwrap_err().into();
+        assert_eq!(errors, vec![InvalidModelMigration::MorType(ustr("loop"))]);
+    }
+
+    #[test]
+    fn pullback_models() {
+        let neg = FinMor::Generator(ustr("Negative"));
+        let include = theory_morphism(
+            th_signed_category(),
+            th_nullable_signed_category(),
+            &[("Negative", neg.clone())],
+        );
+        assert!(include.validate().is_ok());
+
+        let mut model = UstrDiscreteDblModel::new(include.cod().clone());
+        let (x, y) = (ustr("x"), ustr("y"));
+        model.add_ob(x, ustr("Object"));
+        model.add_ob(y, ustr("Object"));
+        model.add_mor(ustr("f"), x, y, neg.clone());
+        model.add_mor(ustr("g"), y, x, FinMor::Generator(ustr("Zero")));
+        let pulled = include.pullback(&model).unwrap();
+        assert!(pulled.validate().is_ok());
+        assert_eq!(pulled.ob_generators().count(), 2);
+        assert_eq!(pulled.mor_generators().collect::<Vec<_>>(), vec![(ustr("f"), neg.clone())]);
+        assert_eq!(include.pushforward(&pulled).unwrap().mor_generators().count(), 1);
+
+        // Each signed morphism lifts to a fast one and a slow one.
+        let (id, neg_slow) = (FinMor::Id(ustr("Object")), FinMor::Generator(ustr("NegativeSlow")));
+        let undelay = theory_morphism(
+            th_delayable_signed_category(),
+            th_signed_category(),
+            &[
+                ("Negative", neg.clone()),
+                ("PositiveSlow", id.clone()),
+                ("NegativeSlow", neg.clone()),
+            ],
+        );
+        let model = negative_loop(undelay.cod().clone());
+        let pulled = undelay.pullback(&model).unwrap();
+        assert!(pulled.validate().is_ok());
+        let (x, lp) = ((ustr("x"), id.clone()), ustr("loop"));
+        assert_eq!(pulled.ob_generators().collect::<Vec<_>>(), vec![x.clone()]);
+        let mut mors: Vec<_> = pulled.mor_generators().collect();
+        mors.sort_by_key(|(_, m)| m == &neg_slow);
+        assert_eq!(mors, vec![(lp, neg.clone()), (lp, neg_slow.clone())]);
+        assert_eq!(pulled.mor_generator_dom(&(lp, neg_slow)), x);
+
+        // Equations lift when their lifts are determined by type. Here only the
+        // fast composite is an identity, so the slow composites are unconstrained.
+        let mut model = negative_loop(undelay.cod().clone());
+        let eq = PathEq::new(Path::pair(lp, lp), Path::empty(ustr("x")));
+        model.add_equation(ustr("inv"), eq);
+        let pulled = undelay.pullback(&model).unwrap();
+        assert_eq!(pulled.equations().count(), 1);
+        assert!(pulled.validate().is_ok());
+
+        // Lifts of the same type are not identified, so the equation cannot be
+        // lifted when either side has several of them.
+        model.add_mor(ustr("loop2"), ustr("x"), ustr("x"), neg);
+        let eq = PathEq::new(Path::pair(lp, lp), Path::pair(ustr("loop2"), ustr("loop2")));
+        model.add_equation(ustr("same"), eq);
+        let errors: Vec<_> = undelay.pullback(&model).unwrap_err().into();
+        assert_eq!(errors, vec![InvalidModelMigration::AmbiguousEquation(ustr("same"))]);
+    }
+}
@@ -563,4 +563,49 @@ mod tests {
         let result = outer.flatten_in(&graph);
         assert!(result.is_isomorphic_to(&tree));
     }
+
+    #[test]
+    fn flatten_and_factor_tree() {
+        let bimod = Bimod::Main();
+        let graph = UnderlyingDblGraph(Bimod::Main());
+        let path = Path::Seq(nonempty![Bimod::Pro::Left, Bimod::Pro::Middle, Bimod::Pro::Right]);
+        let unitl = bimod.unit_ext(Bimod::Ob::Left).unwrap();
+        let unitr = bimod.unit_ext(Bimod::Ob::Right).unwrap();
+        let mid = bimod.composite_ext(path).unwrap();
+        let outer = DblTree(tree!(
+            DblNode::Cell(DblTree::single(mid.clone())) => {
+                DblNode::Cell(DblTree::two_level([unitl.clone()], bimod.id_cell(Bimod::Pro::Left))),
+                DblNode::Cell(DblTree::two_level(
+                    [unitl.clone(), bimod.id_cell(Bimod::Pro::Middle), unitr],
+                    mid,
+                )),
+                DblNode::Cell(DblTree::single(bimod.id_cell(Bimod::Pro::Right))),
+            }
+        ));
+
+        // Paste the flattened tree.
+        let tree = outer.flatten_in(&graph);
+        assert!(tree.contained_in(&graph));
+        assert_eq!(tree.leaves().count(), 5);
+        let cod = Bimod::Pro::Middle;
+        assert_eq!(tree.dom(&graph), Path::pair(cod, Bimod::Pro::Right));
+        assert_eq!(tree.cod(&graph), cod);
+        let cell = bimod.compose_cells(tree);
+
+        // Factor the pasted cell through the composite of its domain.
+        let ext = bimod.composite_ext(bimod.cell_dom(&cell)).unwrap();
+        let factored = bimod.through_composite(cell.clone(), 0..2).unwrap();
+        assert_eq!(factored, Path::single(cod));
+        assert_eq!(bimod.compose_cells(DblTree::two_level([ext], factored)), cell);
+
+        // Factor the pasted cell through a unit in its domain.
+        let factored = bimod.through_unit(cell.clone(), 0).unwrap();
+        assert_eq!(bimod.cell_dom(&factored).len(), 3);
+        let tree = DblTree::two_level(
+            [unitl, bimod.id_cell(cod), bimod.id_cell(Bimod::Pro::Right)],
+            factored,
+        );
+        assert_eq!(bimod.compose_cells(tree), cell);
+        assert_eq!(bimod.through_unit(cell, 3), None);
+    }
 }
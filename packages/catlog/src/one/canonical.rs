@@ -0,0 +1,345 @@
+/*! Canonical forms of finite graphs, for testing isomorphism.
+
+A *canonical form* of a graph is a representative of its isomorphism class that
+depends only on the isomorphism class, so that two graphs are isomorphic if and
+only if their canonical forms are equal. Canonical forms can be compared and
+hashed, allowing graphs to be deduplicated up to isomorphism in linear time.
+Vertices and edges can be labeled, in which case isomorphisms are required to
+preserve the labels.
+
+Canonical forms are computed using the *individualization-refinement* method
+pioneered by [nauty](https://pallini.di.uniroma1.it/). The vertices are
+partitioned by their labels and the partition is refined until it is
+*equitable*, meaning that vertices in the same cell have the same number of
+edges of each label into and out of each cell. When the partition is not
+discrete, each vertex in the first nontrivial cell is individualized in turn
+and the refinement repeated, giving a search tree whose leaves are orderings of
+the vertices. The canonical form is the least relabeled graph among the leaves.
+Automorphisms discovered during the search are used to prune branches of the
+tree that are equivalent to those already explored.
+
+The search tree can be exponentially large for certain highly regular graphs,
+but such graphs are rare in practice.
+ */
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::graph::FinGraph;
+use crate::zero::UnionFind;
+
+/** A finite graph with labeled vertices and edges in canonical form.
+
+The vertices are the integers `0..n`, in canonical order. Parallel edges with
+the same label are indistinguishable, so edges are stored as a sorted list of
+triples consisting of source, target, and label.
+ */
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CanonicalForm<VL, EL> {
+    vertex_labels: Vec<VL>,
+    edges: Vec<(usize, usize, EL)>,
+}
+
+impl<VL, EL> CanonicalForm<VL, EL> {
+    /// Gets the labels of the vertices, in canonical order.
+    pub fn vertex_labels(&self) -> &[VL] {
+        &self.vertex_labels
+    }
+
+    /// Gets the edges as triples of source, target, and label, in sorted order.
+    pub fn edges(&self) -> &[(usize, usize, EL)] {
+        &self.edges
+    }
+}
+
+/** Computes the canonical form of a finite graph with labeled vertices and edges.
+
+The labels are given by functions on vertices and on edges. To compute the
+canonical form of an unlabeled graph, use constant labels such as `()`.
+ */
+pub fn canonical_form<G, VL, EL>(
+    graph: &G,
+    vertex_label: impl Fn(&G::V) -> VL,
+    edge_label: impl Fn(&G::E) -> EL,
+) -> CanonicalForm<VL, EL>
+where
+    G: FinGraph,
+    G::V: Hash,
+    VL: Ord + Clone,
+    EL: Ord + Clone,
+{
+    canonical_labeling(graph, vertex_label, edge_label).0
+}
+
+/** Computes a canonical labeling of a finite graph with labeled vertices and edges.
+
+Returns the canonical form together with the vertices of the graph in
+canonical order, i.e., such that the vertex at position `i` corresponds to the
+vertex `i` of the canonical form. Composing the canonical orderings of two
+isomorphic graphs thus gives an isomorphism between them.
+ */
+pub fn canonical_labeling<G, VL, EL>(
+    graph: &G,
+    vertex_label: impl Fn(&G::V) -> VL,
+    edge_label: impl Fn(&G::E) -> EL,
+) -> (CanonicalForm<VL, EL>, Vec<G::V>)
+where
+    G: FinGraph,
+    G::V: Hash,
+    VL: Ord + Clone,
+    EL: Ord + Clone,
+{
+    let vertices: Vec<_> = graph.vertices().collect();
+    let index: HashMap<_, _> = vertices.iter().cloned().enumerate().map(|(i, v)| (v, i)).collect();
+    let edges: Vec<_> = graph
+        .edges()
+        .map(|e| (index[&graph.src(&e)], index[&graph.tgt(&e)], edge_label(&e)))
+        .collect();
+    let labels: Vec<_> = vertices.iter().map(vertex_label).collect();
+
+    let mut search = Search::new(vertices.len(), edges);
+    search.run(rank(&labels));
+    let Leaf { edges, order } = search.best.expect("Search should visit at least one leaf");
+    let vertex_labels = order.iter().map(|v| labels[*v].clone()).collect();
+    let vertices = order.into_iter().map(|v| vertices[v].clone()).collect();
+    (
+        CanonicalForm {
+            vertex_labels,
+            edges,
+        },
+        vertices,
+    )
+}
+
+/// State of the search for a canonical labeling.
+struct Search<EL> {
+    edges: Vec<(usize, usize, EL)>,
+    out: Vec<Vec<(usize, usize)>>,
+    inc: Vec<Vec<(usize, usize)>>,
+    edge_labels: Vec<usize>,
+    best: Option<Leaf<EL>>,
+    automorphisms: Vec<Vec<usize>>,
+}
+
+impl<EL: Ord + Clone> Search<EL> {
+    fn new(n: usize, edges: Vec<(usize, usize, EL)>) -> Self {
+        let labels: Vec<_> = edges.iter().map(|(_, _, label)| label.clone()).collect();
+        let edge_labels = rank(&labels);
+        let (mut out, mut inc) = (vec![Vec::new(); n], vec![Vec::new(); n]);
+        for (i, (src, tgt, _)) in edges.iter().enumerate() {
+            out[*src].push((i, *tgt));
+            inc[*tgt].push((i, *src));
+        }
+        Self {
+            edges,
+            out,
+            inc,
+            edge_labels,
+            best: None,
+            automorphisms: Vec::new(),
+        }
+    }
+
+    fn run(&mut self, colors: Vec<usize>) {
+        self.search(colors, &mut Vec::new());
+    }
+
+    /// Explores the subtree of the search tree below a coloring.
+    fn search(&mut self, colors: Vec<usize>, individualized: &mut Vec<usize>) {
+        let colors = self.refine(colors);
+        let mut sizes = vec![0; colors.len()];
+        for c in colors.iter() {
+            sizes[*c] += 1;
+        }
+        let Some(target) = sizes.iter().position(|size| *size > 1) else {
+            self.leaf(colors);
+            return;
+        };
+        let cell: Vec<_> = (0..colors.len()).filter(|v| colors[*v] == target).collect();
+        let mut explored: Vec<usize> = Vec::new();
+        for v in cell {
+            let mut orbits = self.orbits(individualized);
+            if explored.iter().any(|u| orbits.same(*u, v)) {
+                continue;
+            }
+            explored.push(v);
+            individualized.push(v);
+            self.search(individualize(&colors, v), individualized);
+            individualized.pop();
+        }
+    }
+
+    /// Refines a coloring until it is equitable.
+    fn refine(&self, mut colors: Vec<usize>) -> Vec<usize> {
+        let mut ncolors = count_colors(&colors);
+        loop {
+            let signatures: Vec<_> = (0..colors.len())
+                .map(|v| {
+                    let neighbors = |edges: &[(usize, usize)]| {
+                        let mut sig: Vec<_> =
+                            edges.iter().map(|(e, w)| (self.edge_labels[*e], colors[*w])).collect();
+                        sig.sort();
+                        sig
+                    };
+                    (colors[v], neighbors(&self.out[v]), neighbors(&self.inc[v]))
+                })
+                .collect();
+            colors = rank(&signatures);
+            let n = count_colors(&colors);
+            if n == ncolors {
+                return colors;
+            }
+            ncolors = n;
+        }
+    }
+
+    /// Compares the graph relabeled by a discrete coloring to the best so far.
+    fn leaf(&mut self, colors: Vec<usize>) {
+        let mut edges: Vec<_> = self
+            .edges
+            .iter()
+            .map(|(src, tgt, label)| (colors[*src], colors[*tgt], label.clone()))
+            .collect();
+        edges.sort();
+        let mut order = vec![0; colors.len()];
+        for (v, c) in colors.iter().enumerate() {
+            order[*c] = v;
+        }
+        match &self.best {
+            Some(best) if best.edges == edges => {
+                let mut automorphism = vec![0; order.len()];
+                for (u, v) in best.order.iter().zip(order.iter()) {
+                    automorphism[*u] = *v;
+                }
+                self.automorphisms.push(automorphism);
+            }
+            Some(best) if best.edges < edges => {}
+            _ => self.best = Some(Leaf { edges, order }),
+        }
+    }
+
+    /// Computes the orbits of the automorphisms found so far that fix the
+    /// individualized vertices.
+    fn orbits(&self, individualized: &[usize]) -> UnionFind {
+        let n = self.out.len();
+        let mut orbits = UnionFind::new(n);
+        for automorphism in self.automorphisms.iter() {
+            if individualized.iter().all(|v| automorphism[*v] == *v) {
+                for (u, v) in automorphism.iter().enumerate() {
+                    orbits.union(u, *v);
+                }
+            }
+        }
+        orbits
+    }
+}
+
+/// A leaf of the search tree: a relabeled graph and the corresponding ordering
+/// of the original vertices.
+struct Leaf<EL> {
+    edges: Vec<(usize, usize, EL)>,
+    order: Vec<usize>,
+}
+
+/// Splits a vertex off from its cell, placing it before the rest of the cell.
+fn individualize(colors: &[usize], v: usize) -> Vec<usize> {
+    let split: Vec<_> = (0..colors.len())
+        .map(|u| 2 * colors[u] + usize::from(u != v && colors[u] == colors[v]))
+        .collect();
+    rank(&split)
+}
+
+/// Replaces each value by its rank among the distinct values.
+fn rank<T: Ord>(values: &[T]) -> Vec<usize> {
+    let mut sorted: Vec<_> = values.iter().collect();
+    sorted.sort();
+    sorted.dedup();
+    values.iter().map(|x| sorted.binary_search(&x).unwrap()).collect()
+}
+
+fn count_colors(colors: &[usize]) -> usize {
+    colors.iter().max().map_or(0, |c| c + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::one::graph::{Graph, HashGraph, SkelGraph};
+
+    fn unlabeled<G: FinGraph>(graph: &G) -> CanonicalForm<(), ()>
+    where
+        G::V: Hash,
+    {
+        canonical_form(graph, |_| (), |_| ())
+    }
+
+    /// Copy of a skeletal graph with vertices permuted and edges reversed.
+    fn permuted(graph: &SkelGraph, perm: &[usize]) -> SkelGraph {
+        let mut result = SkelGraph::default();
+        result.add_vertices(graph.vertex_count());
+        let edges: Vec<_> = graph.edges().collect();
+        for e in edges.into_iter().rev() {
+            result.add_edge(perm[graph.src(&e)], perm[graph.tgt(&e)]);
+        }
+        result
+    }
+
+    #[test]
+    fn isomorphic_graphs() {
+        let g = SkelGraph::cycle(5);
+        let h = permuted(&g, &[3, 0, 4, 2, 1]);
+        assert_eq!(unlabeled(&g), unlabeled(&h));
+        assert_ne!(unlabeled(&g), unlabeled(&SkelGraph::path(6)));
+
+        let mut g = SkelGraph::triangle();
+        g.add_vertex();
+        g.add_edge(3, 0);
+        g.add_edge(1, 1);
+        let h = permuted(&g, &[2, 3, 1, 0]);
+        let (form, order) = canonical_labeling(&h, |_| (), |_| ());
+        assert_eq!(form, unlabeled(&g));
+        assert_eq!(form.vertex_labels().len(), 4);
+        assert_eq!(form.edges().len(), 5);
+        assert_eq!(order.len(), 4);
+
+        // Two 2-cycles versus a 4-cycle.
+        let mut g = SkelGraph::default();
+        g.add_vertices(4);
+        for (v, w) in [(0, 1), (1, 0), (2, 3), (3, 2)] {
+            g.add_edge(v, w);
+        }
+        assert_ne!(unlabeled(&g), unlabeled(&SkelGraph::cycle(4)));
+
+        // Highly symmetric graphs are handled by pruning automorphisms.
+        let mut g = SkelGraph::default();
+        g.add_vertices(20);
+        assert_eq!(unlabeled(&g).vertex_labels().len(), 20);
+        let g = SkelGraph::cycle(30);
+        assert_eq!(unlabeled(&g), unlabeled(&permuted(&g, &(0..30).rev().collect::<Vec<_>>())));
+    }
+
+    #[test]
+    fn labeled_graphs() {
+        let mut g: HashGraph<_, _> = Default::default();
+        g.add_vertices(['x', 'y', 'z']);
+        g.add_edge('f', 'x', 'y');
+        g.add_edge('g', 'y', 'z');
+        let mut h: HashGraph<_, _> = Default::default();
+        h.add_vertices(['a', 'b', 'c']);
+        h.add_edge('u', 'b', 'c');
+        h.add_edge('v', 'a', 'b');
+
+        let sign = |e: &char| ['f', 'v'].contains(e);
+        assert_eq!(canonical_form(&g, |_| 0, sign), canonical_form(&h, |_| 0, sign));
+        let sign = |e: &char| ['f', 'u'].contains(e);
+        assert_ne!(canonical_form(&g, |_| 0, sign), canonical_form(&h, |_| 0, sign));
+
+        let kind = |v: &char| ['x', 'c'].contains(v);
+        assert_ne!(canonical_form(&g, kind, |_| ()), canonical_form(&h, kind, |_| ()));
+        let kind = |v: &char| ['x', 'a'].contains(v);
+        let (form, order) = canonical_labeling(&g, kind, |_| ());
+        assert_eq!(form, canonical_form(&h, kind, |_| ()));
+        assert_eq!(form.vertex_labels(), &[false, false, true]);
+        assert_eq!(order[2], 'x');
+    }
+}
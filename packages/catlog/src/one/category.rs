@@ -5,7 +5,7 @@ use derive_more::From;
 use ref_cast::RefCast;
 
 use super::graph::{FinGraph, Graph};
-use super::path::Path;
+use super::path::{Path, PathEq};
 use crate::zero::{FinSet, Set};
 
 /** A category.
@@ -256,6 +256,43 @@ impl<G: FinGraph> FgCategory for FreeCategory<G> {
     }
 }
 
+/** A finitely generated category with an explicit presentation.
+
+Every morphism in such a category can be written as a path of morphism
+generators, and the category is presented by the generators together with a
+set of path equations, or *relations*. The presentation need not be canonical:
+a finite category, for instance, is presented by its composition table.
+ */
+pub trait PresentedCategory: FgCategory {
+    /// Writes a morphism as a path of morphism generators.
+    fn mor_path(&self, f: &Self::Mor) -> Path<Self::Ob, Self::MorGen>;
+
+    /// Iterates over relations that, together with the generators, present the
+    /// category.
+    fn relations(&self) -> impl Iterator<Item = PathEq<Self::Ob, Self::MorGen>>;
+
+    /** Decides whether two morphisms in the category are equal.
+
+    Returns `None` if the question cannot be decided, which can happen since
+    the word problem for finitely presented categories is undecidable.
+     */
+    fn decide_eq(&self, f: &Self::Mor, g: &Self::Mor) -> Option<bool>;
+}
+
+impl<G: FinGraph> PresentedCategory for FreeCategory<G> {
+    fn mor_path(&self, path: &Path<G::V, G::E>) -> Path<G::V, G::E> {
+        path.clone()
+    }
+
+    fn relations(&self) -> impl Iterator<Item = PathEq<G::V, G::E>> {
+        std::iter::empty()
+    }
+
+    fn decide_eq(&self, path1: &Path<G::V, G::E>, path2: &Path<G::V, G::E>) -> Option<bool> {
+        Some(path1 == path2)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use nonempty::nonempty;
@@ -0,0 +1,374 @@
+/*! C-sets: set-valued functors on finitely presented categories.
+
+A *C-set*, or *copresheaf* on a category `C`, is a functor from `C` to the
+category of sets. When the category `C` is thought of as a database schema, a
+C-set is an instance of the schema: each object of `C` is a table, each morphism
+generator is a foreign key, and the equations in the presentation of `C` are
+integrity constraints. To accommodate data of other kinds, such as strings or
+numbers, some objects of the schema can be designated as *attribute types*.
+Morphism generators into an attribute type are then attributes, which assign
+values to rows instead of referencing other rows. This is the kind of data
+described by a model of the [theory of schemas](crate::stdlib::th_schema).
+
+The rows of each table form a skeletal finite set, and the foreign keys and
+attributes are stored as indexed columns, allowing efficient lookup of
+preimages.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::ops::Range;
+use std::sync::Arc;
+
+use derivative::Derivative;
+use nonempty::NonEmpty;
+use thiserror::Error;
+
+use super::category::*;
+use super::path::*;
+use crate::validate::{self, Validate};
+use crate::zero::*;
+
+/** A C-set, possibly with attributes, on a finitely presented category.
+
+The C-set is defined on a schema, which is any [presented
+category](PresentedCategory). Attribute values have type `T`. Morphism
+generators whose domain is an attribute type are not supported and are reported
+as errors during validation.
+ */
+#[derive(Derivative)]
+#[derivative(Clone(bound = "T: Clone"))]
+pub struct CSet<Cat: PresentedCategory, T> {
+    schema: Arc<Cat>,
+    attr_types: HashSet<Cat::Ob>,
+    parts: HashMap<Cat::Ob, SkelFinSet>,
+    homs: HashMap<Cat::MorGen, SkelIndexedColumn>,
+    attrs: HashMap<Cat::MorGen, IndexedVecColumn<T>>,
+}
+
+impl<Cat, T> CSet<Cat, T>
+where
+    Cat: PresentedCategory,
+    Cat::Ob: Hash,
+    Cat::MorGen: Hash,
+    T: Eq + Hash + Clone,
+{
+    /// Creates an empty C-set on a schema with the given attribute types.
+    pub fn new(schema: Arc<Cat>, attr_types: impl IntoIterator<Item = Cat::Ob>) -> Self {
+        let attr_types: HashSet<_> = attr_types.into_iter().collect();
+        let parts = schema
+            .objects()
+            .filter(|x| !attr_types.contains(x))
+            .map(|x| (x, SkelFinSet::default()))
+            .collect();
+        let (mut homs, mut attrs) = (HashMap::new(), HashMap::new());
+        for e in schema.mor_generators() {
+            if attr_types.contains(&schema.mor_generator_cod(&e)) {
+                attrs.insert(e, Default::default());
+            } else {
+                homs.insert(e, Default::default());
+            }
+        }
+        Self {
+            schema,
+            attr_types,
+            parts,
+            homs,
+            attrs,
+        }
+    }
+
+    /// Gets the schema of the C-set.
+    pub fn schema(&self) -> &Cat {
+        &self.schema
+    }
+
+    /// Is the object of the schema an attribute type?
+    pub fn is_attr_type(&self, x: &Cat::Ob) -> bool {
+        self.attr_types.contains(x)
+    }
+
+    /// Gets the number of parts, or rows, in the table for an object.
+    pub fn nparts(&self, x: &Cat::Ob) -> usize {
+        self.parts.get(x).map_or(0, |set| set.len())
+    }
+
+    /// Iterates over the parts in the table for an object.
+    pub fn parts(&self, x: &Cat::Ob) -> Range<usize> {
+        0..self.nparts(x)
+    }
+
+    /// Adds a part to the table for an object, returning the new part.
+    pub fn add_part(&mut self, x: &Cat::Ob) -> usize {
+        self.table_mut(x).insert()
+    }
+
+    /// Adds several parts to the table for an object, returning the new parts.
+    pub fn add_parts(&mut self, x: &Cat::Ob, n: usize) -> Range<usize> {
+        self.table_mut(x).extend(n)
+    }
+
+    /// Gets the value of a foreign key at a part, if it is set.
+    pub fn subpart(&self, e: &Cat::MorGen, i: usize) -> Option<usize> {
+        self.homs.get(e).and_then(|col| col.apply(&i))
+    }
+
+    /// Sets the value of a foreign key at a part, returning the previous value.
+    pub fn set_subpart(&mut self, e: &Cat::MorGen, i: usize, j: usize) -> Option<usize> {
+        self.homs.get_mut(e).expect("Morphism should be a foreign key").set(i, j)
+    }
+
+    /// Gets the parts referencing a given part via a foreign key.
+    pub fn incident(&self, e: &Cat::MorGen, j: usize) -> Vec<usize> {
+        self.homs.get(e).map(|col| col.preimage(&j).collect()).unwrap_or_default()
+    }
+
+    /// Gets the value of an attribute at a part, if it is set.
+    pub fn attr(&self, e: &Cat::MorGen, i: usize) -> Option<T> {
+        self.attrs.get(e).and_then(|col| col.apply(&i))
+    }
+
+    /// Sets the value of an attribute at a part, returning the previous value.
+    pub fn set_attr(&mut self, e: &Cat::MorGen, i: usize, value: T) -> Option<T> {
+        self.attrs.get_mut(e).expect("Morphism should be an attribute").set(i, value)
+    }
+
+    /// Gets the parts having a given value of an attribute.
+    pub fn attr_incident(&self, e: &Cat::MorGen, value: &T) -> Vec<usize> {
+        self.attrs.get(e).map(|col| col.preimage(value).collect()).unwrap_or_default()
+    }
+
+    /** Removes a part from the table for an object.
+
+    To keep the table skeletal, the last part in the table is moved into the
+    place of the removed part, and all references to it are updated. Foreign
+    keys that referenced the removed part are unset, so the C-set may need to
+    be repaired before it is valid again.
+     */
+    pub fn rem_part(&mut self, x: &Cat::Ob, i: usize) {
+        let n = self.nparts(x);
+        assert!(i < n, "Part to remove should be in the table");
+        let last = n - 1;
+        for (e, col) in self.homs.iter_mut() {
+            if self.schema.mor_generator_dom(e) == *x {
+                move_row(col, last, i);
+            }
+        }
+        for (e, col) in self.attrs.iter_mut() {
+            if self.schema.mor_generator_dom(e) == *x {
+                move_row(col, last, i);
+            }
+        }
+        for (e, col) in self.homs.iter_mut() {
+            if self.schema.mor_generator_cod(e) == *x {
+                let dangling: Vec<_> = col.preimage(&i).collect();
+                for j in dangling {
+                    col.unset(&j);
+                }
+                let moved: Vec<_> = col.preimage(&last).collect();
+                for j in moved {
+                    col.set(j, i);
+                }
+            }
+        }
+        *self.table_mut(x) = SkelFinSet::from(last);
+    }
+
+    /// Iterates over failures of the C-set to be well defined.
+    pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidCSet<Cat::Ob, Cat::MorGen>> + '_ {
+        let mut errs = Vec::new();
+        for e in self.schema.mor_generators() {
+            let x = self.schema.mor_generator_dom(&e);
+            if self.is_attr_type(&x) {
+                errs.push(InvalidCSet::AttrTypeDom(e));
+                continue;
+            }
+            if let Some(col) = self.homs.get(&e) {
+                let y = self.schema.mor_generator_cod(&e);
+                for i in self.parts(&x) {
+                    match col.apply(&i) {
+                        None => errs.push(InvalidCSet::Subpart(e.clone(), i)),
+                        Some(j) if j >= self.nparts(&y) => {
+                            errs.push(InvalidCSet::SubpartCod(e.clone(), i))
+                        }
+                        Some(_) => {}
+                    }
+                }
+            } else if let Some(col) = self.attrs.get(&e) {
+                for i in self.parts(&x).filter(|i| !col.is_set(i)) {
+                    errs.push(InvalidCSet::Subpart(e.clone(), i));
+                }
+            }
+        }
+        if errs.is_empty() {
+            for eq in self.schema.relations() {
+                let x = self.path_src(&eq.lhs);
+                for i in self.parts(&x) {
+                    if self.eval_path(&eq.lhs, i) != self.eval_path(&eq.rhs, i) {
+                        errs.push(InvalidCSet::Equation(eq.clone(), i));
+                    }
+                }
+            }
+        }
+        errs.into_iter()
+    }
+
+    /** Evaluates a path of morphism generators at a part.
+
+    Returns `None` if a foreign key or attribute along the path is unset.
+     */
+    pub fn eval_path(&self, path: &Path<Cat::Ob, Cat::MorGen>, i: usize) -> Option<CSetElem<T>> {
+        let mut elem = CSetElem::Part(i);
+        for e in path.iter() {
+            let CSetElem::Part(j) = elem else {
+                return None;
+            };
+            elem = if let Some(k) = self.subpart(e, j) {
+                CSetElem::Part(k)
+            } else {
+                CSetElem::Value(self.attr(e, j)?)
+            };
+        }
+        Some(elem)
+    }
+
+    fn path_src(&self, path: &Path<Cat::Ob, Cat::MorGen>) -> Cat::Ob {
+        match path {
+            Path::Id(x) => x.clone(),
+            Path::Seq(edges) => self.schema.mor_generator_dom(edges.first()),
+        }
+    }
+
+    fn table_mut(&mut self, x: &Cat::Ob) -> &mut SkelFinSet {
+        self.parts.get_mut(x).expect("Object should be a table in the schema")
+    }
+}
+
+impl<Cat, T> Validate for CSet<Cat, T>
+where
+    Cat: PresentedCategory,
+    Cat::Ob: Hash,
+    Cat::MorGen: Hash,
+    T: Eq + Hash + Clone,
+{
+    type ValidationError = InvalidCSet<Cat::Ob, Cat::MorGen>;
+
+    fn validate(&self) -> Result<(), NonEmpty<Self::ValidationError>> {
+        validate::wrap_errors(self.iter_invalid())
+    }
+}
+
+/// Moves the value of a column at one row to another, unsetting the former.
+fn move_row<Col: MutMapping<Dom = usize>>(col: &mut Col, from: usize, to: usize) {
+    col.unset(&to);
+    if let Some(y) = col.unset(&from) {
+        col.set(to, y);
+    }
+}
+
+/// An element of a C-set: either a part of a table or an attribute value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CSetElem<T> {
+    /// A part of a table, i.e., a row.
+    Part(usize),
+
+    /// A value of an attribute type.
+    Value(T),
+}
+
+/// A failure of a C-set to be well defined.
+#[derive(Debug, Error)]
+pub enum InvalidCSet<V, E> {
+    /// A morphism generator not defined at a part.
+    #[error("Morphism `{0}` is not defined at part `{1}`")]
+    Subpart(E, usize),
+
+    /// A foreign key whose value at a part is not in the codomain table.
+    #[error("Value of morphism `{0}` at part `{1}` is not in the codomain")]
+    SubpartCod(E, usize),
+
+    /// A morphism generator with an attribute type as its domain.
+    #[error("Domain of morphism `{0}` is an attribute type")]
+    AttrTypeDom(E),
+
+    /// A path equation in the schema that does not hold at a part.
+    #[error("Path equation does not hold at part `{1}`")]
+    Equation(PathEq<V, E>, usize),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::one::fin_category::FpCategory;
+    use crate::stdlib::{models::walking_attr, theories::th_schema};
+    use ustr::ustr;
+
+    fn sch_rgraph() -> FpCategory<char, char, &'static str> {
+        let mut sch: FpCategory<_, _, _> = Default::default();
+        sch.add_ob_generators(['V', 'E']);
+        sch.add_mor_generator('s', 'E', 'V');
+        sch.add_mor_generator('t', 'E', 'V');
+        sch.add_mor_generator('r', 'V', 'E');
+        sch.add_equation("src", PathEq::new(Path::pair('r', 's'), Path::empty('V')));
+        sch.add_equation("tgt", PathEq::new(Path::pair('r', 't'), Path::empty('V')));
+        sch
+    }
+
+    #[test]
+    fn reflexive_graph() {
+        let mut g: CSet<_, ()> = CSet::new(Arc::new(sch_rgraph()), []);
+        let vs = g.add_parts(&'V', 2);
+        for v in vs.clone() {
+            let e = g.add_part(&'E');
+            g.set_subpart(&'r', v, e);
+            g.set_subpart(&'s', e, v);
+            g.set_subpart(&'t', e, v);
+        }
+        assert!(g.validate().is_ok());
+
+        let e = g.add_part(&'E');
+        g.set_subpart(&'s', e, 0);
+        g.set_subpart(&'t', e, 1);
+        assert_eq!(g.nparts(&'E'), 3);
+        assert!(g.validate().is_ok());
+        assert_eq!(g.incident(&'s', 0).into_iter().collect::<HashSet<_>>(), HashSet::from([0, 2]));
+
+        g.set_subpart(&'r', 1, 2);
+        let errs: Vec<_> = g.iter_invalid().collect();
+        assert!(matches!(errs.as_slice(), [InvalidCSet::Equation(_, 1)]));
+        g.set_subpart(&'r', 1, 1);
+
+        // Removing the first edge moves the last edge into its place.
+        g.rem_part(&'E', 0);
+        assert_eq!(g.nparts(&'E'), 2);
+        assert_eq!((g.subpart(&'s', 0), g.subpart(&'t', 0)), (Some(0), Some(1)));
+        assert_eq!(g.subpart(&'r', 0), None);
+        let errs: Vec<_> = g.iter_invalid().collect();
+        assert!(matches!(errs.as_slice(), [InvalidCSet::Subpart('r', 0)]));
+    }
+
+    #[test]
+    fn attributed_schema() {
+        let th = Arc::new(th_schema());
+        let schema = Arc::new(walking_attr(th));
+        let (entity, attr) = (ustr("entity"), ustr("attr"));
+        let mut data = CSet::new(schema, [ustr("type")]);
+        assert!(data.is_attr_type(&ustr("type")));
+        for name in ["foo", "bar", "baz"] {
+            let i = data.add_part(&entity);
+            data.set_attr(&attr, i, name.to_string());
+        }
+        assert!(data.validate().is_ok());
+        assert_eq!(data.attr(&attr, 1), Some("bar".to_string()));
+        let value = "baz".to_string();
+        assert_eq!(data.attr_incident(&attr, &value), vec![2]);
+
+        data.rem_part(&entity, 0);
+        assert_eq!(data.attr(&attr, 0), Some("baz".to_string()));
+        assert!(data.validate().is_ok());
+
+        data.add_part(&entity);
+        let errs: Vec<_> = data.iter_invalid().collect();
+        assert!(matches!(errs.as_slice(), [InvalidCSet::Subpart(_, 2)]));
+    }
+}
@@ -1,20 +1,31 @@
 //! Data structures for finite and finitely presented categories.
 
 use std::hash::{BuildHasher, BuildHasherDefault, Hash, RandomState};
+use std::sync::OnceLock;
 
 use derivative::Derivative;
 use nonempty::NonEmpty;
 use thiserror::Error;
 use ustr::{IdentityHasher, Ustr};
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde-wasm")]
+use tsify_next::Tsify;
+
 use super::category::*;
 use super::graph::*;
 use super::path::*;
+use super::rewriting::{IndexedRewriteSystem, RewriteSystem};
 use crate::validate::{self, Validate};
 use crate::zero::{Column, HashColumn, Mapping, MutMapping};
 
 /// Morphism in a finite category.
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum FinMor<V, E> {
     /// Identity morphism on an object.
     Id(V),
@@ -83,6 +94,37 @@ where
         self.compose_map.set((d, e), f);
     }
 
+    /** Relabels the objects and morphism generators of the category.
+
+    The labeling functions should be injective. Otherwise, distinct objects or
+    generators will be merged and the resulting category may be invalid.
+     */
+    pub fn relabel<W, F, T>(
+        &self,
+        ob: impl Fn(&V) -> W,
+        mor: impl Fn(&E) -> F,
+    ) -> FinCategory<W, F, T>
+    where
+        W: Eq + Hash + Clone,
+        F: Eq + Hash + Clone,
+        T: BuildHasher + Default,
+    {
+        let mut cat = FinCategory::default();
+        cat.add_ob_generators(self.generators.vertices().map(|x| ob(&x)));
+        for e in self.generators.edges() {
+            let (x, y) = (self.generators.src(&e), self.generators.tgt(&e));
+            cat.add_mor_generator(mor(&e), ob(&x), ob(&y));
+        }
+        for ((d, e), f) in self.compose_map.iter() {
+            let f = match f {
+                FinMor::Id(x) => FinMor::Id(ob(x)),
+                FinMor::Generator(g) => FinMor::Generator(mor(g)),
+            };
+            cat.set_composite(mor(&d), mor(&e), f);
+        }
+        cat
+    }
+
     /// Iterates over failures to be a well-defined finite category.
     pub fn iter_invalid(&self) -> impl Iterator<Item = InvalidFinCategory<E>> + '_ {
         let generator_errors = self.generators.iter_invalid().map(|err| match err {
@@ -109,7 +151,63 @@ where
                 errs
             })
         });
-        generator_errors.chain(compose_errors)
+        let law_errors = self.generators.edges().flat_map(move |e1| {
+            let x = self.generators.tgt(&e1);
+            let triples: Vec<_> = self
+                .generators
+                .out_edges(&x)
+                .flat_map(|e2| {
+                    let y = self.generators.tgt(&e2);
+                    let e1 = &e1;
+                    self.generators
+                        .out_edges(&y)
+                        .map(move |e3| (e1.clone(), e2.clone(), e3))
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            triples.into_iter().filter_map(|(e1, e2, e3)| self.check_laws(e1, e2, e3))
+        });
+        generator_errors.chain(compose_errors).chain(law_errors)
+    }
+
+    /** Checks the associativity and unit laws for a composable triple.
+
+    Whenever the composite of two generators is an identity, associativity for
+    the triple reduces to a unit law, which is reported as such. Triples whose
+    binary composites are undefined or ill-typed are skipped, since those
+    failures are reported separately.
+     */
+    fn check_laws(&self, e1: E, e2: E, e3: E) -> Option<InvalidFinCategory<E>> {
+        let f = FinMor::Generator(e1.clone());
+        let g = FinMor::Generator(e2.clone());
+        let h = FinMor::Generator(e3.clone());
+        let fg = self.try_compose2(f.clone(), g.clone())?;
+        let gh = self.try_compose2(g, h.clone())?;
+        let lhs = self.try_compose2(fg.clone(), h)?;
+        let rhs = self.try_compose2(f, gh.clone())?;
+        if lhs == rhs {
+            None
+        } else if matches!(fg, FinMor::Id(_)) {
+            Some(InvalidFinCategory::LeftUnit(e1, e2, e3))
+        } else if matches!(gh, FinMor::Id(_)) {
+            Some(InvalidFinCategory::RightUnit(e1, e2, e3))
+        } else {
+            Some(InvalidFinCategory::Associativity(e1, e2, e3))
+        }
+    }
+
+    /// Composes two morphisms, returning `None` if ill-typed or undefined.
+    pub(crate) fn try_compose2(&self, f: FinMor<V, E>, g: FinMor<V, E>) -> Option<FinMor<V, E>> {
+        if !(self.has_mor(&f) && self.has_mor(&g) && self.cod(&f) == self.dom(&g)) {
+            return None;
+        }
+        let fg = match (f, g) {
+            (FinMor::Id(_), g) => g,
+            (f, FinMor::Id(_)) => f,
+            (FinMor::Generator(d), FinMor::Generator(e)) => self.compose_map.apply(&(d, e))?,
+        };
+        let valid = self.has_mor(&fg);
+        valid.then_some(fg)
     }
 }
 
@@ -209,8 +307,36 @@ where
     }
 }
 
+impl<V, E, S> PresentedCategory for FinCategory<V, E, S>
+where
+    V: Eq + Hash + Clone,
+    E: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    fn mor_path(&self, f: &FinMor<V, E>) -> Path<V, E> {
+        match f {
+            FinMor::Id(v) => Path::Id(v.clone()),
+            FinMor::Generator(e) => Path::single(e.clone()),
+        }
+    }
+
+    fn relations(&self) -> impl Iterator<Item = PathEq<V, E>> {
+        self.compose_map
+            .iter()
+            .map(|((d, e), f)| PathEq::new(Path::pair(d.clone(), e.clone()), self.mor_path(f)))
+    }
+
+    fn decide_eq(&self, f: &FinMor<V, E>, g: &FinMor<V, E>) -> Option<bool> {
+        Some(f == g)
+    }
+}
+
 /// A failure of a finite category to be well defined.
-#[derive(Debug, Error)]
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "tag", content = "content"))]
+#[cfg_attr(feature = "serde-wasm", derive(Tsify))]
+#[cfg_attr(feature = "serde-wasm", tsify(into_wasm_abi, from_wasm_abi))]
 pub enum InvalidFinCategory<E> {
     /// Morphism assigned a domain not contained in the category.
     #[error("Domain of morphism `{0}` is not in the category")]
@@ -231,6 +357,20 @@ pub enum InvalidFinCategory<E> {
     /// Composite of a pair of morphisms has incompatible codomain.
     #[error("Composite of morphisms `{0}` and `{1}` has incompatible codomain")]
     CompositeCod(E, E),
+
+    /// Composition of a triple of morphisms is not associative.
+    #[error("Composition of morphisms `{0}`, `{1}`, and `{2}` is not associative")]
+    Associativity(E, E, E),
+
+    /// Composite of the first two morphisms is an identity but does not act
+    /// as one on the third morphism.
+    #[error("Composite of morphisms `{0}` and `{1}` is an identity but is not a unit for `{2}`")]
+    LeftUnit(E, E, E),
+
+    /// Composite of the last two morphisms is an identity but does not act
+    /// as one on the first morphism.
+    #[error("Composite of morphisms `{1}` and `{2}` is an identity but is not a unit for `{0}`")]
+    RightUnit(E, E, E),
 }
 
 /** A finitely presented category.
@@ -242,14 +382,23 @@ rather than morphism themselves.
 
 Like the object and morphism generators, the equations are identified by keys.
 Depending on the application, these could be axiom names or meaningless IDs.
+
+The rewriting systems used to decide equality of morphisms and to compute
+normal forms are computed when first needed and then cached until the
+presentation is modified.
  */
-#[derive(Clone, Derivative, Debug)]
+#[derive(Clone, Derivative)]
+#[derivative(Debug)]
 #[derivative(Default(bound = "S: Default"))]
 #[derivative(PartialEq(bound = "V: Eq + Hash, E: Eq + Hash, EqKey: Eq + Hash, S: BuildHasher"))]
 #[derivative(Eq(bound = "V: Eq + Hash, E: Eq + Hash, EqKey: Eq + Hash, S: BuildHasher"))]
 pub struct FpCategory<V, E, EqKey, S = RandomState> {
     generators: HashGraph<V, E, S>,
     equations: HashColumn<EqKey, PathEq<V, E>, S>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    rewriting: OnceLock<Option<RewriteSystem<V, E>>>,
+    #[derivative(Debug = "ignore", PartialEq = "ignore")]
+    indexed_rewriting: OnceLock<Option<IndexedRewriteSystem<V, E>>>,
 }
 
 /// A finitely presented category with generators and equation keys of type
@@ -278,6 +427,11 @@ where
         self.equations.values()
     }
 
+    /// Iterates over path equations in the presentation, along with their keys.
+    pub fn keyed_equations(&self) -> impl Iterator<Item = (EqKey, &PathEq<V, E>)> {
+        self.equations.iter()
+    }
+
     /// Adds an object generator, returning whether it is new.
     pub fn add_ob_generator(&mut self, v: V) -> bool {
         self.generators.add_vertex(v)
@@ -293,11 +447,15 @@ where
 
     /// Adds a morphism generator, returning whether it is new.
     pub fn add_mor_generator(&mut self, e: E, dom: V, cod: V) -> bool {
+        self.rewriting.take();
+        self.indexed_rewriting.take();
         self.generators.add_edge(e, dom, cod)
     }
 
     /// Adds a morphism generator without initializing its (co)domain.
     pub fn make_mor_generator(&mut self, e: E) -> bool {
+        self.rewriting.take();
+        self.indexed_rewriting.take();
         self.generators.make_edge(e)
     }
 
@@ -313,16 +471,22 @@ where
 
     /// Sets the domain of a morphism generator.
     pub fn set_dom(&mut self, e: E, v: V) -> Option<V> {
+        self.rewriting.take();
+        self.indexed_rewriting.take();
         self.generators.set_src(e, v)
     }
 
     /// Sets the codomain of a morphism generator.
     pub fn set_cod(&mut self, e: E, v: V) -> Option<V> {
+        self.rewriting.take();
+        self.indexed_rewriting.take();
         self.generators.set_tgt(e, v)
     }
 
     /// Adds a path equation to the presentation.
     pub fn add_equation(&mut self, key: EqKey, eq: PathEq<V, E>) {
+        self.rewriting.take();
+        self.indexed_rewriting.take();
         self.equations.set(key, eq);
     }
 
@@ -349,6 +513,160 @@ where
     }
 }
 
+/// Bound on the number of rules created when completing a presentation to
+/// decide equality of morphisms.
+pub(crate) const MAX_COMPLETION_RULES: usize = 1000;
+
+impl<V, E, EqKey, S> FpCategory<V, E, EqKey, S>
+where
+    V: Eq + Clone + Hash,
+    E: Ord + Clone + Hash,
+    EqKey: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    /** Completes the equations of the presentation into a rewriting system.
+
+    Runs Knuth-Bendix completion, as described in the [`rewriting`](super::rewriting)
+    module, creating at most `max_rules` rules. Assumes that the presentation
+    is valid.
+     */
+    pub fn complete(&self, max_rules: usize) -> Option<RewriteSystem<V, E>> {
+        RewriteSystem::complete(&self.generators, self.equations.values(), max_rules)
+    }
+
+    /// Gets the cached rewriting system, completing the equations if needed.
+    fn rewriting(&self) -> Option<&RewriteSystem<V, E>> {
+        self.rewriting.get_or_init(|| self.complete(MAX_COMPLETION_RULES)).as_ref()
+    }
+
+    /** Composes a path of morphisms and reduces the result to normal form.
+
+    Unlike [`compose`](Category::compose), which simply concatenates paths, the
+    resulting morphism representative is canonical: two composites are equal
+    in the presented category just when they are equal as paths. Returns
+    `None` under the same conditions as [`is_equal`](Self::is_equal).
+     */
+    pub fn compose_normal(&self, path: Path<V, Path<V, E>>) -> Option<Path<V, E>> {
+        let composite = self.compose(path);
+        if self.is_free() {
+            return Some(composite);
+        }
+        Some(self.rewriting()?.normalize(composite))
+    }
+
+    /** Enumerates the morphisms of the presented category, if it is finite.
+
+    Equations are first completed into a confluent rewriting system, after
+    which the normal forms of paths are enumerated, in order of increasing
+    length, by extending normal forms one edge at a time. The enumeration
+    succeeds just when the presented category has at most `max_mors`
+    morphisms, including identities. On success, a finite category is returned
+    whose morphism generators are the non-identity normal forms, named using
+    the given function, which is assumed to be injective.
+
+    Returns `None` if the bound is exceeded or if completion fails, in which
+    case the presented category may be infinite.
+     */
+    pub fn to_fin_category<E2, T>(
+        &self,
+        max_mors: usize,
+        mut name: impl FnMut(&NonEmpty<E>) -> E2,
+    ) -> Option<FinCategory<V, E2, T>>
+    where
+        E2: Eq + Clone + Hash,
+        T: BuildHasher + Default,
+    {
+        let rewriting = self.rewriting()?;
+        let mut normal_forms: Vec<Path<V, E>> = self.generators.vertices().map(Path::Id).collect();
+        if normal_forms.len() > max_mors {
+            return None;
+        }
+        let mut i = 0;
+        while let Some(path) = normal_forms.get(i).cloned() {
+            let x = path.tgt(&self.generators);
+            for e in self.generators.out_edges(&x) {
+                let extended = match path.clone() {
+                    Path::Id(_) => Path::single(e),
+                    Path::Seq(mut edges) => {
+                        edges.push(e);
+                        Path::Seq(edges)
+                    }
+                };
+                if rewriting.is_normal(&extended) {
+                    if normal_forms.len() >= max_mors {
+                        return None;
+                    }
+                    normal_forms.push(extended);
+                }
+            }
+            i += 1;
+        }
+
+        let mut cat: FinCategory<V, E2, T> = Default::default();
+        cat.add_ob_generators(self.generators.vertices());
+        let mut names: HashColumn<Path<V, E>, FinMor<V, E2>> = Default::default();
+        for path in normal_forms.iter() {
+            let mor = match path {
+                Path::Id(x) => FinMor::Id(x.clone()),
+                Path::Seq(edges) => {
+                    let e = name(edges);
+                    let (dom, cod) = (path.src(&self.generators), path.tgt(&self.generators));
+                    assert!(
+                        cat.add_mor_generator(e.clone(), dom, cod),
+                        "Names of morphisms should be distinct"
+                    );
+                    FinMor::Generator(e)
+                }
+            };
+            names.set(path.clone(), mor);
+        }
+        for f in normal_forms.iter() {
+            let Some(FinMor::Generator(d)) = names.get(f) else {
+                continue;
+            };
+            let x = f.tgt(&self.generators);
+            for g in normal_forms.iter().filter(|g| g.src(&self.generators) == x) {
+                let Some(FinMor::Generator(e)) = names.get(g) else {
+                    continue;
+                };
+                let fg = f.clone().concat_in(&self.generators, g.clone()).unwrap();
+                let composite = names.apply(&rewriting.normalize(fg)).unwrap();
+                cat.set_composite(d.clone(), e.clone(), composite);
+            }
+        }
+        Some(cat)
+    }
+}
+
+impl<V, E, EqKey, S> FpCategory<V, E, EqKey, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    EqKey: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    /** Are the two paths equal as morphisms in the presented category?
+
+    Returns `None` if the word problem could not be solved because completion
+    of the equations did not terminate within a fixed bound. The completed
+    rewriting system is cached, so repeated queries are cheap. Since only
+    equality is decided, the morphism generators need not be ordered.
+     */
+    pub fn is_equal(&self, f: &Path<V, E>, g: &Path<V, E>) -> Option<bool> {
+        if self.is_free() {
+            return Some(f == g);
+        }
+        let rewriting = self.indexed_rewriting.get_or_init(|| {
+            IndexedRewriteSystem::complete(
+                &self.generators,
+                self.equations.values(),
+                MAX_COMPLETION_RULES,
+            )
+        });
+        Some(rewriting.as_ref()?.is_equal(f, g))
+    }
+}
+
 impl<V, E, EqKey, S> Validate for FpCategory<V, E, EqKey, S>
 where
     V: Eq + Clone + Hash,
@@ -423,6 +741,26 @@ where
     }
 }
 
+impl<V, E, EqKey, S> PresentedCategory for FpCategory<V, E, EqKey, S>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    EqKey: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    fn mor_path(&self, path: &Path<V, E>) -> Path<V, E> {
+        path.clone()
+    }
+
+    fn relations(&self) -> impl Iterator<Item = PathEq<V, E>> {
+        self.equations.values().cloned()
+    }
+
+    fn decide_eq(&self, path1: &Path<V, E>, path2: &Path<V, E>) -> Option<bool> {
+        self.is_equal(path1, path2)
+    }
+}
+
 /// A failure of a finite presentation of a category to be well defined.
 #[derive(Debug, Error)]
 pub enum InvalidFpCategory<E, EqKey> {
@@ -486,6 +824,38 @@ mod tests {
         assert_eq!(sch_sgraph.compose(path), Mor::Generator('t'));
     }
 
+    #[test]
+    fn fin_category_laws() {
+        type Mor<V, E> = FinMor<V, E>;
+
+        let mut sch_sgraph: FinCategory<char, char> = Default::default();
+        sch_sgraph.add_ob_generators(['V', 'E']);
+        sch_sgraph.add_mor_generator('s', 'E', 'V');
+        sch_sgraph.add_mor_generator('t', 'E', 'V');
+        sch_sgraph.add_mor_generator('i', 'E', 'E');
+        sch_sgraph.set_composite('i', 'i', Mor::Id('E'));
+        sch_sgraph.set_composite('i', 's', Mor::Generator('t'));
+        sch_sgraph.set_composite('i', 't', Mor::Generator('t'));
+        let errs: Vec<_> = sch_sgraph.iter_invalid().collect();
+        assert!(matches!(errs.as_slice(), [InvalidFinCategory::LeftUnit('i', 'i', 's')]));
+
+        // Non-associative magma on two elements: a*a = b, a*b = b*a = b*b = a.
+        let mut magma: FinCategory<char, char> = Default::default();
+        magma.add_ob_generator('x');
+        magma.add_mor_generator('a', 'x', 'x');
+        magma.add_mor_generator('b', 'x', 'x');
+        magma.set_composite('a', 'a', Mor::Generator('b'));
+        magma.set_composite('a', 'b', Mor::Generator('a'));
+        magma.set_composite('b', 'a', Mor::Generator('a'));
+        magma.set_composite('b', 'b', Mor::Generator('a'));
+        let errs = magma.validate().unwrap_err();
+        assert!(errs.iter().all(|err| matches!(err, InvalidFinCategory::Associativity(..))));
+        assert!(
+            errs.iter()
+                .any(|err| matches!(err, InvalidFinCategory::Associativity('a', 'a', 'b')))
+        );
+    }
+
     #[test]
     fn fp_category() {
         let mut sch_sgraph: FpCategory<_, _, _> = Default::default();
@@ -506,6 +876,54 @@ mod tests {
         assert!(!sch_sgraph.is_free());
         assert_eq!(sch_sgraph.equations().count(), 3);
         assert!(sch_sgraph.validate().is_ok());
+        assert_eq!(sch_sgraph.is_equal(&Path::pair('i', 't'), &Path::single('s')), Some(true));
+        assert_eq!(sch_sgraph.is_equal(&Path::single('s'), &Path::single('t')), Some(false));
+
+        // Modifying the presentation invalidates the cached rewriting system.
+        let mut sch_refl = sch_sgraph.clone();
+        sch_refl.add_equation("refl", PathEq::new(Path::single('s'), Path::single('t')));
+        assert_eq!(sch_refl.is_equal(&Path::single('s'), &Path::single('t')), Some(true));
+        assert_eq!(sch_sgraph.is_equal(&Path::single('s'), &Path::single('t')), Some(false));
+
+        let path = Path::Seq(nonempty![Path::pair('i', 'i'), Path::Id('E'), Path::pair('i', 's')]);
+        assert_eq!(sch_sgraph.compose_normal(path), Some(Path::single('t')));
+
+        let sch_sgraph_fin: FinCategory<_, _> = sch_sgraph
+            .to_fin_category(10, |edges| edges.iter().collect::<String>())
+            .unwrap();
+        assert_eq!(sch_sgraph_fin.mor_generators().count(), 3);
+        assert!(sch_sgraph_fin.validate().is_ok());
+        assert_eq!(
+            sch_sgraph_fin.compose2(FinMor::Generator("i".into()), FinMor::Generator("s".into())),
+            FinMor::Generator("t".into())
+        );
+        let result: Option<FinCategory<_, _>> =
+            sch_sgraph.to_fin_category(4, |edges| *edges.first());
+        assert!(result.is_none());
+
+        // Monoid of signs with delays, as in the theory of delayable signed
+        // categories.
+        let mut sgn_delay: FpCategory<_, _, _> = Default::default();
+        sgn_delay.add_ob_generator('x');
+        sgn_delay.add_mor_generator('n', 'x', 'x');
+        sgn_delay.add_mor_generator('d', 'x', 'x');
+        sgn_delay.add_equation(0, PathEq::new(Path::pair('n', 'n'), Path::empty('x')));
+        sgn_delay.add_equation(1, PathEq::new(Path::pair('d', 'd'), Path::single('d')));
+        sgn_delay.add_equation(2, PathEq::new(Path::pair('n', 'd'), Path::pair('d', 'n')));
+        let sgn_delay_fin: FinCategory<_, _> =
+            sgn_delay.to_fin_category(4, |edges| edges.iter().collect::<String>()).unwrap();
+        assert_eq!(sgn_delay_fin.mor_generators().count(), 3);
+        assert!(sgn_delay_fin.validate().is_ok());
+        assert_eq!(
+            sgn_delay_fin.compose2(FinMor::Generator("dn".into()), FinMor::Generator("n".into())),
+            FinMor::Generator("d".into())
+        );
+
+        let mut infinite: FpCategory<_, _, usize> = Default::default();
+        infinite.add_ob_generator('x');
+        infinite.add_mor_generator('f', 'x', 'x');
+        let result: Option<FinCategory<_, _>> = infinite.to_fin_category(100, |edges| edges.len());
+        assert!(result.is_none());
 
         let mut sch_bad: FpCategory<_, _, _> = Default::default();
         sch_bad.add_ob_generators(['x', 'y']);
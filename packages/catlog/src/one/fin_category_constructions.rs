@@ -0,0 +1,463 @@
+/*! Constructions of new finite categories from old ones.
+
+Many finite categories of interest are assembled from smaller ones. For
+instance, the monoid of signs with delays is the product of the group of signs
+with the monoid `{fast, slow}`, while the monoid of signs with zero is obtained
+from the group of signs by adjoining a zero element. This module computes such
+constructions on [finite categories](FinCategory), namely
+
+- [products](product), with their projection functors;
+- [coproducts](coproduct), with their injection functors;
+- [adjoining zero morphisms](adjoin_zero), with the inclusion functor.
+
+The labels of objects and morphisms in the constructed categories record how
+they arise from the original ones. They can be replaced with friendlier labels
+using [`FinCategory::relabel`]. The categories given as input are assumed to be
+valid; if they are not, the functions in this module may panic.
+ */
+
+use std::hash::{BuildHasher, Hash};
+
+use either::Either;
+
+use super::category::*;
+use super::fin_category::*;
+use super::functor::FinFunctor;
+
+/// Morphism generator in a product of finite categories.
+pub type ProductGen<V1, E1, V2, E2> = (FinMor<V1, E1>, FinMor<V2, E2>);
+
+/// Morphism in a product of finite categories.
+pub type ProductMor<V1, E1, V2, E2> = FinMor<(V1, V2), ProductGen<V1, E1, V2, E2>>;
+
+/// Product of two finite categories.
+pub type ProductCategory<V1, E1, V2, E2> = FinCategory<(V1, V2), ProductGen<V1, E1, V2, E2>>;
+
+/// Projection functor out of a product of finite categories.
+pub type ProductProj<V1, E1, V2, E2, V, E> =
+    FinFunctor<(V1, V2), ProductGen<V1, E1, V2, E2>, V, FinMor<V, E>>;
+
+/// Functor into a product of finite categories.
+pub type IntoProduct<V, E, V1, E1, V2, E2> = FinFunctor<V, E, (V1, V2), ProductMor<V1, E1, V2, E2>>;
+
+/// A product of two finite categories, with its projection functors.
+#[derive(Clone)]
+pub struct FinCategoryProduct<V1, E1, V2, E2> {
+    apex: ProductCategory<V1, E1, V2, E2>,
+    left: ProductProj<V1, E1, V2, E2, V1, E1>,
+    right: ProductProj<V1, E1, V2, E2, V2, E2>,
+}
+
+impl<V1, E1, V2, E2> FinCategoryProduct<V1, E1, V2, E2>
+where
+    V1: Eq + Clone + Hash,
+    E1: Eq + Clone + Hash,
+    V2: Eq + Clone + Hash,
+    E2: Eq + Clone + Hash,
+{
+    /// Gets the product category.
+    pub fn apex(&self) -> &ProductCategory<V1, E1, V2, E2> {
+        &self.apex
+    }
+
+    /// Gets the projection onto the first factor.
+    pub fn left(&self) -> &ProductProj<V1, E1, V2, E2, V1, E1> {
+        &self.left
+    }
+
+    /// Gets the projection onto the second factor.
+    pub fn right(&self) -> &ProductProj<V1, E1, V2, E2, V2, E2> {
+        &self.right
+    }
+
+    /** Computes the functor into the product induced by a pair of functors.
+
+    The functors `f` and `g` share the domain `dom`. The induced functor is
+    defined wherever both of them are.
+     */
+    pub fn universal<Dom>(
+        &self,
+        dom: &Dom,
+        f: &FinFunctor<Dom::Ob, Dom::MorGen, V1, FinMor<V1, E1>>,
+        g: &FinFunctor<Dom::Ob, Dom::MorGen, V2, FinMor<V2, E2>>,
+    ) -> IntoProduct<Dom::Ob, Dom::MorGen, V1, E1, V2, E2>
+    where
+        Dom: FgCategory,
+        Dom::Ob: Hash,
+        Dom::MorGen: Hash,
+    {
+        let ob_map = dom
+            .objects()
+            .filter_map(|x| Some((x.clone(), (f.apply_ob(&x)?, g.apply_ob(&x)?))));
+        let mor_map = dom.mor_generators().filter_map(|e| {
+            let pair = (f.apply_mor_generator(&e)?, g.apply_mor_generator(&e)?);
+            Some((e, product_mor(pair)))
+        });
+        FinFunctor::new(ob_map.collect(), mor_map.collect())
+    }
+}
+
+/** Computes the product of two finite categories.
+
+The morphism generators of the product are the pairs of morphisms that are not
+both identities.
+ */
+pub fn product<V1, E1, S1, V2, E2, S2>(
+    c: &FinCategory<V1, E1, S1>,
+    d: &FinCategory<V2, E2, S2>,
+) -> FinCategoryProduct<V1, E1, V2, E2>
+where
+    V1: Eq + Clone + Hash,
+    E1: Eq + Clone + Hash,
+    S1: BuildHasher,
+    V2: Eq + Clone + Hash,
+    E2: Eq + Clone + Hash,
+    S2: BuildHasher,
+{
+    let mut apex = ProductCategory::default();
+    for x in c.objects() {
+        apex.add_ob_generators(d.objects().map(|y| (x.clone(), y)));
+    }
+    let (mors1, mors2) = (all_morphisms(c), all_morphisms(d));
+    let gens: Vec<_> = mors1
+        .iter()
+        .flat_map(|f| mors2.iter().map(move |g| (f.clone(), g.clone())))
+        .filter(|(f, g)| !(matches!(f, FinMor::Id(_)) && matches!(g, FinMor::Id(_))))
+        .collect();
+    for (f, g) in &gens {
+        let (dom, cod) = ((c.dom(f), d.dom(g)), (c.cod(f), d.cod(g)));
+        apex.add_mor_generator((f.clone(), g.clone()), dom, cod);
+    }
+    for (f1, g1) in &gens {
+        for (f2, g2) in &gens {
+            if c.cod(f1) != c.dom(f2) || d.cod(g1) != d.dom(g2) {
+                continue;
+            }
+            let composite =
+                (c.compose2(f1.clone(), f2.clone()), d.compose2(g1.clone(), g2.clone()));
+            apex.set_composite(
+                (f1.clone(), g1.clone()),
+                (f2.clone(), g2.clone()),
+                product_mor(composite),
+            );
+        }
+    }
+
+    let left_obs = apex.objects().map(|(x, y)| ((x.clone(), y), x));
+    let right_obs = apex.objects().map(|(x, y)| ((x, y.clone()), y));
+    let left_mors = gens.iter().map(|(f, g)| ((f.clone(), g.clone()), f.clone()));
+    let right_mors = gens.iter().map(|(f, g)| ((f.clone(), g.clone()), g.clone()));
+    FinCategoryProduct {
+        left: FinFunctor::new(left_obs.collect(), left_mors.collect()),
+        right: FinFunctor::new(right_obs.collect(), right_mors.collect()),
+        apex,
+    }
+}
+
+/// Converts a pair of morphisms into a morphism in the product category.
+fn product_mor<V1, E1, V2, E2>(pair: ProductGen<V1, E1, V2, E2>) -> ProductMor<V1, E1, V2, E2> {
+    match pair {
+        (FinMor::Id(x), FinMor::Id(y)) => FinMor::Id((x, y)),
+        pair => FinMor::Generator(pair),
+    }
+}
+
+/// Lists all the morphisms in a finite category, identities first.
+fn all_morphisms<V, E, S>(cat: &FinCategory<V, E, S>) -> Vec<FinMor<V, E>>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    cat.objects().map(FinMor::Id).chain(cat.morphisms()).collect()
+}
+
+/// Coproduct of two finite categories.
+pub type CoproductCategory<V1, E1, V2, E2> = FinCategory<Either<V1, V2>, Either<E1, E2>>;
+
+/// Morphism in a coproduct of two finite categories.
+pub type CoproductMor<V1, E1, V2, E2> = FinMor<Either<V1, V2>, Either<E1, E2>>;
+
+/// Injection functor into a coproduct of finite categories.
+pub type CoproductInj<V1, E1, V2, E2, V, E> =
+    FinFunctor<V, E, Either<V1, V2>, CoproductMor<V1, E1, V2, E2>>;
+
+/// A coproduct of two finite categories, with its injection functors.
+#[derive(Clone)]
+pub struct FinCategoryCoproduct<V1, E1, V2, E2> {
+    apex: CoproductCategory<V1, E1, V2, E2>,
+    left: CoproductInj<V1, E1, V2, E2, V1, E1>,
+    right: CoproductInj<V1, E1, V2, E2, V2, E2>,
+}
+
+impl<V1, E1, V2, E2> FinCategoryCoproduct<V1, E1, V2, E2>
+where
+    V1: Eq + Clone + Hash,
+    E1: Eq + Clone + Hash,
+    V2: Eq + Clone + Hash,
+    E2: Eq + Clone + Hash,
+{
+    /// Gets the coproduct category.
+    pub fn apex(&self) -> &CoproductCategory<V1, E1, V2, E2> {
+        &self.apex
+    }
+
+    /// Gets the injection of the first summand.
+    pub fn left(&self) -> &CoproductInj<V1, E1, V2, E2, V1, E1> {
+        &self.left
+    }
+
+    /// Gets the injection of the second summand.
+    pub fn right(&self) -> &CoproductInj<V1, E1, V2, E2, V2, E2> {
+        &self.right
+    }
+
+    /** Computes the functor out of the coproduct induced by a pair of functors.
+
+    The functors `f` and `g` share a codomain. The induced functor is defined
+    wherever `f` or `g` is.
+     */
+    pub fn universal<CodOb, CodMor>(
+        &self,
+        f: &FinFunctor<V1, E1, CodOb, CodMor>,
+        g: &FinFunctor<V2, E2, CodOb, CodMor>,
+    ) -> FinFunctor<Either<V1, V2>, Either<E1, E2>, CodOb, CodMor>
+    where
+        CodOb: Eq + Clone,
+        CodMor: Eq + Clone,
+    {
+        let ob_map = self.apex.objects().filter_map(|x| {
+            let y = x.as_ref().either(|x| f.apply_ob(x), |x| g.apply_ob(x))?;
+            Some((x, y))
+        });
+        let mor_map = self.apex.mor_generators().filter_map(|e| {
+            let h =
+                e.as_ref().either(|e| f.apply_mor_generator(e), |e| g.apply_mor_generator(e))?;
+            Some((e, h))
+        });
+        FinFunctor::new(ob_map.collect(), mor_map.collect())
+    }
+}
+
+/// Computes the coproduct, or disjoint union, of two finite categories.
+pub fn coproduct<V1, E1, S1, V2, E2, S2>(
+    c: &FinCategory<V1, E1, S1>,
+    d: &FinCategory<V2, E2, S2>,
+) -> FinCategoryCoproduct<V1, E1, V2, E2>
+where
+    V1: Eq + Clone + Hash,
+    E1: Eq + Clone + Hash,
+    S1: BuildHasher,
+    V2: Eq + Clone + Hash,
+    E2: Eq + Clone + Hash,
+    S2: BuildHasher,
+{
+    let left = inject(c, Either::Left, Either::Left);
+    let right = inject(d, Either::Right, Either::Right);
+    let mut apex = c.relabel(|x| Either::Left(x.clone()), |e| Either::Left(e.clone()));
+    apex.add_ob_generators(d.objects().map(Either::Right));
+    for e in d.mor_generators() {
+        let (x, y) = (d.mor_generator_dom(&e), d.mor_generator_cod(&e));
+        apex.add_mor_generator(Either::Right(e), Either::Right(x), Either::Right(y));
+    }
+    for (d1, d2) in composable_pairs(d) {
+        let f = d.compose2(FinMor::Generator(d1.clone()), FinMor::Generator(d2.clone()));
+        apex.set_composite(
+            Either::Right(d1),
+            Either::Right(d2),
+            map_mor(f, Either::Right, Either::Right),
+        );
+    }
+    FinCategoryCoproduct { apex, left, right }
+}
+
+/// Generator of a finite category with zero morphisms adjoined.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum WithZero<V, E> {
+    /// Generator of the original category.
+    Generator(E),
+
+    /// Zero morphism between two objects.
+    Zero(V, V),
+}
+
+/// Finite category with zero morphisms adjoined.
+pub type ZeroCategory<V, E> = FinCategory<V, WithZero<V, E>>;
+
+/// A finite category with zero morphisms adjoined, with its inclusion functor.
+#[derive(Clone)]
+pub struct FinCategoryWithZero<V, E> {
+    apex: ZeroCategory<V, E>,
+    incl: FinFunctor<V, E, V, FinMor<V, WithZero<V, E>>>,
+}
+
+impl<V, E> FinCategoryWithZero<V, E>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+{
+    /// Gets the category with zero morphisms.
+    pub fn apex(&self) -> &ZeroCategory<V, E> {
+        &self.apex
+    }
+
+    /// Gets the inclusion of the original category.
+    pub fn incl(&self) -> &FinFunctor<V, E, V, FinMor<V, WithZero<V, E>>> {
+        &self.incl
+    }
+}
+
+/** Adjoins zero morphisms to a finite category.
+
+A zero morphism is adjoined between every pair of objects, including each
+object and itself. Zero morphisms are absorbing: the composite of a zero
+morphism with any other morphism is again a zero morphism. When the category
+has just one object, and so is a monoid, this adjoins a zero element to the
+monoid.
+ */
+pub fn adjoin_zero<V, E, S>(cat: &FinCategory<V, E, S>) -> FinCategoryWithZero<V, E>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    let incl = inject(cat, |x| x, WithZero::Generator);
+    let mut apex: ZeroCategory<V, E> =
+        cat.relabel(|x| x.clone(), |e| WithZero::Generator(e.clone()));
+    let obs: Vec<_> = cat.objects().collect();
+    for x in &obs {
+        for y in &obs {
+            apex.add_mor_generator(WithZero::Zero(x.clone(), y.clone()), x.clone(), y.clone());
+        }
+    }
+    for (f, g) in composable_pairs(&apex) {
+        if matches!(f, WithZero::Zero(..)) || matches!(g, WithZero::Zero(..)) {
+            let zero = WithZero::Zero(apex.mor_generator_dom(&f), apex.mor_generator_cod(&g));
+            apex.set_composite(f, g, FinMor::Generator(zero));
+        }
+    }
+    FinCategoryWithZero { apex, incl }
+}
+
+/// Constructs the functor relabeling a finite category.
+fn inject<V, E, S, W, F>(
+    cat: &FinCategory<V, E, S>,
+    ob: impl Fn(V) -> W,
+    mor: impl Fn(E) -> F,
+) -> FinFunctor<V, E, W, FinMor<W, F>>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+    W: Eq + Clone,
+    F: Eq + Clone,
+{
+    let ob_map = cat.objects().map(|x| (x.clone(), ob(x)));
+    let mor_map = cat.mor_generators().map(|e| (e.clone(), FinMor::Generator(mor(e))));
+    FinFunctor::new(ob_map.collect(), mor_map.collect())
+}
+
+/// Maps the labels of a morphism in a finite category.
+fn map_mor<V, E, W, F>(f: FinMor<V, E>, ob: impl Fn(V) -> W, mor: impl Fn(E) -> F) -> FinMor<W, F> {
+    match f {
+        FinMor::Id(x) => FinMor::Id(ob(x)),
+        FinMor::Generator(e) => FinMor::Generator(mor(e)),
+    }
+}
+
+/// Lists the pairs of composable generators in a finite category.
+fn composable_pairs<V, E, S>(cat: &FinCategory<V, E, S>) -> Vec<(E, E)>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+    S: BuildHasher,
+{
+    let gens: Vec<_> = cat.mor_generators().collect();
+    gens.iter()
+        .flat_map(|e1| gens.iter().map(move |e2| (e1.clone(), e2.clone())))
+        .filter(|(e1, e2)| cat.mor_generator_cod(e1) == cat.mor_generator_dom(e2))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::Validate;
+
+    fn signs() -> FinCategory<char, char> {
+        let mut sgn: FinCategory<char, char> = Default::default();
+        sgn.add_ob_generator('x');
+        sgn.add_mor_generator('n', 'x', 'x');
+        sgn.set_composite('n', 'n', FinMor::Id('x'));
+        sgn
+    }
+
+    fn delays() -> FinCategory<char, char> {
+        let mut delays: FinCategory<char, char> = Default::default();
+        delays.add_ob_generator('x');
+        delays.add_mor_generator('s', 'x', 'x');
+        delays.set_composite('s', 's', FinMor::Generator('s'));
+        delays
+    }
+
+    #[test]
+    fn product_categories() {
+        let (sgn, delays) = (signs(), delays());
+        let prod = product(&sgn, &delays);
+        let apex = prod.apex();
+        assert!(apex.validate().is_ok());
+        assert_eq!(apex.ob_generators().count(), 1);
+        assert_eq!(apex.mor_generators().count(), 3);
+        assert!(prod.left().validate_in(apex, &sgn).is_ok());
+        assert!(prod.right().validate_in(apex, &delays).is_ok());
+
+        let (n, s) = (FinMor::Generator('n'), FinMor::Generator('s'));
+        let neg_slow = FinMor::Generator((n.clone(), s.clone()));
+        let neg = FinMor::Generator((n, FinMor::Id('x')));
+        let pos_slow = FinMor::Generator((FinMor::Id('x'), s));
+        assert_eq!(apex.compose2(neg.clone(), neg_slow.clone()), pos_slow);
+        assert_eq!(apex.compose2(neg_slow.clone(), neg_slow), pos_slow);
+        assert_eq!(apex.compose2(neg.clone(), neg), FinMor::Id(('x', 'x')));
+
+        let pairing = prod.universal(apex, prod.left(), prod.right());
+        assert_eq!(pairing, FinFunctor::identity(apex));
+    }
+
+    #[test]
+    fn coproduct_categories() {
+        let (sgn, delays) = (signs(), delays());
+        let coprod = coproduct(&sgn, &delays);
+        let apex = coprod.apex();
+        assert!(apex.validate().is_ok());
+        assert_eq!(apex.ob_generators().count(), 2);
+        assert_eq!(apex.mor_generators().count(), 2);
+        assert!(coprod.left().validate_in(&sgn, apex).is_ok());
+        assert!(coprod.right().validate_in(&delays, apex).is_ok());
+
+        let copairing = coprod.universal(coprod.left(), coprod.right());
+        assert_eq!(copairing, FinFunctor::identity(apex));
+    }
+
+    #[test]
+    fn adjoin_zero_to_category() {
+        let sgn = signs();
+        let with_zero = adjoin_zero(&sgn);
+        let apex = with_zero.apex();
+        assert!(apex.validate().is_ok());
+        assert_eq!(apex.mor_generators().count(), 2);
+        assert!(with_zero.incl().validate_in(&sgn, apex).is_ok());
+
+        let (n, z) = (
+            FinMor::Generator(WithZero::Generator('n')),
+            FinMor::Generator(WithZero::Zero('x', 'x')),
+        );
+        assert_eq!(apex.compose2(n.clone(), n.clone()), FinMor::Id('x'));
+        assert_eq!(apex.compose2(n, z.clone()), z);
+
+        // Adjoining zero morphisms to a category with several objects.
+        let coprod = coproduct(&sgn, &delays());
+        let with_zero = adjoin_zero(coprod.apex());
+        assert!(with_zero.apex().validate().is_ok());
+        assert_eq!(with_zero.apex().mor_generators().count(), 6);
+    }
+}
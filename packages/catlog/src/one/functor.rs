@@ -0,0 +1,524 @@
+/*! Functors and natural transformations between finitely presented categories.
+
+A functor out of a [finitely presented](PresentedCategory) category is
+determined by its values on the object and morphism generators. Conversely, an
+assignment of values to generators defines a functor just when it respects the
+typing of the generators and preserves every relation in the presentation.
+Checking the latter requires deciding equality of morphisms in the codomain,
+which need not always be possible.
+ */
+
+use std::hash::Hash;
+
+use derivative::Derivative;
+use nonempty::NonEmpty;
+use thiserror::Error;
+
+use super::category::*;
+use super::path::*;
+use crate::validate;
+use crate::zero::{Column, HashColumn, Mapping, MutMapping};
+
+/** A functor out of a finitely presented category.
+
+The functor is defined by mappings on object generators and morphism
+generators. Morphism generators are sent to arbitrary morphisms in the
+codomain category. Like [graph mappings](super::GraphMapping), the data of the
+functor does not include its domain or codomain, which are instead supplied
+when applying the functor to general morphisms and when validating it.
+ */
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+#[derivative(PartialEq(bound = "DomOb: Eq + Hash, DomGen: Eq + Hash, \
+                                 CodOb: PartialEq, CodMor: PartialEq"))]
+pub struct FinFunctor<DomOb, DomGen, CodOb, CodMor> {
+    ob_map: HashColumn<DomOb, CodOb>,
+    mor_map: HashColumn<DomGen, CodMor>,
+}
+
+impl<DomOb, DomGen, CodOb, CodMor> FinFunctor<DomOb, DomGen, CodOb, CodMor>
+where
+    DomOb: Eq + Clone + Hash,
+    DomGen: Eq + Clone + Hash,
+    CodOb: Eq + Clone,
+    CodMor: Eq + Clone,
+{
+    /// Constructs a functor from existing mappings on generators.
+    pub fn new(ob_map: HashColumn<DomOb, CodOb>, mor_map: HashColumn<DomGen, CodMor>) -> Self {
+        Self { ob_map, mor_map }
+    }
+
+    /// Applies the functor to an object.
+    pub fn apply_ob(&self, x: &DomOb) -> Option<CodOb> {
+        self.ob_map.apply(x)
+    }
+
+    /// Applies the functor to a morphism generator.
+    pub fn apply_mor_generator(&self, e: &DomGen) -> Option<CodMor> {
+        self.mor_map.apply(e)
+    }
+
+    /// Is the functor defined at an object?
+    pub fn is_ob_assigned(&self, x: &DomOb) -> bool {
+        self.ob_map.is_set(x)
+    }
+
+    /// Is the functor defined at a morphism generator?
+    pub fn is_mor_generator_assigned(&self, e: &DomGen) -> bool {
+        self.mor_map.is_set(e)
+    }
+
+    /// Assigns the functor at an object, returning the previous assignment.
+    pub fn assign_ob(&mut self, x: DomOb, y: CodOb) -> Option<CodOb> {
+        self.ob_map.set(x, y)
+    }
+
+    /// Assigns the functor at a morphism generator, returning the previous
+    /// assignment.
+    pub fn assign_mor_generator(&mut self, e: DomGen, f: CodMor) -> Option<CodMor> {
+        self.mor_map.set(e, f)
+    }
+
+    /// Unassigns the functor at an object, returning the previous assignment.
+    pub fn unassign_ob(&mut self, x: &DomOb) -> Option<CodOb> {
+        self.ob_map.unset(x)
+    }
+
+    /// Unassigns the functor at a morphism generator, returning the previous
+    /// assignment.
+    pub fn unassign_mor_generator(&mut self, e: &DomGen) -> Option<CodMor> {
+        self.mor_map.unset(e)
+    }
+
+    /** Applies the functor to a path of morphism generators.
+
+    The images of the generators are composed in the codomain category, which
+    should be compatible with the functor.
+     */
+    pub fn apply_path<Cod>(&self, cod: &Cod, path: Path<DomOb, DomGen>) -> Option<CodMor>
+    where
+        Cod: Category<Ob = CodOb, Mor = CodMor>,
+    {
+        let path = path.partial_map(|x| self.apply_ob(&x), |e| self.apply_mor_generator(&e))?;
+        Some(cod.compose(path))
+    }
+
+    /// Applies the functor to a morphism in the domain category.
+    pub fn apply_mor<Dom, Cod>(&self, dom: &Dom, cod: &Cod, f: &Dom::Mor) -> Option<CodMor>
+    where
+        Dom: PresentedCategory<Ob = DomOb, MorGen = DomGen>,
+        Cod: Category<Ob = CodOb, Mor = CodMor>,
+    {
+        self.apply_path(cod, dom.mor_path(f))
+    }
+
+    /** Composes this functor with another functor.
+
+    The category `mid` is the codomain of this functor and the domain of the
+    other, while `cod` is the codomain of the other functor. The composite is
+    defined wherever both functors are.
+     */
+    pub fn compose<MidGen, Mid, Cod>(
+        &self,
+        other: &FinFunctor<CodOb, MidGen, Cod::Ob, Cod::Mor>,
+        mid: &Mid,
+        cod: &Cod,
+    ) -> FinFunctor<DomOb, DomGen, Cod::Ob, Cod::Mor>
+    where
+        CodOb: Hash,
+        MidGen: Eq + Clone + Hash,
+        Mid: PresentedCategory<Ob = CodOb, Mor = CodMor, MorGen = MidGen>,
+        Cod: Category,
+    {
+        let ob_map = self.ob_map.iter().filter_map(|(x, y)| Some((x, other.apply_ob(y)?)));
+        let mor_map = self
+            .mor_map
+            .iter()
+            .filter_map(|(e, f)| Some((e, other.apply_mor(mid, cod, f)?)));
+        FinFunctor::new(ob_map.collect(), mor_map.collect())
+    }
+
+    /** Validates that the functor is well defined between the given categories.
+
+    Assumes that the domain and codomain are valid. If they are not, this
+    function may panic.
+     */
+    pub fn validate_in<Dom, Cod>(
+        &self,
+        dom: &Dom,
+        cod: &Cod,
+    ) -> Result<(), NonEmpty<InvalidFinFunctor<DomOb, DomGen>>>
+    where
+        Dom: PresentedCategory<Ob = DomOb, MorGen = DomGen>,
+        Cod: PresentedCategory<Ob = CodOb, Mor = CodMor>,
+    {
+        validate::wrap_errors(self.iter_invalid_in(dom, cod))
+    }
+
+    /** Iterates over failures of the mapping to be a functor.
+
+    Equations are checked only when the generators are mapped correctly.
+     */
+    pub fn iter_invalid_in<Dom, Cod>(
+        &self,
+        dom: &Dom,
+        cod: &Cod,
+    ) -> impl Iterator<Item = InvalidFinFunctor<DomOb, DomGen>>
+    + use<Dom, Cod, DomOb, DomGen, CodOb, CodMor>
+    where
+        Dom: PresentedCategory<Ob = DomOb, MorGen = DomGen>,
+        Cod: PresentedCategory<Ob = CodOb, Mor = CodMor>,
+    {
+        let mut errs = Vec::new();
+        for x in dom.objects() {
+            if !self.apply_ob(&x).is_some_and(|y| cod.has_ob(&y)) {
+                errs.push(InvalidFinFunctor::Ob(x));
+            }
+        }
+        for e in dom.mor_generators() {
+            let Some(f) = self.apply_mor_generator(&e).filter(|f| cod.has_mor(f)) else {
+                errs.push(InvalidFinFunctor::Mor(e));
+                continue;
+            };
+            if self.apply_ob(&dom.mor_generator_dom(&e)).is_some_and(|x| x != cod.dom(&f)) {
+                errs.push(InvalidFinFunctor::Dom(e.clone()));
+            }
+            if self.apply_ob(&dom.mor_generator_cod(&e)).is_some_and(|y| y != cod.cod(&f)) {
+                errs.push(InvalidFinFunctor::Cod(e));
+            }
+        }
+        if errs.is_empty() {
+            for eq in dom.relations() {
+                let lhs = self.apply_path(cod, eq.lhs.clone()).unwrap();
+                let rhs = self.apply_path(cod, eq.rhs.clone()).unwrap();
+                match cod.decide_eq(&lhs, &rhs) {
+                    Some(true) => {}
+                    Some(false) => errs.push(InvalidFinFunctor::Equation(eq)),
+                    None => errs.push(InvalidFinFunctor::UndecidedEquation(eq)),
+                }
+            }
+        }
+        errs.into_iter()
+    }
+}
+
+impl<Ob, Gen, Mor> FinFunctor<Ob, Gen, Ob, Mor>
+where
+    Ob: Eq + Clone + Hash,
+    Gen: Eq + Clone + Hash,
+    Mor: Eq + Clone,
+{
+    /// Constructs the identity functor on a finitely generated category.
+    pub fn identity<Cat>(cat: &Cat) -> Self
+    where
+        Cat: FgCategory<Ob = Ob, Mor = Mor, MorGen = Gen>,
+        Gen: Into<Mor>,
+    {
+        let ob_map = cat.objects().map(|x| (x.clone(), x));
+        let mor_map = cat.mor_generators().map(|e| {
+            let f = e.clone().into();
+            (e, f)
+        });
+        Self::new(ob_map.collect(), mor_map.collect())
+    }
+}
+
+/// A failure of a mapping out of a finitely presented category to be a
+/// functor.
+#[derive(Debug, Error)]
+pub enum InvalidFinFunctor<V, E> {
+    /// An object not mapped to an object in the codomain.
+    #[error("Object `{0}` is not mapped to an object in the codomain")]
+    Ob(V),
+
+    /// A morphism generator not mapped to a morphism in the codomain.
+    #[error("Morphism generator `{0}` is not mapped to a morphism in the codomain")]
+    Mor(E),
+
+    /// A morphism generator whose domain is not preserved.
+    #[error("Functor does not preserve the domain of morphism generator `{0}`")]
+    Dom(E),
+
+    /// A morphism generator whose codomain is not preserved.
+    #[error("Functor does not preserve the codomain of morphism generator `{0}`")]
+    Cod(E),
+
+    /// A relation in the domain that does not hold in the codomain.
+    #[error("Functor does not preserve a path equation")]
+    Equation(PathEq<V, E>),
+
+    /// A relation in the domain that cannot be checked in the codomain.
+    #[error("Cannot decide whether functor preserves a path equation")]
+    UndecidedEquation(PathEq<V, E>),
+}
+
+/** A natural transformation between functors out of a finitely presented
+category.
+
+The transformation is defined by its components, which are morphisms in the
+codomain category indexed by objects in the domain category. As with
+[functors](FinFunctor), the domain and codomain categories, as well as the
+source and target functors, are supplied when validating.
+ */
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default(bound = ""))]
+#[derivative(PartialEq(bound = "DomOb: Eq + Hash, CodMor: PartialEq"))]
+pub struct FinNatTrans<DomOb, CodMor> {
+    components: HashColumn<DomOb, CodMor>,
+}
+
+impl<DomOb, CodMor> FinNatTrans<DomOb, CodMor>
+where
+    DomOb: Eq + Clone + Hash,
+    CodMor: Eq + Clone,
+{
+    /// Constructs a natural transformation from an existing mapping.
+    pub fn new(components: HashColumn<DomOb, CodMor>) -> Self {
+        Self { components }
+    }
+
+    /// Gets the component at an object.
+    pub fn component(&self, x: &DomOb) -> Option<CodMor> {
+        self.components.apply(x)
+    }
+
+    /// Assigns the component at an object, returning the previous assignment.
+    pub fn assign_component(&mut self, x: DomOb, f: CodMor) -> Option<CodMor> {
+        self.components.set(x, f)
+    }
+
+    /// Constructs the identity natural transformation on a functor.
+    pub fn identity<DomGen, Cod>(
+        functor: &FinFunctor<DomOb, DomGen, Cod::Ob, CodMor>,
+        cod: &Cod,
+    ) -> Self
+    where
+        DomGen: Eq + Clone + Hash,
+        Cod: Category<Mor = CodMor>,
+    {
+        let components = functor.ob_map.iter().map(|(x, y)| (x, cod.id(y.clone())));
+        Self::new(components.collect())
+    }
+
+    /** Vertically composes this transformation with another one.
+
+    The composite is defined at those objects where both transformations are.
+     */
+    pub fn compose<Cod>(&self, other: &Self, cod: &Cod) -> Self
+    where
+        Cod: Category<Mor = CodMor>,
+    {
+        let components = self.components.iter().filter_map(|(x, f)| {
+            let g = other.component(&x)?;
+            Some((x, cod.compose2(f.clone(), g)))
+        });
+        Self::new(components.collect())
+    }
+
+    /** Validates that the transformation is natural between the given functors.
+
+    Assumes that the categories and the functors are valid. If they are not,
+    this function may panic.
+     */
+    pub fn validate_in<DomGen, Dom, Cod>(
+        &self,
+        dom: &Dom,
+        cod: &Cod,
+        source: &FinFunctor<DomOb, DomGen, Cod::Ob, CodMor>,
+        target: &FinFunctor<DomOb, DomGen, Cod::Ob, CodMor>,
+    ) -> Result<(), NonEmpty<InvalidFinNatTrans<DomOb, DomGen>>>
+    where
+        DomGen: Eq + Clone + Hash,
+        Dom: PresentedCategory<Ob = DomOb, MorGen = DomGen>,
+        Cod: PresentedCategory<Mor = CodMor>,
+    {
+        validate::wrap_errors(self.iter_invalid_in(dom, cod, source, target))
+    }
+
+    /** Iterates over failures of the transformation to be natural.
+
+    Naturality is checked only when the components are correctly typed.
+     */
+    pub fn iter_invalid_in<DomGen, Dom, Cod>(
+        &self,
+        dom: &Dom,
+        cod: &Cod,
+        source: &FinFunctor<DomOb, DomGen, Cod::Ob, CodMor>,
+        target: &FinFunctor<DomOb, DomGen, Cod::Ob, CodMor>,
+    ) -> impl Iterator<Item = InvalidFinNatTrans<DomOb, DomGen>> + use<DomGen, Dom, Cod, DomOb, CodMor>
+    where
+        DomGen: Eq + Clone + Hash,
+        Dom: PresentedCategory<Ob = DomOb, MorGen = DomGen>,
+        Cod: PresentedCategory<Mor = CodMor>,
+    {
+        let mut errs = Vec::new();
+        for x in dom.objects() {
+            let Some(f) = self.component(&x).filter(|f| cod.has_mor(f)) else {
+                errs.push(InvalidFinNatTrans::Component(x));
+                continue;
+            };
+            if source.apply_ob(&x).is_none_or(|y| y != cod.dom(&f)) {
+                errs.push(InvalidFinNatTrans::ComponentDom(x.clone()));
+            }
+            if target.apply_ob(&x).is_none_or(|y| y != cod.cod(&f)) {
+                errs.push(InvalidFinNatTrans::ComponentCod(x));
+            }
+        }
+        if errs.is_empty() {
+            for e in dom.mor_generators() {
+                let (x, y) = (dom.mor_generator_dom(&e), dom.mor_generator_cod(&e));
+                let (Some(source_e), Some(target_e)) =
+                    (source.apply_mor_generator(&e), target.apply_mor_generator(&e))
+                else {
+                    continue;
+                };
+                let lhs = cod.compose2(source_e, self.component(&y).unwrap());
+                let rhs = cod.compose2(self.component(&x).unwrap(), target_e);
+                match cod.decide_eq(&lhs, &rhs) {
+                    Some(true) => {}
+                    Some(false) => errs.push(InvalidFinNatTrans::Naturality(e)),
+                    None => errs.push(InvalidFinNatTrans::UndecidedNaturality(e)),
+                }
+            }
+        }
+        errs.into_iter()
+    }
+}
+
+/// A failure of a transformation between functors to be natural.
+#[derive(Debug, Error)]
+pub enum InvalidFinNatTrans<V, E> {
+    /// An object at which the component is missing or not in the codomain.
+    #[error("Component at object `{0}` is not a morphism in the codomain")]
+    Component(V),
+
+    /// An object at which the component has the wrong domain.
+    #[error("Component at object `{0}` has domain incompatible with source functor")]
+    ComponentDom(V),
+
+    /// An object at which the component has the wrong codomain.
+    #[error("Component at object `{0}` has codomain incompatible with target functor")]
+    ComponentCod(V),
+
+    /// A morphism generator at which the naturality square does not commute.
+    #[error("Naturality square at morphism generator `{0}` does not commute")]
+    Naturality(E),
+
+    /// A morphism generator at which commutativity cannot be decided.
+    #[error("Cannot decide whether naturality square at morphism generator `{0}` commutes")]
+    UndecidedNaturality(E),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::one::fin_category::*;
+
+    type Functor<X, E> = FinFunctor<X, E, X, Path<X, E>>;
+
+    fn sch_graph() -> FpCategory<char, char, &'static str> {
+        let mut sch_graph: FpCategory<_, _, _> = Default::default();
+        sch_graph.add_ob_generators(['V', 'E']);
+        sch_graph.add_mor_generator('s', 'E', 'V');
+        sch_graph.add_mor_generator('t', 'E', 'V');
+        sch_graph
+    }
+
+    fn sch_sgraph() -> FpCategory<char, char, &'static str> {
+        let mut sch_sgraph = sch_graph();
+        sch_sgraph.add_mor_generator('i', 'E', 'E');
+        sch_sgraph.add_equation("inv", PathEq::new(Path::pair('i', 'i'), Path::empty('E')));
+        sch_sgraph.add_equation("rev_src", PathEq::new(Path::pair('i', 's'), Path::single('t')));
+        sch_sgraph.add_equation("rev_tgt", PathEq::new(Path::pair('i', 't'), Path::single('s')));
+        sch_sgraph
+    }
+
+    fn inclusion() -> Functor<char, char> {
+        let mut incl: Functor<_, _> = Default::default();
+        incl.assign_ob('V', 'V');
+        incl.assign_ob('E', 'E');
+        incl.assign_mor_generator('s', Path::single('s'));
+        incl.assign_mor_generator('t', Path::single('t'));
+        incl
+    }
+
+    #[test]
+    fn validate_functor() {
+        let (sch_graph, sch_sgraph) = (sch_graph(), sch_sgraph());
+        let incl = inclusion();
+        assert!(incl.validate_in(&sch_graph, &sch_sgraph).is_ok());
+        assert_eq!(
+            incl.apply_mor(&sch_graph, &sch_sgraph, &Path::single('t')),
+            Some(Path::single('t'))
+        );
+
+        // Collapsing the involution fails to preserve the equations.
+        let mut collapse: Functor<_, _> = FinFunctor::identity(&sch_graph);
+        collapse.assign_mor_generator('i', Path::empty('E'));
+        let errs = collapse.validate_in(&sch_sgraph, &sch_graph).unwrap_err();
+        assert_eq!(errs.len(), 2);
+        assert!(errs.iter().all(|err| matches!(err, InvalidFinFunctor::Equation(_))));
+
+        let mut bad: Functor<_, _> = FinFunctor::identity(&sch_graph);
+        bad.assign_mor_generator('s', Path::single('x'));
+        bad.unassign_ob(&'V');
+        let errs: Vec<_> = bad.iter_invalid_in(&sch_graph, &sch_sgraph).collect();
+        assert!(matches!(
+            errs.as_slice(),
+            [InvalidFinFunctor::Ob('V'), InvalidFinFunctor::Mor('s')]
+        ));
+    }
+
+    #[test]
+    fn compose_functors() {
+        let (sch_graph, sch_sgraph) = (sch_graph(), sch_sgraph());
+        let incl = inclusion();
+        let id: Functor<_, _> = FinFunctor::identity(&sch_sgraph);
+        assert!(id.validate_in(&sch_sgraph, &sch_sgraph).is_ok());
+        assert_eq!(incl.compose(&id, &sch_sgraph, &sch_sgraph), incl);
+
+        // Functor into the terminal category.
+        let mut terminal: FinCategory<char, char> = Default::default();
+        terminal.add_ob_generator('*');
+        let mut bang: FinFunctor<_, _, _, FinMor<_, _>> = Default::default();
+        bang.assign_ob('V', '*');
+        bang.assign_ob('E', '*');
+        for e in ['s', 't', 'i'] {
+            bang.assign_mor_generator(e, FinMor::Id('*'));
+        }
+        assert!(bang.validate_in(&sch_sgraph, &terminal).is_ok());
+        let composite = incl.compose(&bang, &sch_sgraph, &terminal);
+        assert!(composite.validate_in(&sch_graph, &terminal).is_ok());
+        assert_eq!(composite.apply_mor_generator(&'s'), Some(FinMor::Id('*')));
+        assert_eq!(composite.apply_mor_generator(&'i'), None);
+    }
+
+    #[test]
+    fn validate_nat_trans() {
+        let (sch_graph, sch_sgraph) = (sch_graph(), sch_sgraph());
+        let incl = inclusion();
+        let mut reverse = incl.clone();
+        reverse.assign_mor_generator('s', Path::single('t'));
+        reverse.assign_mor_generator('t', Path::single('s'));
+        assert!(reverse.validate_in(&sch_graph, &sch_sgraph).is_ok());
+
+        let mut alpha: FinNatTrans<_, _> = Default::default();
+        alpha.assign_component('V', Path::empty('V'));
+        alpha.assign_component('E', Path::single('i'));
+        assert!(alpha.validate_in(&sch_graph, &sch_sgraph, &incl, &reverse).is_ok());
+        let alpha2 = alpha.compose(&alpha, &sch_sgraph);
+        assert_eq!(alpha2.component(&'E'), Some(Path::pair('i', 'i')));
+        assert!(alpha2.validate_in(&sch_graph, &sch_sgraph, &incl, &incl).is_ok());
+
+        let id = FinNatTrans::identity(&incl, &sch_sgraph);
+        assert!(id.validate_in(&sch_graph, &sch_sgraph, &incl, &incl).is_ok());
+        let errs = id.validate_in(&sch_graph, &sch_sgraph, &incl, &reverse).unwrap_err();
+        assert_eq!(errs.len(), 2);
+        assert!(errs.iter().all(|err| matches!(err, InvalidFinNatTrans::Naturality(_))));
+
+        alpha.assign_component('E', Path::single('s'));
+        let errs: Vec<_> =
+            alpha.iter_invalid_in(&sch_graph, &sch_sgraph, &incl, &reverse).collect();
+        assert!(matches!(errs.as_slice(), [InvalidFinNatTrans::ComponentCod('E')]));
+    }
+}
@@ -1,6 +1,6 @@
 //! Algorithms on graphs.
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
 
 use super::graph::*;
@@ -168,6 +168,293 @@ where
     result
 }
 
+/** Computes the strongly connected components of a finite graph.
+
+Two vertices are in the same [strongly connected
+component](https://en.wikipedia.org/wiki/Strongly_connected_component) when
+each is reachable from the other by a path. The components are computed using
+[Tarjan's
+algorithm](https://en.wikipedia.org/wiki/Tarjan%27s_strongly_connected_components_algorithm)
+and are returned in topological order: whenever there is an edge from a vertex
+in one component to a vertex in a different component, the former component
+comes first.
+ */
+pub fn strongly_connected_components<G>(graph: &G) -> Vec<Vec<G::V>>
+where
+    G: FinGraph,
+    G::V: Hash,
+{
+    let adj = Adjacency::new(graph);
+    let mut components = adj.tarjan(|_| true);
+    components.reverse();
+    components
+        .into_iter()
+        .map(|comp| comp.into_iter().map(|v| adj.vertices[v].clone()).collect())
+        .collect()
+}
+
+/** Computes the condensation of a finite graph.
+
+The [condensation](https://en.wikipedia.org/wiki/Strongly_connected_component)
+of a graph is the acyclic graph obtained by contracting each strongly connected
+component to a single vertex. Returns the components, in the order of
+[`strongly_connected_components`], together with the condensation as a skeletal
+graph whose vertices are the indices of the components. The condensation has at
+most one edge between any two vertices and no loops.
+ */
+pub fn condensation<G>(graph: &G) -> (Vec<Vec<G::V>>, SkelGraph)
+where
+    G: FinGraph,
+    G::V: Hash,
+{
+    let components = strongly_connected_components(graph);
+    let component_of: HashMap<_, _> = components
+        .iter()
+        .enumerate()
+        .flat_map(|(i, comp)| comp.iter().map(move |v| (v.clone(), i)))
+        .collect();
+    let mut dag = SkelGraph::default();
+    dag.add_vertices(components.len());
+    let mut pairs = HashSet::new();
+    for e in graph.edges() {
+        let (i, j) = (component_of[&graph.src(&e)], component_of[&graph.tgt(&e)]);
+        if i != j && pairs.insert((i, j)) {
+            dag.add_edge(i, j);
+        }
+    }
+    (components, dag)
+}
+
+/** Sorts the vertices of a finite graph in topological order.
+
+In a [topological
+ordering](https://en.wikipedia.org/wiki/Topological_sorting), the source of
+every edge comes before its target. Such an ordering exists just when the graph
+is acyclic. If the graph has a cycle, one such cycle is returned as the error.
+ */
+#[allow(clippy::type_complexity)]
+pub fn toposort<G>(graph: &G) -> Result<Vec<G::V>, Path<G::V, G::E>>
+where
+    G: FinGraph,
+    G::V: Hash,
+{
+    let adj = Adjacency::new(graph);
+    let mut in_degree = vec![0; adj.len()];
+    for (_, w) in adj.out.iter().flatten() {
+        in_degree[*w] += 1;
+    }
+    let mut queue: VecDeque<_> = (0..adj.len()).filter(|v| in_degree[*v] == 0).collect();
+    let mut sorted = Vec::with_capacity(adj.len());
+    while let Some(v) = queue.pop_front() {
+        sorted.push(adj.vertices[v].clone());
+        for (_, w) in adj.out[v].iter() {
+            in_degree[*w] -= 1;
+            if in_degree[*w] == 0 {
+                queue.push_back(*w);
+            }
+        }
+    }
+    if sorted.len() == adj.len() {
+        return Ok(sorted);
+    }
+
+    // Every remaining vertex has an incoming edge from another remaining
+    // vertex, so walking backwards along such edges must eventually repeat.
+    let mut incoming = vec![None; adj.len()];
+    for (v, out) in adj.out.iter().enumerate().filter(|(v, _)| in_degree[*v] > 0) {
+        for (e, w) in out.iter() {
+            incoming[*w] = Some((e.clone(), v));
+        }
+    }
+    let mut v = (0..adj.len()).find(|v| in_degree[*v] > 0).unwrap();
+    let mut seen = HashSet::new();
+    while seen.insert(v) {
+        v = incoming[v].as_ref().expect("Remaining vertex should have an incoming edge").1;
+    }
+    let mut cycle = Vec::new();
+    let start = v;
+    loop {
+        let (e, u) = incoming[v].clone().unwrap();
+        cycle.push(e);
+        v = u;
+        if v == start {
+            break;
+        }
+    }
+    cycle.reverse();
+    Err(Path::collect(cycle).unwrap())
+}
+
+/** Enumerates all elementary cycles in a finite graph.
+
+An **elementary cycle** is a nonempty path whose source and target coincide and
+which otherwise visits no vertex more than once. Unlike the [simple
+cycles](simple_paths) at a given vertex, elementary cycles are enumerated up to
+rotation, so each appears only once, starting at its least vertex according to
+the order in which the graph iterates over vertices. Parallel edges give rise to
+distinct cycles.
+
+# References
+
+The cycles are enumerated using [Johnson's
+algorithm](https://doi.org/10.1137/0204007), which takes time linear in the
+size of the graph per cycle found.
+ */
+pub fn elementary_cycles<G>(graph: &G) -> Vec<Path<G::V, G::E>>
+where
+    G: FinGraph,
+    G::V: Hash,
+{
+    let adj = Adjacency::new(graph);
+    let mut cycles = Vec::new();
+    for start in 0..adj.len() {
+        let components = adj.tarjan(|v| v >= start);
+        let component = components.into_iter().find(|comp| comp.contains(&start)).unwrap();
+        let mut in_component = vec![false; adj.len()];
+        for v in component {
+            in_component[v] = true;
+        }
+        let mut search = Johnson {
+            adj: &adj,
+            start,
+            in_component,
+            blocked: vec![false; adj.len()],
+            blocking: vec![HashSet::new(); adj.len()],
+            path: Vec::new(),
+            cycles: &mut cycles,
+        };
+        search.circuit(start);
+    }
+    cycles
+}
+
+/// A finite graph as adjacency lists on the indices of its vertices.
+struct Adjacency<V, E> {
+    vertices: Vec<V>,
+    out: Vec<Vec<(E, usize)>>,
+}
+
+impl<V: Eq + Clone + Hash, E: Clone> Adjacency<V, E> {
+    fn new<G: FinGraph<V = V, E = E>>(graph: &G) -> Self {
+        let vertices: Vec<_> = graph.vertices().collect();
+        let index: HashMap<_, _> =
+            vertices.iter().cloned().enumerate().map(|(i, v)| (v, i)).collect();
+        let out = vertices
+            .iter()
+            .map(|v| graph.out_edges(v).map(|e| (e.clone(), index[&graph.tgt(&e)])).collect())
+            .collect();
+        Self { vertices, out }
+    }
+
+    fn len(&self) -> usize {
+        self.vertices.len()
+    }
+
+    /// Tarjan's algorithm on the subgraph induced by a set of vertices,
+    /// returning components in reverse topological order.
+    fn tarjan(&self, include: impl Fn(usize) -> bool) -> Vec<Vec<usize>> {
+        let n = self.len();
+        let (mut index, mut lowlink) = (vec![None; n], vec![0; n]);
+        let (mut stack, mut on_stack) = (Vec::new(), vec![false; n]);
+        let (mut components, mut next) = (Vec::new(), 0);
+        for root in (0..n).filter(|v| include(*v)) {
+            if index[root].is_some() {
+                continue;
+            }
+            let mut calls = vec![(root, 0)];
+            while let Some((v, pos)) = calls.last_mut() {
+                let v = *v;
+                if *pos == 0 && index[v].is_none() {
+                    index[v] = Some(next);
+                    lowlink[v] = next;
+                    next += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                }
+                if let Some((_, w)) = self.out[v].get(*pos) {
+                    *pos += 1;
+                    let w = *w;
+                    if !include(w) {
+                        continue;
+                    }
+                    match index[w] {
+                        None => calls.push((w, 0)),
+                        Some(i) if on_stack[w] => lowlink[v] = lowlink[v].min(i),
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+                calls.pop();
+                if let Some((u, _)) = calls.last() {
+                    lowlink[*u] = lowlink[*u].min(lowlink[v]);
+                }
+                if index[v] == Some(lowlink[v]) {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+/// State of Johnson's algorithm while searching for cycles through a vertex.
+struct Johnson<'a, V, E> {
+    adj: &'a Adjacency<V, E>,
+    start: usize,
+    in_component: Vec<bool>,
+    blocked: Vec<bool>,
+    blocking: Vec<HashSet<usize>>,
+    path: Vec<E>,
+    cycles: &'a mut Vec<Path<V, E>>,
+}
+
+impl<V: Clone, E: Clone> Johnson<'_, V, E> {
+    fn circuit(&mut self, v: usize) -> bool {
+        let adj = self.adj;
+        let mut found = false;
+        self.blocked[v] = true;
+        for (e, w) in adj.out[v].iter() {
+            if !self.in_component[*w] {
+                continue;
+            }
+            if *w == self.start {
+                let cycle = self.path.iter().cloned().chain(Some(e.clone()));
+                self.cycles.push(Path::collect(cycle).unwrap());
+                found = true;
+            } else if !self.blocked[*w] {
+                self.path.push(e.clone());
+                found |= self.circuit(*w);
+                self.path.pop();
+            }
+        }
+        if found {
+            self.unblock(v);
+        } else {
+            for (_, w) in adj.out[v].iter().filter(|(_, w)| self.in_component[*w]) {
+                self.blocking[*w].insert(v);
+            }
+        }
+        found
+    }
+
+    fn unblock(&mut self, v: usize) {
+        self.blocked[v] = false;
+        for w in std::mem::take(&mut self.blocking[v]) {
+            if self.blocked[w] {
+                self.unblock(w);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::GraphElem::*;
@@ -231,4 +518,65 @@ mod tests {
         let g = SkelGraph::cycle(1);
         assert_eq!(spec_order_all(&g), vec![Vertex(0), Edge(0)]);
     }
+
+    #[test]
+    fn components_and_toposort() {
+        let mut g = SkelGraph::cycle(3);
+        g.add_vertices(2);
+        g.add_edge(3, 0);
+        g.add_edge(2, 4);
+        g.add_edge(3, 4);
+        let mut components = strongly_connected_components(&g);
+        components.iter_mut().for_each(|comp| comp.sort());
+        assert_eq!(components, vec![vec![3], vec![0, 1, 2], vec![4]]);
+
+        let (components, dag) = condensation(&g);
+        assert_eq!(components.len(), 3);
+        assert_eq!((dag.vertex_count(), dag.edge_count()), (3, 3));
+        assert_eq!(toposort(&dag), Ok(vec![0, 1, 2]));
+
+        assert_eq!(toposort(&SkelGraph::path(3)), Ok(vec![0, 1, 2]));
+        let cycle = toposort(&g).unwrap_err();
+        assert_eq!(cycle.len(), 3);
+        assert!(cycle.contained_in(&g));
+        assert_eq!(cycle.src(&g), cycle.tgt(&g));
+        assert_eq!(toposort(&SkelGraph::cycle(1)), Err(Path::single(0)));
+    }
+
+    #[test]
+    fn find_elementary_cycles() {
+        assert!(elementary_cycles(&SkelGraph::path(4)).is_empty());
+        assert_eq!(elementary_cycles(&SkelGraph::cycle(3)), vec![Path::Seq(nonempty![0, 1, 2])]);
+
+        // Complete graph on three vertices, with loops at every vertex.
+        let mut g = SkelGraph::default();
+        g.add_vertices(3);
+        for v in 0..3 {
+            for w in 0..3 {
+                g.add_edge(v, w);
+            }
+        }
+        let cycles = elementary_cycles(&g);
+        assert_eq!(cycles.len(), 3 + 3 + 2);
+        assert!(cycles.iter().all(|path| path.src(&g) == path.tgt(&g)));
+        assert_eq!(cycles.iter().collect::<HashSet<_>>().len(), cycles.len());
+
+        let mut g: HashGraph<_, _> = Default::default();
+        g.add_vertices(['x', 'y']);
+        g.add_edge('f', 'x', 'x');
+        g.add_edge('g', 'x', 'y');
+        g.add_edge('h', 'x', 'y');
+        g.add_edge('k', 'y', 'x');
+        // The cycles start at whichever vertex the hash graph iterates first.
+        let cycles: HashSet<_> = elementary_cycles(&g)
+            .into_iter()
+            .map(|path| {
+                let mut edges: Vec<_> = path.iter().cloned().collect();
+                edges.sort();
+                edges
+            })
+            .collect();
+        let target = HashSet::from([vec!['f'], vec!['g', 'k'], vec!['h', 'k']]);
+        assert_eq!(cycles, target);
+    }
 }
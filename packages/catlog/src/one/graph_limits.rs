@@ -0,0 +1,532 @@
+/*! Finite limits and colimits of finite graphs.
+
+Limits and colimits of graphs are computed pointwise, on vertices and on edges,
+using the corresponding [constructions on finite sets](crate::zero::limits).
+The apex of each (co)limit is a [skeletal graph](SkelGraph) and its legs are
+[columnar graph mappings](ColumnarGraphMapping). The graph mappings in the
+diagram are assumed to be valid graph homomorphisms; if they are not, the
+functions in this module may panic.
+ */
+
+use std::hash::Hash;
+
+use super::graph::*;
+use crate::zero::limits::{self, *};
+use crate::zero::{Column, FinSet, HashColumn, Mapping, VecColumn};
+
+/// Columnar graph mapping out of a skeletal graph.
+pub type FromSkelGraph<V, E> = ColumnarGraphMapping<VecColumn<V>, VecColumn<E>>;
+
+/// Columnar graph mapping into a skeletal graph.
+pub type IntoSkelGraph<V, E> = ColumnarGraphMapping<HashColumn<V, usize>, HashColumn<E, usize>>;
+
+/// A limit of finite graphs with two legs, such as a product or pullback.
+#[derive(Clone)]
+pub struct GraphLimitSpan<XV, XE, YV, YE> {
+    apex: SkelGraph,
+    left: FromSkelGraph<XV, XE>,
+    right: FromSkelGraph<YV, YE>,
+    vertices: LimitSpan<XV, YV>,
+    edges: LimitSpan<XE, YE>,
+}
+
+impl<XV, XE, YV, YE> GraphLimitSpan<XV, XE, YV, YE>
+where
+    XV: Eq + Clone + Hash,
+    XE: Eq + Clone + Hash,
+    YV: Eq + Clone + Hash,
+    YE: Eq + Clone + Hash,
+{
+    fn new(
+        vertices: LimitSpan<XV, YV>,
+        edges: LimitSpan<XE, YE>,
+        src: impl Fn(&XE, &YE) -> (XV, YV),
+        tgt: impl Fn(&XE, &YE) -> (XV, YV),
+    ) -> Self {
+        let mut apex = SkelGraph::default();
+        apex.add_vertices(vertices.apex().len());
+        for (e1, e2) in std::iter::zip(edges.left().values(), edges.right().values()) {
+            let vertex = |(v1, v2): (XV, YV)| {
+                vertices
+                    .get(v1, v2)
+                    .expect("Graph mappings should preserve sources and targets")
+            };
+            apex.add_edge(vertex(src(e1, e2)), vertex(tgt(e1, e2)));
+        }
+        Self {
+            apex,
+            left: ColumnarGraphMapping::new(vertices.left().clone(), edges.left().clone()),
+            right: ColumnarGraphMapping::new(vertices.right().clone(), edges.right().clone()),
+            vertices,
+            edges,
+        }
+    }
+
+    /// Gets the apex of the limit.
+    pub fn apex(&self) -> &SkelGraph {
+        &self.apex
+    }
+
+    /// Gets the left leg of the limit.
+    pub fn left(&self) -> &FromSkelGraph<XV, XE> {
+        &self.left
+    }
+
+    /// Gets the right leg of the limit.
+    pub fn right(&self) -> &FromSkelGraph<YV, YE> {
+        &self.right
+    }
+
+    /** Computes the graph mapping into the limit induced by a cone.
+
+    The cone has apex `dom` and legs `f` and `g`. Returns `None` if the cone
+    does not commute with the diagram.
+     */
+    pub fn universal<W, F, G>(&self, dom: &W, f: &F, g: &G) -> Option<IntoSkelGraph<W::V, W::E>>
+    where
+        W: FinGraph,
+        W::V: Hash,
+        W::E: Hash,
+        F: GraphMapping<DomV = W::V, DomE = W::E, CodV = XV, CodE = XE>,
+        G: GraphMapping<DomV = W::V, DomE = W::E, CodV = YV, CodE = YE>,
+    {
+        let vertex_map = self.vertices.universal_by(
+            dom.vertices(),
+            |v| f.apply_vertex(v),
+            |v| g.apply_vertex(v),
+        )?;
+        let edge_map =
+            self.edges.universal_by(dom.edges(), |e| f.apply_edge(e), |e| g.apply_edge(e))?;
+        Some(ColumnarGraphMapping::new(vertex_map, edge_map))
+    }
+}
+
+/// A colimit of finite graphs with two legs, such as a coproduct or pushout.
+#[derive(Clone)]
+pub struct GraphColimitCospan<XV, XE, YV, YE> {
+    apex: SkelGraph,
+    left: IntoSkelGraph<XV, XE>,
+    right: IntoSkelGraph<YV, YE>,
+    vertices: ColimitCospan<XV, YV>,
+    edges: ColimitCospan<XE, YE>,
+}
+
+impl<XV, XE, YV, YE> GraphColimitCospan<XV, XE, YV, YE>
+where
+    XV: Eq + Clone + Hash,
+    XE: Eq + Clone + Hash,
+    YV: Eq + Clone + Hash,
+    YE: Eq + Clone + Hash,
+{
+    fn new<X, Y>(
+        x: &X,
+        y: &Y,
+        vertices: ColimitCospan<XV, YV>,
+        edges: ColimitCospan<XE, YE>,
+    ) -> Self
+    where
+        X: FinGraph<V = XV, E = XE>,
+        Y: FinGraph<V = YV, E = YE>,
+    {
+        let (left_v, right_v) = (vertices.left(), vertices.right());
+        let mut ends = vec![(0, 0); edges.apex().len()];
+        for (e, i) in edges.left().iter() {
+            ends[*i] = (left_v.apply(&x.src(&e)).unwrap(), left_v.apply(&x.tgt(&e)).unwrap());
+        }
+        for (e, i) in edges.right().iter() {
+            ends[*i] = (right_v.apply(&y.src(&e)).unwrap(), right_v.apply(&y.tgt(&e)).unwrap());
+        }
+        let mut apex = SkelGraph::default();
+        apex.add_vertices(vertices.apex().len());
+        for (src, tgt) in ends {
+            apex.add_edge(src, tgt);
+        }
+        Self {
+            apex,
+            left: ColumnarGraphMapping::new(vertices.left().clone(), edges.left().clone()),
+            right: ColumnarGraphMapping::new(vertices.right().clone(), edges.right().clone()),
+            vertices,
+            edges,
+        }
+    }
+
+    /// Gets the apex of the colimit.
+    pub fn apex(&self) -> &SkelGraph {
+        &self.apex
+    }
+
+    /// Gets the left leg of the colimit.
+    pub fn left(&self) -> &IntoSkelGraph<XV, XE> {
+        &self.left
+    }
+
+    /// Gets the right leg of the colimit.
+    pub fn right(&self) -> &IntoSkelGraph<YV, YE> {
+        &self.right
+    }
+
+    /** Computes the graph mapping out of the colimit induced by a cocone.
+
+    The cocone has legs `f` and `g` with a common codomain. Returns `None` if
+    the cocone does not commute with the diagram.
+     */
+    pub fn universal<F, G>(&self, f: &F, g: &G) -> Option<FromSkelGraph<F::CodV, F::CodE>>
+    where
+        F: GraphMapping<DomV = XV, DomE = XE>,
+        G: GraphMapping<DomV = YV, DomE = YE, CodV = F::CodV, CodE = F::CodE>,
+    {
+        let vertex_map =
+            self.vertices.universal_by(|v| f.apply_vertex(v), |v| g.apply_vertex(v))?;
+        let edge_map = self.edges.universal_by(|e| f.apply_edge(e), |e| g.apply_edge(e))?;
+        Some(ColumnarGraphMapping::new(vertex_map, edge_map))
+    }
+}
+
+/// An equalizer of two parallel graph homomorphisms.
+#[derive(Clone)]
+pub struct GraphEqualizer<V, E> {
+    apex: SkelGraph,
+    incl: FromSkelGraph<V, E>,
+    vertices: Equalizer<V>,
+    edges: Equalizer<E>,
+}
+
+impl<V, E> GraphEqualizer<V, E>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+{
+    /// Gets the apex of the equalizer.
+    pub fn apex(&self) -> &SkelGraph {
+        &self.apex
+    }
+
+    /// Gets the inclusion of the equalizer into the domain of the mappings.
+    pub fn incl(&self) -> &FromSkelGraph<V, E> {
+        &self.incl
+    }
+
+    /** Computes the graph mapping into the equalizer induced by a mapping that
+    equalizes.
+
+    Returns `None` if the mapping `h` does not in fact equalize.
+     */
+    pub fn universal<W, H>(&self, dom: &W, h: &H) -> Option<IntoSkelGraph<W::V, W::E>>
+    where
+        W: FinGraph,
+        W::V: Hash,
+        W::E: Hash,
+        H: GraphMapping<DomV = W::V, DomE = W::E, CodV = V, CodE = E>,
+    {
+        let vertex_map = self.vertices.universal_by(dom.vertices(), |v| h.apply_vertex(v))?;
+        let edge_map = self.edges.universal_by(dom.edges(), |e| h.apply_edge(e))?;
+        Some(ColumnarGraphMapping::new(vertex_map, edge_map))
+    }
+}
+
+/// A coequalizer of two parallel graph homomorphisms.
+#[derive(Clone)]
+pub struct GraphCoequalizer<V, E> {
+    apex: SkelGraph,
+    proj: IntoSkelGraph<V, E>,
+    vertices: Coequalizer<V>,
+    edges: Coequalizer<E>,
+}
+
+impl<V, E> GraphCoequalizer<V, E>
+where
+    V: Eq + Clone + Hash,
+    E: Eq + Clone + Hash,
+{
+    /// Gets the apex of the coequalizer.
+    pub fn apex(&self) -> &SkelGraph {
+        &self.apex
+    }
+
+    /// Gets the projection onto the coequalizer.
+    pub fn proj(&self) -> &IntoSkelGraph<V, E> {
+        &self.proj
+    }
+
+    /** Computes the graph mapping out of the coequalizer induced by a mapping
+    that coequalizes.
+
+    Returns `None` if the mapping `h` does not in fact coequalize.
+     */
+    pub fn universal<H>(&self, h: &H) -> Option<FromSkelGraph<H::CodV, H::CodE>>
+    where
+        H: GraphMapping<DomV = V, DomE = E>,
+    {
+        let vertex_map = self.vertices.universal_by(|v| h.apply_vertex(v))?;
+        let edge_map = self.edges.universal_by(|e| h.apply_edge(e))?;
+        Some(ColumnarGraphMapping::new(vertex_map, edge_map))
+    }
+}
+
+/// Computes the product of two finite graphs.
+pub fn product<X, Y>(x: &X, y: &Y) -> GraphLimitSpan<X::V, X::E, Y::V, Y::E>
+where
+    X: FinGraph,
+    X::V: Hash,
+    X::E: Hash,
+    Y: FinGraph,
+    Y::V: Hash,
+    Y::E: Hash,
+{
+    GraphLimitSpan::new(
+        limits::pullback_by(x.vertices(), y.vertices(), |_| Some(()), |_| Some(())),
+        limits::pullback_by(x.edges(), y.edges(), |_| Some(()), |_| Some(())),
+        |e1, e2| (x.src(e1), y.src(e2)),
+        |e1, e2| (x.tgt(e1), y.tgt(e2)),
+    )
+}
+
+/// Computes the pullback of two graph homomorphisms `f: X → Z` and `g: Y → Z`.
+pub fn pullback<X, Y, F, G>(x: &X, y: &Y, f: &F, g: &G) -> GraphLimitSpan<X::V, X::E, Y::V, Y::E>
+where
+    X: FinGraph,
+    X::V: Hash,
+    X::E: Hash,
+    Y: FinGraph,
+    Y::V: Hash,
+    Y::E: Hash,
+    F: GraphMapping<DomV = X::V, DomE = X::E>,
+    F::CodV: Hash,
+    F::CodE: Hash,
+    G: GraphMapping<DomV = Y::V, DomE = Y::E, CodV = F::CodV, CodE = F::CodE>,
+{
+    GraphLimitSpan::new(
+        limits::pullback_by(
+            x.vertices(),
+            y.vertices(),
+            |v| f.apply_vertex(v),
+            |v| g.apply_vertex(v),
+        ),
+        limits::pullback_by(x.edges(), y.edges(), |e| f.apply_edge(e), |e| g.apply_edge(e)),
+        |e1, e2| (x.src(e1), y.src(e2)),
+        |e1, e2| (x.tgt(e1), y.tgt(e2)),
+    )
+}
+
+/// Computes the equalizer of two parallel graph homomorphisms `f, g: X → Y`.
+pub fn equalizer<X, F, G>(x: &X, f: &F, g: &G) -> GraphEqualizer<X::V, X::E>
+where
+    X: FinGraph,
+    X::V: Hash,
+    X::E: Hash,
+    F: GraphMapping<DomV = X::V, DomE = X::E>,
+    G: GraphMapping<DomV = X::V, DomE = X::E, CodV = F::CodV, CodE = F::CodE>,
+{
+    let vertices = limits::equalizer_by(x.vertices(), |v| f.apply_vertex(v), |v| g.apply_vertex(v));
+    let edges = limits::equalizer_by(x.edges(), |e| f.apply_edge(e), |e| g.apply_edge(e));
+    let mut apex = SkelGraph::default();
+    apex.add_vertices(vertices.apex().len());
+    for e in edges.incl().values() {
+        let vertex =
+            |v| vertices.get(&v).expect("Graph mappings should preserve sources and targets");
+        apex.add_edge(vertex(x.src(e)), vertex(x.tgt(e)));
+    }
+    GraphEqualizer {
+        apex,
+        incl: ColumnarGraphMapping::new(vertices.incl().clone(), edges.incl().clone()),
+        vertices,
+        edges,
+    }
+}
+
+/// Computes the coproduct, or disjoint union, of two finite graphs.
+pub fn coproduct<X, Y>(x: &X, y: &Y) -> GraphColimitCospan<X::V, X::E, Y::V, Y::E>
+where
+    X: FinGraph,
+    X::V: Hash,
+    X::E: Hash,
+    Y: FinGraph,
+    Y::V: Hash,
+    Y::E: Hash,
+{
+    GraphColimitCospan::new(
+        x,
+        y,
+        limits::pushout_by(
+            x.vertices(),
+            y.vertices(),
+            std::iter::empty::<()>(),
+            |_| None,
+            |_| None,
+        ),
+        limits::pushout_by(x.edges(), y.edges(), std::iter::empty::<()>(), |_| None, |_| None),
+    )
+}
+
+/// Computes the pushout of two graph homomorphisms `f: Z → X` and `g: Z → Y`.
+pub fn pushout<X, Y, Z, F, G>(
+    x: &X,
+    y: &Y,
+    z: &Z,
+    f: &F,
+    g: &G,
+) -> GraphColimitCospan<X::V, X::E, Y::V, Y::E>
+where
+    X: FinGraph,
+    X::V: Hash,
+    X::E: Hash,
+    Y: FinGraph,
+    Y::V: Hash,
+    Y::E: Hash,
+    Z: FinGraph,
+    F: GraphMapping<DomV = Z::V, DomE = Z::E, CodV = X::V, CodE = X::E>,
+    G: GraphMapping<DomV = Z::V, DomE = Z::E, CodV = Y::V, CodE = Y::E>,
+{
+    GraphColimitCospan::new(
+        x,
+        y,
+        limits::pushout_by(
+            x.vertices(),
+            y.vertices(),
+            z.vertices(),
+            |v| f.apply_vertex(v),
+            |v| g.apply_vertex(v),
+        ),
+        limits::pushout_by(
+            x.edges(),
+            y.edges(),
+            z.edges(),
+            |e| f.apply_edge(e),
+            |e| g.apply_edge(e),
+        ),
+    )
+}
+
+/// Computes the coequalizer of two parallel graph homomorphisms `f, g: X → Y`.
+pub fn coequalizer<X, Y, F, G>(x: &X, y: &Y, f: &F, g: &G) -> GraphCoequalizer<Y::V, Y::E>
+where
+    X: FinGraph,
+    Y: FinGraph,
+    Y::V: Hash,
+    Y::E: Hash,
+    F: GraphMapping<DomV = X::V, DomE = X::E, CodV = Y::V, CodE = Y::E>,
+    G: GraphMapping<DomV = X::V, DomE = X::E, CodV = Y::V, CodE = Y::E>,
+{
+    let vertices = limits::coequalizer_by(
+        x.vertices(),
+        y.vertices(),
+        |v| f.apply_vertex(v),
+        |v| g.apply_vertex(v),
+    );
+    let edges =
+        limits::coequalizer_by(x.edges(), y.edges(), |e| f.apply_edge(e), |e| g.apply_edge(e));
+    let mut ends = vec![(0, 0); edges.apex().len()];
+    for (e, i) in edges.proj().iter() {
+        let vertex = |v| vertices.proj().apply(&v).unwrap();
+        ends[*i] = (vertex(y.src(&e)), vertex(y.tgt(&e)));
+    }
+    let mut apex = SkelGraph::default();
+    apex.add_vertices(vertices.apex().len());
+    for (src, tgt) in ends {
+        apex.add_edge(src, tgt);
+    }
+    GraphCoequalizer {
+        apex,
+        proj: ColumnarGraphMapping::new(vertices.proj().clone(), edges.proj().clone()),
+        vertices,
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate::Validate;
+
+    fn is_graph_morphism<Map, Dom, Cod>(f: &Map, dom: &Dom, cod: &Cod) -> bool
+    where
+        Map: GraphMapping,
+        Dom: FinGraph<V = Map::DomV, E = Map::DomE>,
+        Cod: Graph<V = Map::CodV, E = Map::CodE>,
+    {
+        GraphMorphism(f, dom, cod).validate().is_ok()
+    }
+
+    #[test]
+    fn graph_limits() {
+        let (x, y) = (SkelGraph::path(3), SkelGraph::triangle());
+        let prod = product(&x, &y);
+        assert_eq!(prod.apex().vertex_count(), 9);
+        assert_eq!(prod.apex().edge_count(), 6);
+        assert!(prod.apex().validate().is_ok());
+        assert!(is_graph_morphism(prod.left(), prod.apex(), &x));
+        assert!(is_graph_morphism(prod.right(), prod.apex(), &y));
+
+        // Pair the identity on the path with its inclusion into the triangle.
+        let pair = prod.universal(
+            &x,
+            &identity(&x),
+            &ColumnarGraphMapping::new(VecColumn::new(vec![0, 1, 2]), VecColumn::new(vec![0, 1])),
+        );
+        assert!(pair.is_some_and(|pair| is_graph_morphism(&pair, &x, prod.apex())));
+
+        // Pulling back the edge `0 → 1` of the triangle along itself.
+        let z = SkelGraph::path(2);
+        let incl = ColumnarGraphMapping::new(VecColumn::new(vec![0, 1]), VecColumn::new(vec![0]));
+        let pb = pullback(&z, &y, &incl, &identity(&y));
+        assert_eq!(pb.apex().vertex_count(), 2);
+        assert_eq!(pb.apex().edge_count(), 1);
+        assert!(is_graph_morphism(pb.right(), pb.apex(), &y));
+
+        // Equalizing the two inclusions of a vertex into an edge.
+        let w = SkelGraph::path(1);
+        let (f, g) = (
+            ColumnarGraphMapping::new(VecColumn::new(vec![0]), VecColumn::<usize>::default()),
+            ColumnarGraphMapping::new(VecColumn::new(vec![1]), VecColumn::<usize>::default()),
+        );
+        let eq = equalizer(&w, &f, &g);
+        assert_eq!(eq.apex().vertex_count(), 0);
+        assert!(eq.universal(&w, &identity(&w)).is_none());
+        let eq = equalizer(&w, &f, &f);
+        assert_eq!(eq.apex().vertex_count(), 1);
+        assert!(eq.universal(&w, &identity(&w)).is_some());
+    }
+
+    #[test]
+    fn graph_colimits() {
+        let (x, y) = (SkelGraph::path(2), SkelGraph::triangle());
+        let coprod = coproduct(&x, &y);
+        assert_eq!(coprod.apex().vertex_count(), 5);
+        assert_eq!(coprod.apex().edge_count(), 4);
+        assert!(is_graph_morphism(coprod.left(), &x, coprod.apex()));
+        assert!(is_graph_morphism(coprod.right(), &y, coprod.apex()));
+
+        // Glue the ends of a path together to form a cycle.
+        let (z, x) = (SkelGraph::path(1), SkelGraph::path(3));
+        let (f, g) = (
+            ColumnarGraphMapping::new(VecColumn::new(vec![0]), VecColumn::<usize>::default()),
+            ColumnarGraphMapping::new(VecColumn::new(vec![2]), VecColumn::<usize>::default()),
+        );
+        let coeq = coequalizer(&z, &x, &f, &g);
+        assert_eq!(coeq.apex().vertex_count(), 2);
+        assert_eq!(coeq.apex().edge_count(), 2);
+        assert!(is_graph_morphism(coeq.proj(), &x, coeq.apex()));
+        let cycle = SkelGraph::cycle(2);
+        let h =
+            ColumnarGraphMapping::new(VecColumn::new(vec![0, 1, 0]), VecColumn::new(vec![0, 1]));
+        let map = coeq.universal(&h).unwrap();
+        assert!(is_graph_morphism(&map, coeq.apex(), &cycle));
+        assert!(coeq.universal(&identity(&x)).is_none());
+
+        // Glue two edges along their targets.
+        let y = SkelGraph::path(2);
+        let g = ColumnarGraphMapping::new(VecColumn::new(vec![1]), VecColumn::<usize>::default());
+        let tgt = ColumnarGraphMapping::new(VecColumn::new(vec![1]), VecColumn::<usize>::default());
+        let po = pushout(&y, &y, &z, &g, &tgt);
+        assert_eq!(po.apex().vertex_count(), 3);
+        assert_eq!(po.apex().edge_count(), 2);
+        assert_eq!(po.apex().in_degree(&po.left().apply_vertex(&1).unwrap()), 2);
+        assert!(po.universal(&identity(&y), &identity(&y)).is_some());
+    }
+
+    fn identity(x: &SkelGraph) -> ColumnarGraphMapping<VecColumn<usize>, VecColumn<usize>> {
+        let vertices = VecColumn::new(x.vertex_set().iter().collect());
+        let edges = VecColumn::new(x.edge_set().iter().collect());
+        ColumnarGraphMapping::new(vertices, edges)
+    }
+}
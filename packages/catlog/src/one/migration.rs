@@ -0,0 +1,710 @@
+/*! Functorial data migration between C-sets.
+
+A functor `F: C → D` between schemas induces three functors between the
+categories of [C-sets](super::cset) on them:
+
+- *Pullback* or *delta migration*, `Δ_F`, sends a `D`-set `X` to the `C`-set
+  `X ∘ F`. It is the simplest migration: tables are copied and renamed, and a
+  table of `D` can be duplicated by sending several tables of `C` to it.
+- *Left pushforward* or *sigma migration*, `Σ_F`, is left adjoint to `Δ_F`. The
+  table of `Σ_F X` at an object `d` is the colimit of `X` over the comma
+  category `F ↓ d`, so it glues together the rows of `X` that are identified by
+  `F`.
+- *Right pushforward* or *pi migration*, `Π_F`, is right adjoint to `Δ_F`. The
+  table of `Π_F X` at an object `d` is the limit of `X` over the comma category
+  `d ↓ F`, so it consists of compatible families of rows of `X`.
+
+Computing the left and right pushforwards requires enumerating morphisms in the
+codomain schema, which is possible only when the relevant hom-sets are finite
+and their equality is decidable. Both migrations therefore take a bound on the
+number of morphisms to enumerate and return `None` when it is exceeded or when
+equality of morphisms cannot be decided.
+
+Attribute types are supported, with some restrictions: the functor must send
+attribute types to attribute types and other objects to other objects. Attribute
+values are never created by migration, only transported along with the rows
+that determine them. In the left pushforward, an attribute of a row is set when
+it is the image of an attribute generator at one of the rows glued into it and
+is otherwise left unset.
+ */
+
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use std::sync::Arc;
+
+use super::category::*;
+use super::cset::*;
+use super::functor::FinFunctor;
+use crate::zero::UnionFind;
+
+/** Pulls back a C-set along a functor of schemas.
+
+Given a functor `F: C → D` and a `D`-set `X`, this computes the `C`-set
+`Δ_F X = X ∘ F`. An object of `C` is an attribute type just when its image under
+`F` is. The functor is assumed to be valid and defined on all of `C`.
+ */
+pub fn delta<C, D, T>(
+    functor: &FinFunctor<C::Ob, C::MorGen, D::Ob, D::Mor>,
+    schema: Arc<C>,
+    data: &CSet<D, T>,
+) -> CSet<C, T>
+where
+    C: PresentedCategory,
+    C::Ob: Hash,
+    C::MorGen: Hash,
+    D: PresentedCategory,
+    D::Ob: Hash,
+    D::MorGen: Hash,
+    T: Eq + Hash + Clone,
+{
+    let ob_map = |x: &C::Ob| functor.apply_ob(x).expect("Functor should be defined on objects");
+    let attr_types: Vec<_> = schema.objects().filter(|x| data.is_attr_type(&ob_map(x))).collect();
+    let mut result = CSet::new(schema.clone(), attr_types);
+    for x in schema.objects() {
+        if !result.is_attr_type(&x) {
+            result.add_parts(&x, data.nparts(&ob_map(&x)));
+        }
+    }
+    for e in schema.mor_generators() {
+        let x = schema.mor_generator_dom(&e);
+        if result.is_attr_type(&x) {
+            continue;
+        }
+        let f = functor
+            .apply_mor_generator(&e)
+            .expect("Functor should be defined on morphism generators");
+        let path = data.schema().mor_path(&f);
+        for i in result.parts(&x) {
+            match data.eval_path(&path, i) {
+                Some(CSetElem::Part(j)) => {
+                    result.set_subpart(&e, i, j);
+                }
+                Some(CSetElem::Value(value)) => {
+                    result.set_attr(&e, i, value);
+                }
+                None => {}
+            }
+        }
+    }
+    result
+}
+
+/** Pushes forward a C-set along a functor of schemas using colimits.
+
+Given a functor `F: C → D` and a `C`-set `X`, this computes the `D`-set
+`Σ_F X`. The attribute types of the result are the images of those of `X`. The
+morphisms in `D` out of the image of each table of `C` are enumerated, up to the
+given maximum number per table.
+ */
+pub fn sigma<C, D, T>(
+    functor: &FinFunctor<C::Ob, C::MorGen, D::Ob, D::Mor>,
+    data: &CSet<C, T>,
+    schema: Arc<D>,
+    max_mors: usize,
+) -> Option<CSet<D, T>>
+where
+    C: PresentedCategory,
+    C::Ob: Hash,
+    C::MorGen: Hash,
+    D: PresentedCategory,
+    D::Ob: Hash,
+    D::MorGen: Hash,
+    T: Eq + Hash + Clone,
+{
+    let (dom, cod) = (data.schema(), schema.as_ref());
+    let ob_map = |x: &C::Ob| functor.apply_ob(x).expect("Functor should be defined on objects");
+    let mor_map = |e: &C::MorGen| {
+        functor
+            .apply_mor_generator(e)
+            .expect("Functor should be defined on morphism generators")
+    };
+    let attr_types: HashSet<_> =
+        dom.objects().filter(|x| data.is_attr_type(x)).map(|x| ob_map(&x)).collect();
+    let out_gens = out_generators(cod);
+
+    // The elements of the comma categories `F ↓ d` at a row of `X` are the
+    // morphisms out of the image of its table, enumerated once per table.
+    let tables: Vec<_> = dom.objects().filter(|x| !data.is_attr_type(x)).collect();
+    let (mut homs, mut offsets, mut nelems) = (HashMap::new(), HashMap::new(), 0);
+    for x in tables.iter() {
+        let y = ob_map(x);
+        assert!(!attr_types.contains(&y), "Functor should not send tables to attribute types");
+        let fs = homs_out(cod, &out_gens, &attr_types, y, max_mors)?;
+        offsets.insert(x.clone(), nelems);
+        nelems += data.nparts(x) * fs.len();
+        homs.insert(x.clone(), fs);
+    }
+    let elem = |x: &C::Ob, i: usize, k: usize| offsets[x] + i * homs[x].len() + k;
+
+    // Glue the element at `(x, i, F(e) · f)` to the element at `(x', e(i), f)`
+    // for every foreign key `e: x → x'`.
+    let mut classes = UnionFind::new(nelems);
+    for e in dom.mor_generators() {
+        let (x, x2) = (dom.mor_generator_dom(&e), dom.mor_generator_cod(&e));
+        if data.is_attr_type(&x) || data.is_attr_type(&x2) {
+            continue;
+        }
+        let fe = mor_map(&e);
+        for (k2, f) in homs[&x2].iter().enumerate() {
+            let k = position(cod, &homs[&x], &cod.compose2(fe.clone(), f.clone()))?
+                .expect("Composite should be enumerated");
+            for i in data.parts(&x) {
+                if let Some(i2) = data.subpart(&e, i) {
+                    classes.union(elem(&x, i, k), elem(&x2, i2, k2));
+                }
+            }
+        }
+    }
+
+    // Number the equivalence classes of elements, which are the rows of the
+    // tables of the result.
+    let mut roots: HashMap<D::Ob, Vec<usize>> = HashMap::new();
+    let mut members: HashMap<usize, Vec<(C::Ob, usize, usize)>> = HashMap::new();
+    for x in tables.iter() {
+        for (k, f) in homs[x].iter().enumerate() {
+            let y = cod.cod(f);
+            if attr_types.contains(&y) {
+                continue;
+            }
+            for i in data.parts(x) {
+                let root = classes.find(elem(x, i, k));
+                let class = members.entry(root).or_insert_with(|| {
+                    roots.entry(y.clone()).or_default().push(root);
+                    Vec::new()
+                });
+                class.push((x.clone(), i, k));
+            }
+        }
+    }
+    let mut result = CSet::new(schema.clone(), attr_types.iter().cloned());
+    let mut part_of = HashMap::new();
+    for (y, roots) in roots.iter() {
+        result.add_parts(y, roots.len());
+        part_of.extend(roots.iter().enumerate().map(|(part, root)| (*root, part)));
+    }
+
+    let attr_gens = attr_generators(data);
+    for h in cod.mor_generators() {
+        let (y, y2) = (cod.mor_generator_dom(&h), cod.mor_generator_cod(&h));
+        let Some(roots) = roots.get(&y) else {
+            continue;
+        };
+        for (part, root) in roots.iter().enumerate() {
+            let class = &members[root];
+            if !attr_types.contains(&y2) {
+                let (x, i, k) = &class[0];
+                let f = cod.compose2(homs[x][*k].clone(), h.clone().into());
+                let k2 = position(cod, &homs[x], &f)?.expect("Composite should be enumerated");
+                result.set_subpart(&h, part, part_of[&classes.find(elem(x, *i, k2))]);
+                continue;
+            }
+            'members: for (x, i, k) in class.iter() {
+                let f = cod.compose2(homs[x][*k].clone(), h.clone().into());
+                for e in attr_gens.get(x).into_iter().flatten() {
+                    if !cod.decide_eq(&f, &mor_map(e))? {
+                        continue;
+                    }
+                    if let Some(value) = data.attr(e, *i) {
+                        result.set_attr(&h, part, value);
+                        break 'members;
+                    }
+                }
+            }
+        }
+    }
+    Some(result)
+}
+
+/** Pushes forward a C-set along a functor of schemas using limits.
+
+Given a functor `F: C → D` and a `C`-set `X`, this computes the `D`-set
+`Π_F X`. The attribute types of the result are the images of those of `X`. The
+morphisms in `D` out of each object are enumerated, up to the given maximum
+number per object. Besides the failure modes shared with [`sigma`], this
+function returns `None` if the value of an attribute in the result is not
+determined by the rows of `X`, since such a table would have a row for every
+possible attribute value.
+
+The rows of each table are found by a backtracking search and so the
+computation can be expensive when the comma categories are large.
+ */
+pub fn pi<C, D, T>(
+    functor: &FinFunctor<C::Ob, C::MorGen, D::Ob, D::Mor>,
+    data: &CSet<C, T>,
+    schema: Arc<D>,
+    max_mors: usize,
+) -> Option<CSet<D, T>>
+where
+    C: PresentedCategory,
+    C::Ob: Hash,
+    C::MorGen: Hash,
+    D: PresentedCategory,
+    D::Ob: Hash,
+    D::MorGen: Hash,
+    T: Eq + Hash + Clone,
+{
+    let (dom, cod) = (data.schema(), schema.as_ref());
+    let ob_map = |x: &C::Ob| functor.apply_ob(x).expect("Functor should be defined on objects");
+    let attr_types: HashSet<_> =
+        dom.objects().filter(|x| data.is_attr_type(x)).map(|x| ob_map(&x)).collect();
+    assert!(
+        dom.objects()
+            .all(|x| data.is_attr_type(&x) || !attr_types.contains(&ob_map(&x))),
+        "Functor should not send tables to attribute types"
+    );
+    let out_gens = out_generators(cod);
+
+    let mut result = CSet::new(schema.clone(), attr_types.iter().cloned());
+    let mut limits = HashMap::new();
+    for y in cod.objects().filter(|y| !attr_types.contains(y)) {
+        let homs = homs_out(cod, &out_gens, &attr_types, y.clone(), max_mors)?;
+        let limit = Limit::compute(functor, data, cod, &homs)?;
+        result.add_parts(&y, limit.families.len());
+        limits.insert(y, (homs, limit));
+    }
+
+    for h in cod.mor_generators() {
+        let (y, y2) = (cod.mor_generator_dom(&h), cod.mor_generator_cod(&h));
+        let Some((homs, limit)) = limits.get(&y) else {
+            continue;
+        };
+        if attr_types.contains(&y2) {
+            let k =
+                position(cod, homs, &h.clone().into())?.expect("Generator should be enumerated");
+            let Some(node) = limit.attr_node(dom, data, k) else {
+                continue;
+            };
+            for (part, values) in limit.values.iter().enumerate() {
+                result.set_attr(&h, part, values[node - limit.ntables].clone());
+            }
+            continue;
+        }
+        // The family at `(x, f)` in the image is the family at `(x, h · f)`.
+        let (homs2, limit2) = &limits[&y2];
+        let mut reindex = Vec::with_capacity(limit2.ntables);
+        for (x, k2) in limit2.nodes[..limit2.ntables].iter() {
+            let f = cod.compose2(h.clone().into(), homs2[*k2].clone());
+            let k = position(cod, homs, &f)?.expect("Composite should be enumerated");
+            reindex.push(limit.index[&(x.clone(), k)]);
+        }
+        for (part, family) in limit.families.iter().enumerate() {
+            let image: Vec<_> = reindex.iter().map(|node| family[*node]).collect();
+            result.set_subpart(&h, part, limit2.family_index[&image]);
+        }
+    }
+    Some(result)
+}
+
+/** The limit of a C-set over a comma category `d ↓ F`.
+
+The nodes of the comma category are pairs of an object `x` of `C` and a
+morphism `d → F(x)`, the latter given by its index in an enumeration of the
+morphisms out of `d`. Nodes at tables precede those at attribute types. A
+family assigns a row to each node at a table, and determines a value at each
+node at an attribute type.
+ */
+struct Limit<Ob, Gen, T> {
+    nodes: Vec<(Ob, usize)>,
+    index: HashMap<(Ob, usize), usize>,
+    ntables: usize,
+    edges: Vec<(usize, Gen, usize)>,
+    families: Vec<Vec<usize>>,
+    family_index: HashMap<Vec<usize>, usize>,
+    values: Vec<Vec<T>>,
+}
+
+impl<Ob, Gen, T> Limit<Ob, Gen, T>
+where
+    Ob: Eq + Clone + Hash,
+    Gen: Eq + Clone + Hash,
+    T: Eq + Hash + Clone,
+{
+    fn compute<C, D>(
+        functor: &FinFunctor<Ob, Gen, D::Ob, D::Mor>,
+        data: &CSet<C, T>,
+        cod: &D,
+        homs: &[D::Mor],
+    ) -> Option<Self>
+    where
+        C: PresentedCategory<Ob = Ob, MorGen = Gen>,
+        D: PresentedCategory,
+        D::Ob: Hash,
+        D::MorGen: Hash,
+    {
+        let dom = data.schema();
+        let ob_map = |x: &Ob| functor.apply_ob(x).expect("Functor should be defined on objects");
+        let (tables, attr_types): (Vec<_>, Vec<_>) =
+            dom.objects().partition(|x| !data.is_attr_type(x));
+        let mut nodes = Vec::new();
+        for x in tables.iter().chain(attr_types.iter()) {
+            let y = ob_map(x);
+            nodes.extend(
+                homs.iter()
+                    .enumerate()
+                    .filter(|(_, f)| cod.cod(f) == y)
+                    .map(|(k, _)| (x.clone(), k)),
+            );
+        }
+        let ntables = nodes.iter().take_while(|(x, _)| !data.is_attr_type(x)).count();
+        let index: HashMap<_, _> =
+            nodes.iter().cloned().enumerate().map(|(node, key)| (key, node)).collect();
+
+        let mut edges = Vec::new();
+        for e in dom.mor_generators() {
+            let x = dom.mor_generator_dom(&e);
+            if data.is_attr_type(&x) {
+                continue;
+            }
+            let fe = functor
+                .apply_mor_generator(&e)
+                .expect("Functor should be defined on morphism generators");
+            let x2 = dom.mor_generator_cod(&e);
+            for (k, f) in homs.iter().enumerate().filter(|(_, f)| cod.cod(f) == ob_map(&x)) {
+                let f2 = cod.compose2(f.clone(), fe.clone());
+                let k2 = position(cod, homs, &f2)?.expect("Composite should be enumerated");
+                edges.push((index[&(x.clone(), k)], e.clone(), index[&(x2.clone(), k2)]));
+            }
+        }
+        // Attribute values must be determined by rows.
+        let determined: HashSet<_> = edges.iter().map(|(_, _, node)| *node).collect();
+        if (ntables..nodes.len()).any(|node| !determined.contains(&node)) {
+            return None;
+        }
+
+        let mut limit = Limit {
+            nodes,
+            index,
+            ntables,
+            edges,
+            families: Vec::new(),
+            family_index: HashMap::new(),
+            values: Vec::new(),
+        };
+        limit.search(data, &mut Vec::new());
+        limit.family_index = limit
+            .families
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, family)| (family, i))
+            .collect();
+        Some(limit)
+    }
+
+    /// Extends a partial family in all possible ways by backtracking.
+    fn search<C>(&mut self, data: &CSet<C, T>, family: &mut Vec<usize>)
+    where
+        C: PresentedCategory<Ob = Ob, MorGen = Gen>,
+    {
+        let node = family.len();
+        if node == self.ntables {
+            if let Some(values) = self.attr_values(data, family) {
+                self.families.push(family.clone());
+                self.values.push(values);
+            }
+            return;
+        }
+        let x = self.nodes[node].0.clone();
+        for i in data.parts(&x) {
+            family.push(i);
+            let consistent = self.edges.iter().all(|(src, e, tgt)| {
+                (*src).max(*tgt) != node
+                    || *tgt >= self.ntables
+                    || data.subpart(e, family[*src]) == Some(family[*tgt])
+            });
+            if consistent {
+                self.search(data, family);
+            }
+            family.pop();
+        }
+    }
+
+    /// Computes the attribute values determined by a family, if they agree.
+    fn attr_values<C>(&self, data: &CSet<C, T>, family: &[usize]) -> Option<Vec<T>>
+    where
+        C: PresentedCategory<Ob = Ob, MorGen = Gen>,
+    {
+        let mut values = vec![None; self.nodes.len() - self.ntables];
+        for (src, e, tgt) in self.edges.iter().filter(|(_, _, tgt)| *tgt >= self.ntables) {
+            let value = data.attr(e, family[*src])?;
+            let slot = &mut values[tgt - self.ntables];
+            if slot.get_or_insert_with(|| value.clone()) != &value {
+                return None;
+            }
+        }
+        values.into_iter().collect()
+    }
+
+    /// Finds a node at an attribute type over the morphism with given index.
+    fn attr_node<C>(&self, dom: &C, data: &CSet<C, T>, k: usize) -> Option<usize>
+    where
+        C: PresentedCategory<Ob = Ob, MorGen = Gen>,
+    {
+        dom.objects()
+            .filter(|x| data.is_attr_type(x))
+            .find_map(|x| self.index.get(&(x, k)).copied())
+    }
+}
+
+/// Collects the morphism generators out of each object of a category.
+fn out_generators<Cat>(cat: &Cat) -> HashMap<Cat::Ob, Vec<Cat::MorGen>>
+where
+    Cat: FgCategory,
+    Cat::Ob: Hash,
+{
+    let mut out_gens: HashMap<_, Vec<_>> = HashMap::new();
+    for e in cat.mor_generators() {
+        out_gens.entry(cat.mor_generator_dom(&e)).or_default().push(e);
+    }
+    out_gens
+}
+
+/// Collects the attributes out of each table of a C-set.
+fn attr_generators<C, T>(data: &CSet<C, T>) -> HashMap<C::Ob, Vec<C::MorGen>>
+where
+    C: PresentedCategory,
+    C::Ob: Hash,
+    C::MorGen: Hash,
+    T: Eq + Hash + Clone,
+{
+    let schema = data.schema();
+    let mut attr_gens: HashMap<_, Vec<_>> = HashMap::new();
+    for e in schema.mor_generators() {
+        if data.is_attr_type(&schema.mor_generator_cod(&e)) {
+            attr_gens.entry(schema.mor_generator_dom(&e)).or_default().push(e);
+        }
+    }
+    attr_gens
+}
+
+/** Enumerates the morphisms out of an object, up to equality.
+
+Morphisms into attribute types are included but not extended further. The
+identity comes first. Returns `None` if there are more than `max_mors`
+morphisms or if equality cannot be decided.
+ */
+fn homs_out<Cat>(
+    cat: &Cat,
+    out_gens: &HashMap<Cat::Ob, Vec<Cat::MorGen>>,
+    attr_types: &HashSet<Cat::Ob>,
+    x: Cat::Ob,
+    max_mors: usize,
+) -> Option<Vec<Cat::Mor>>
+where
+    Cat: PresentedCategory,
+    Cat::Ob: Hash,
+{
+    let mut homs = vec![cat.id(x)];
+    let mut next = 0;
+    while let Some(f) = homs.get(next).cloned() {
+        next += 1;
+        let y = cat.cod(&f);
+        if attr_types.contains(&y) {
+            continue;
+        }
+        for e in out_gens.get(&y).into_iter().flatten() {
+            let g = cat.compose2(f.clone(), e.clone().into());
+            if position(cat, &homs, &g)?.is_none() {
+                homs.push(g);
+                if homs.len() > max_mors {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(homs)
+}
+
+/// Finds the index of a morphism in a list, up to equality in the category.
+fn position<Cat: PresentedCategory>(
+    cat: &Cat,
+    homs: &[Cat::Mor],
+    f: &Cat::Mor,
+) -> Option<Option<usize>> {
+    let y = cat.cod(f);
+    for (k, g) in homs.iter().enumerate() {
+        if cat.cod(g) == y && cat.decide_eq(f, g)? {
+            return Some(Some(k));
+        }
+    }
+    Some(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::one::fin_category::FpCategory;
+    use crate::one::path::{Path, PathEq};
+    use crate::validate::Validate;
+
+    type Schema = FpCategory<char, char, &'static str>;
+    type SchemaFunctor = FinFunctor<char, char, char, Path<char, char>>;
+
+    fn sch_graph() -> Schema {
+        let mut sch: Schema = Default::default();
+        sch.add_ob_generators(['V', 'E']);
+        sch.add_mor_generator('s', 'E', 'V');
+        sch.add_mor_generator('t', 'E', 'V');
+        sch
+    }
+
+    fn sch_rgraph() -> Schema {
+        let mut sch = sch_graph();
+        sch.add_mor_generator('r', 'V', 'E');
+        sch.add_equation("src", PathEq::new(Path::pair('r', 's'), Path::empty('V')));
+        sch.add_equation("tgt", PathEq::new(Path::pair('r', 't'), Path::empty('V')));
+        sch
+    }
+
+    fn functor(obs: &[(char, char)], mors: &[(char, Path<char, char>)]) -> SchemaFunctor {
+        FinFunctor::new(obs.iter().cloned().collect(), mors.iter().cloned().collect())
+    }
+
+    fn graph(nv: usize, edges: &[(usize, usize)]) -> CSet<Schema, ()> {
+        let mut g = CSet::new(Arc::new(sch_graph()), []);
+        g.add_parts(&'V', nv);
+        for (v, w) in edges {
+            let e = g.add_part(&'E');
+            g.set_subpart(&'s', e, *v);
+            g.set_subpart(&'t', e, *w);
+        }
+        g
+    }
+
+    #[test]
+    fn delta_migration() {
+        // Split the edges of a graph into two kinds.
+        let mut sch = sch_graph();
+        sch.add_ob_generator('F');
+        sch.add_mor_generator('u', 'F', 'V');
+        sch.add_mor_generator('w', 'F', 'V');
+        let f = functor(
+            &[('V', 'V'), ('E', 'E'), ('F', 'E')],
+            &[
+                ('s', Path::single('s')),
+                ('t', Path::single('t')),
+                ('u', Path::single('s')),
+                ('w', Path::single('t')),
+            ],
+        );
+        let g = graph(2, &[(0, 1), (1, 0), (1, 1)]);
+        let h = delta(&f, Arc::new(sch), &g);
+        assert!(h.validate().is_ok());
+        assert_eq!((h.nparts(&'E'), h.nparts(&'F')), (3, 3));
+        assert_eq!((h.subpart(&'u', 0), h.subpart(&'w', 0)), (Some(0), Some(1)));
+
+        // The underlying graph of a reflexive graph.
+        let f = SchemaFunctor::identity(&sch_graph());
+        let mut g = CSet::<_, ()>::new(Arc::new(sch_rgraph()), []);
+        let v = g.add_part(&'V');
+        let e = g.add_part(&'E');
+        g.set_subpart(&'r', v, e);
+        g.set_subpart(&'s', e, v);
+        g.set_subpart(&'t', e, v);
+        let h = delta(&f, Arc::new(sch_graph()), &g);
+        assert!(h.validate().is_ok());
+        assert_eq!((h.nparts(&'V'), h.nparts(&'E')), (1, 1));
+    }
+
+    #[test]
+    fn sigma_migration() {
+        // Connected components of a graph.
+        let mut terminal: Schema = Default::default();
+        terminal.add_ob_generator('*');
+        let f =
+            functor(&[('V', '*'), ('E', '*')], &[('s', Path::empty('*')), ('t', Path::empty('*'))]);
+        let g = graph(5, &[(0, 1), (2, 1), (3, 3)]);
+        let h = sigma(&f, &g, Arc::new(terminal), 10).unwrap();
+        assert_eq!(h.nparts(&'*'), 3);
+
+        // Free reflexive graph on a graph.
+        let f = functor(
+            &[('V', 'V'), ('E', 'E')],
+            &[('s', Path::single('s')), ('t', Path::single('t'))],
+        );
+        let g = graph(2, &[(0, 1)]);
+        let h = sigma(&f, &g, Arc::new(sch_rgraph()), 10).unwrap();
+        assert!(h.validate().is_ok());
+        assert_eq!((h.nparts(&'V'), h.nparts(&'E')), (2, 3));
+
+        // Discrete graph on a set.
+        let mut sch_set: Schema = Default::default();
+        sch_set.add_ob_generator('V');
+        let mut set = CSet::<_, ()>::new(Arc::new(sch_set), []);
+        set.add_parts(&'V', 4);
+        let f = functor(&[('V', 'V')], &[]);
+        let h = sigma(&f, &set, Arc::new(sch_graph()), 10).unwrap();
+        assert!(h.validate().is_ok());
+        assert_eq!((h.nparts(&'V'), h.nparts(&'E')), (4, 0));
+
+        // Morphisms out of a vertex are unbounded in a free category.
+        let mut sch_loop: Schema = Default::default();
+        sch_loop.add_ob_generator('V');
+        sch_loop.add_mor_generator('n', 'V', 'V');
+        assert!(sigma(&f, &set, Arc::new(sch_loop), 10).is_none());
+    }
+
+    #[test]
+    fn pi_migration() {
+        // Loops in a graph.
+        let mut terminal: Schema = Default::default();
+        terminal.add_ob_generator('*');
+        let f =
+            functor(&[('V', '*'), ('E', '*')], &[('s', Path::empty('*')), ('t', Path::empty('*'))]);
+        let g = graph(3, &[(0, 1), (2, 2), (2, 2), (1, 1)]);
+        let h = pi(&f, &g, Arc::new(terminal), 10).unwrap();
+        assert_eq!(h.nparts(&'*'), 3);
+
+        // Codiscrete graph on a set.
+        let mut sch_set: Schema = Default::default();
+        sch_set.add_ob_generator('V');
+        let mut set = CSet::<_, ()>::new(Arc::new(sch_set), []);
+        set.add_parts(&'V', 3);
+        let f = functor(&[('V', 'V')], &[]);
+        let h = pi(&f, &set, Arc::new(sch_graph()), 10).unwrap();
+        assert!(h.validate().is_ok());
+        assert_eq!((h.nparts(&'V'), h.nparts(&'E')), (3, 9));
+        let pairs: HashSet<_> =
+            h.parts(&'E').map(|e| (h.subpart(&'s', e), h.subpart(&'t', e))).collect();
+        assert_eq!(pairs.len(), 9);
+    }
+
+    #[test]
+    fn migrate_attributes() {
+        let mut sch_x: Schema = Default::default();
+        sch_x.add_ob_generators(['X', 'A']);
+        sch_x.add_mor_generator('a', 'X', 'A');
+        let mut sch_y: Schema = Default::default();
+        sch_y.add_ob_generators(['Y', 'B']);
+        sch_y.add_mor_generator('b', 'Y', 'B');
+        let f = functor(&[('X', 'Y'), ('A', 'B')], &[('a', Path::single('b'))]);
+
+        let mut data = CSet::new(Arc::new(sch_x.clone()), ['A']);
+        for name in ["foo", "bar"] {
+            let i = data.add_part(&'X');
+            data.set_attr(&'a', i, name);
+        }
+        let sch_y = Arc::new(sch_y);
+        let renamed = sigma(&f, &data, sch_y.clone(), 10).unwrap();
+        assert!(renamed.validate().is_ok());
+        assert!(renamed.is_attr_type(&'B'));
+        assert_eq!(renamed.attr_incident(&'b', &"bar").len(), 1);
+
+        let renamed = pi(&f, &data, sch_y.clone(), 10).unwrap();
+        assert!(renamed.validate().is_ok());
+        assert_eq!(renamed.attr_incident(&'b', &"bar").len(), 1);
+
+        let restored = delta(&f, Arc::new(sch_x), &renamed);
+        assert!(restored.validate().is_ok());
+        assert_eq!(restored.attr_incident(&'a', &"bar").len(), 1);
+
+        // An attribute not in the image of the functor is unset by the left
+        // pushforward and is undetermined in the right pushforward.
+        let mut sch_y = sch_y.as_ref().clone();
+        sch_y.add_mor_generator('c', 'Y', 'B');
+        let sch_y = Arc::new(sch_y);
+        let renamed = sigma(&f, &data, sch_y.clone(), 10).unwrap();
+        assert_eq!(renamed.attr(&'c', 0), None);
+        assert!(pi(&f, &data, sch_y, 10).is_none());
+    }
+}
@@ -1,10 +1,17 @@
 //! Category theory in dimension one.
 
+pub mod canonical;
 pub mod category;
+pub mod cset;
 pub mod fin_category;
+pub mod fin_category_constructions;
+pub mod functor;
 pub mod graph;
 pub mod graph_algorithms;
+pub mod graph_limits;
+pub mod migration;
 pub mod path;
+pub mod rewriting;
 
 pub use self::category::*;
 pub use self::graph::*;
@@ -215,6 +215,44 @@ impl<V, E> Path<V, E> {
         Some(concatenated)
     }
 
+    /** Replaces a subpath of this path with a single edge.
+
+    The subpath is specified by a range of positions of edges in the path. An
+    empty range specifies the empty path at a vertex along the path, so that
+    edges can also be inserted. The function is called with the subpath and
+    returns the edge to replace it with, if there is one. Nothing is returned
+    if the range is out of bounds or the function returns nothing.
+
+    Like [`src`](Self::src) and [`tgt`](Self::tgt), this method assumes that the
+    path is contained in the graph.
+     */
+    pub fn splice_in(
+        self,
+        graph: &impl Graph<V = V, E = E>,
+        range: std::ops::Range<usize>,
+        f: impl FnOnce(Self) -> Option<E>,
+    ) -> Option<Self>
+    where
+        V: Clone,
+    {
+        if range.start > range.end || range.end > self.len() {
+            return None;
+        }
+        let x = match &self {
+            Path::Id(v) => v.clone(),
+            Path::Seq(edges) => match edges.get(range.start) {
+                Some(e) => graph.src(e),
+                None => graph.tgt(edges.last()),
+            },
+        };
+        let mut edges: Vec<_> = self.into_iter().collect();
+        let tail = edges.split_off(range.end);
+        let subpath = Path::from_vec(edges.split_off(range.start)).unwrap_or(Path::Id(x));
+        edges.push(f(subpath)?);
+        edges.extend(tail);
+        Path::from_vec(edges)
+    }
+
     /// Is the path contained in the given graph?
     pub fn contained_in(&self, graph: &impl Graph<V = V, E = E>) -> bool
     where
@@ -478,6 +516,20 @@ mod tests {
         assert!(!Path::pair(1, 0).contained_in(&g));
     }
 
+    #[test]
+    fn splice_path() {
+        let g = SkelGraph::triangle();
+        let path = Path::pair(0, 1);
+        let composite = |p: SkelPath| (p == Path::pair(0, 1)).then_some(2);
+        assert_eq!(path.clone().splice_in(&g, 0..2, composite), Some(Path::single(2)));
+        assert_eq!(path.clone().splice_in(&g, 0..1, composite), None);
+        assert_eq!(path.clone().splice_in(&g, 1..3, composite), None);
+
+        let unit = |p: SkelPath| (p == Path::Id(1)).then_some(3);
+        assert_eq!(path.clone().splice_in(&g, 1..1, unit), Some(Path::Seq(nonempty![0, 3, 1])));
+        assert_eq!(Path::Id(1).splice_in(&g, 0..0, unit), Some(Path::single(3)));
+    }
+
     #[test]
     fn singleton_path() {
         let e = 1;
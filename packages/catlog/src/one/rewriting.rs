@@ -0,0 +1,444 @@
+/*! Rewriting systems for solving word problems in finitely presented categories.
+
+A path equation in a finitely presented category can be oriented into a
+*rewriting rule*, which replaces occurrences of one side of the equation by the
+other. When the resulting rewriting system is *confluent* and terminating,
+every path has a unique normal form and two paths are equal in the presented
+category if and only if their normal forms coincide. The [Knuth–Bendix
+completion
+procedure](https://en.wikipedia.org/wiki/Knuth%E2%80%93Bendix_completion_algorithm)
+attempts to build such a system from a set of equations.
+
+Since the word problem for finitely presented categories is undecidable,
+completion need not terminate. We therefore bound the number of rules created
+and give up if the bound is exceeded.
+ */
+
+use nonempty::NonEmpty;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::graph::Graph;
+use super::path::*;
+
+/// A rewriting rule on nonempty paths, together with its source.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Rule<V, E> {
+    src: V,
+    lhs: Vec<E>,
+    rhs: Vec<E>,
+}
+
+/** A terminating rewriting system on paths in a graph.
+
+Rules are oriented according to the *shortlex* order: longer paths rewrite to
+shorter ones, and paths of equal length are compared lexicographically using
+the order on edges. Systems produced by [`RewriteSystem::complete`] are
+moreover confluent and interreduced.
+ */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RewriteSystem<V, E> {
+    rules: Vec<Rule<V, E>>,
+}
+
+impl<V, E> RewriteSystem<V, E>
+where
+    V: Eq + Clone,
+    E: Ord + Clone,
+{
+    /** Completes a set of path equations into a confluent rewriting system.
+
+    The equations are assumed to be valid in the given graph. Returns `None` if
+    completion creates more than `max_rules` rules without terminating.
+     */
+    pub fn complete<'a, G>(
+        graph: &G,
+        equations: impl IntoIterator<Item = &'a PathEq<V, E>>,
+        max_rules: usize,
+    ) -> Option<Self>
+    where
+        G: Graph<V = V, E = E>,
+        V: 'a,
+        E: 'a,
+    {
+        let pending = equations
+            .into_iter()
+            .map(|eq| (eq.src(graph), to_word(eq.lhs.clone()), to_word(eq.rhs.clone())))
+            .collect();
+        Self::complete_words(pending, max_rules)
+    }
+
+    /// Completes a set of equations between words, each with its source.
+    fn complete_words(mut pending: Vec<(V, Vec<E>, Vec<E>)>, max_rules: usize) -> Option<Self> {
+        let mut system = RewriteSystem { rules: Vec::new() };
+        let mut nrules = 0;
+        loop {
+            while let Some((src, lhs, rhs)) = pending.pop() {
+                let Some(rule) = system.orient(src, lhs, rhs) else {
+                    continue;
+                };
+                nrules += 1;
+                if nrules > max_rules {
+                    return None;
+                }
+                system.insert(rule, &mut pending);
+            }
+            pending = system.critical_pairs().collect();
+            if pending.is_empty() {
+                return Some(system);
+            }
+        }
+    }
+
+    /// Iterates over the rules of the system, oriented from left to right.
+    pub fn rules(&self) -> impl Iterator<Item = PathEq<V, E>> + '_ {
+        self.rules.iter().map(|rule| {
+            PathEq::new(
+                to_path(rule.src.clone(), rule.lhs.clone()),
+                to_path(rule.src.clone(), rule.rhs.clone()),
+            )
+        })
+    }
+
+    /// Number of rules in the system.
+    pub fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    /// Is the system empty, i.e., does it have no rules?
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Reduces a path to normal form by applying rules until none applies.
+    pub fn normalize(&self, path: Path<V, E>) -> Path<V, E> {
+        match path {
+            Path::Id(x) => Path::Id(x),
+            Path::Seq(edges) => {
+                let mut word: Vec<_> = edges.into();
+                let emptied_at = self.reduce(&mut word);
+                match NonEmpty::from_vec(word) {
+                    Some(edges) => Path::Seq(edges),
+                    None => Path::Id(emptied_at.expect("Path should be reduced by a rule")),
+                }
+            }
+        }
+    }
+
+    /// Is the path in normal form?
+    pub fn is_normal(&self, path: &Path<V, E>) -> bool {
+        match path {
+            Path::Id(_) => true,
+            Path::Seq(edges) => {
+                let word: Vec<_> = edges.iter().cloned().collect();
+                self.find_redex(&word).is_none()
+            }
+        }
+    }
+
+    /** Are the two paths equal modulo the rewriting system?
+
+    Only a decision procedure when the system is confluent.
+     */
+    pub fn is_equal(&self, f: &Path<V, E>, g: &Path<V, E>) -> bool {
+        self.normalize(f.clone()) == self.normalize(g.clone())
+    }
+
+    /// Reduces a word in place, returning the source of the rule that emptied
+    /// the word, if any.
+    fn reduce(&self, word: &mut Vec<E>) -> Option<V> {
+        let mut emptied_at = None;
+        while let Some((i, rule)) = self.find_redex(word) {
+            word.splice(i..(i + rule.lhs.len()), rule.rhs.iter().cloned());
+            if word.is_empty() {
+                emptied_at = Some(rule.src.clone());
+            }
+        }
+        emptied_at
+    }
+
+    /// Finds the first rule applicable to a word, along with its position.
+    fn find_redex(&self, word: &[E]) -> Option<(usize, &Rule<V, E>)> {
+        self.rules
+            .iter()
+            .find_map(|rule| find_subword(word, &rule.lhs).map(|i| (i, rule)))
+    }
+
+    /// Orients an equation into a rule, unless both sides have the same normal
+    /// form.
+    fn orient(&self, src: V, mut lhs: Vec<E>, mut rhs: Vec<E>) -> Option<Rule<V, E>> {
+        self.reduce(&mut lhs);
+        self.reduce(&mut rhs);
+        match (lhs.len(), &lhs).cmp(&(rhs.len(), &rhs)) {
+            std::cmp::Ordering::Equal => None,
+            std::cmp::Ordering::Greater => Some(Rule { src, lhs, rhs }),
+            std::cmp::Ordering::Less => Some(Rule {
+                src,
+                lhs: rhs,
+                rhs: lhs,
+            }),
+        }
+    }
+
+    /** Inserts a new rule, keeping the system interreduced.
+
+    Existing rules whose left-hand side is reducible by the new rule are
+    removed and returned to the pending equations, while right-hand sides are
+    brought into normal form.
+     */
+    fn insert(&mut self, rule: Rule<V, E>, pending: &mut Vec<(V, Vec<E>, Vec<E>)>) {
+        let (kept, removed) = std::mem::take(&mut self.rules)
+            .into_iter()
+            .partition(|old| find_subword(&old.lhs, &rule.lhs).is_none());
+        self.rules = kept;
+        pending.extend(removed.into_iter().map(|old: Rule<V, E>| (old.src, old.lhs, old.rhs)));
+        self.rules.push(rule);
+        for i in 0..self.rules.len() {
+            let mut rhs = self.rules[i].rhs.clone();
+            self.reduce(&mut rhs);
+            self.rules[i].rhs = rhs;
+        }
+    }
+
+    /** Iterates over critical pairs that are not joinable.
+
+    Because the system is interreduced, no left-hand side contains another, so
+    only proper overlaps between a suffix of one left-hand side and a prefix of
+    another need be considered.
+     */
+    fn critical_pairs(&self) -> impl Iterator<Item = (V, Vec<E>, Vec<E>)> + '_ {
+        let overlaps = self.rules.iter().flat_map(move |r1| {
+            self.rules.iter().flat_map(move |r2| {
+                let n = r1.lhs.len().min(r2.lhs.len());
+                (1..n).filter_map(move |k| {
+                    let (prefix, suffix) = r1.lhs.split_at(r1.lhs.len() - k);
+                    if suffix != &r2.lhs[..k] {
+                        return None;
+                    }
+                    let lhs = [&r1.rhs[..], &r2.lhs[k..]].concat();
+                    let rhs = [prefix, &r2.rhs[..]].concat();
+                    Some((r1.src.clone(), lhs, rhs))
+                })
+            })
+        });
+        overlaps
+            .filter(|(src, lhs, rhs)| self.orient(src.clone(), lhs.clone(), rhs.clone()).is_some())
+    }
+}
+
+/** A rewriting system on paths in a graph whose edges are not ordered.
+
+The shortlex order underlying a [`RewriteSystem`] needs a total order on edges.
+This wrapper orders the edges by their first occurrence in the equations being
+completed, so that equality of paths can be decided when the edges are merely
+hashable. Edges not occurring in any equation are never rewritten.
+ */
+#[derive(Clone, Debug)]
+pub struct IndexedRewriteSystem<V, E> {
+    system: RewriteSystem<V, usize>,
+    edges: Vec<E>,
+    index: HashMap<E, usize>,
+}
+
+impl<V, E> IndexedRewriteSystem<V, E>
+where
+    V: Eq + Clone,
+    E: Eq + Clone + Hash,
+{
+    /** Completes a set of path equations into a confluent rewriting system.
+
+    Returns `None` under the same conditions as [`RewriteSystem::complete`].
+     */
+    pub fn complete<'a, G>(
+        graph: &G,
+        equations: impl IntoIterator<Item = &'a PathEq<V, E>>,
+        max_rules: usize,
+    ) -> Option<Self>
+    where
+        G: Graph<V = V, E = E>,
+        V: 'a,
+        E: 'a,
+    {
+        let mut edges = Vec::new();
+        let mut index = HashMap::new();
+        let mut encode = |path: &Path<V, E>| -> Vec<usize> {
+            to_word(path.clone())
+                .into_iter()
+                .map(|e| {
+                    *index.entry(e).or_insert_with_key(|e| {
+                        edges.push(e.clone());
+                        edges.len() - 1
+                    })
+                })
+                .collect()
+        };
+        let pending = equations
+            .into_iter()
+            .map(|eq| (eq.src(graph), encode(&eq.lhs), encode(&eq.rhs)))
+            .collect();
+        let system = RewriteSystem::complete_words(pending, max_rules)?;
+        Some(IndexedRewriteSystem {
+            system,
+            edges,
+            index,
+        })
+    }
+
+    /// Reduces a path to normal form by applying rules until none applies.
+    pub fn normalize(&self, path: Path<V, E>) -> Path<V, E> {
+        let mut fresh = Vec::new();
+        let path = self.encode(path, &mut fresh);
+        self.system.normalize(path).map(|x| x, |i| self.decode(i, &fresh))
+    }
+
+    /// Is the path in normal form?
+    pub fn is_normal(&self, path: &Path<V, E>) -> bool {
+        self.system.is_normal(&self.encode(path.clone(), &mut Vec::new()))
+    }
+
+    /// Are the two paths equal modulo the rewriting system?
+    pub fn is_equal(&self, f: &Path<V, E>, g: &Path<V, E>) -> bool {
+        let mut fresh = Vec::new();
+        let f = self.encode(f.clone(), &mut fresh);
+        let g = self.encode(g.clone(), &mut fresh);
+        self.system.is_equal(&f, &g)
+    }
+
+    /// Encodes a path by edge indices, indexing unknown edges after the known
+    /// ones in order of occurrence.
+    fn encode(&self, path: Path<V, E>, fresh: &mut Vec<E>) -> Path<V, usize> {
+        path.map(
+            |x| x,
+            |e| match self.index.get(&e) {
+                Some(i) => *i,
+                None => {
+                    let i = fresh.iter().position(|f| *f == e).unwrap_or_else(|| {
+                        fresh.push(e);
+                        fresh.len() - 1
+                    });
+                    self.edges.len() + i
+                }
+            },
+        )
+    }
+
+    fn decode(&self, i: usize, fresh: &[E]) -> E {
+        match self.edges.get(i) {
+            Some(e) => e.clone(),
+            None => fresh[i - self.edges.len()].clone(),
+        }
+    }
+}
+
+fn find_subword<E: Eq>(word: &[E], sub: &[E]) -> Option<usize> {
+    if sub.len() > word.len() {
+        return None;
+    }
+    word.windows(sub.len()).position(|w| w == sub)
+}
+
+fn to_word<V, E>(path: Path<V, E>) -> Vec<E> {
+    match path {
+        Path::Id(_) => Vec::new(),
+        Path::Seq(edges) => edges.into(),
+    }
+}
+
+fn to_path<V, E>(src: V, word: Vec<E>) -> Path<V, E> {
+    match NonEmpty::from_vec(word) {
+        Some(edges) => Path::Seq(edges),
+        None => Path::Id(src),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::one::HashGraph;
+    use nonempty::nonempty;
+    use std::collections::HashSet;
+
+    #[test]
+    fn rewrite_sgraph() {
+        let mut graph: HashGraph<_, _> = Default::default();
+        graph.add_vertices(['V', 'E']);
+        graph.add_edge('s', 'E', 'V');
+        graph.add_edge('t', 'E', 'V');
+        graph.add_edge('i', 'E', 'E');
+        let eqs = [
+            PathEq::new(Path::pair('i', 'i'), Path::empty('E')),
+            PathEq::new(Path::pair('i', 's'), Path::single('t')),
+            PathEq::new(Path::pair('i', 't'), Path::single('s')),
+        ];
+        let system = RewriteSystem::complete(&graph, &eqs, 10).unwrap();
+        assert_eq!(system.len(), 3);
+        assert_eq!(system.normalize(Path::Seq(nonempty!['i', 'i', 'i', 's'])), Path::single('t'));
+        assert_eq!(system.normalize(Path::Seq(nonempty!['i', 'i'])), Path::Id('E'));
+        assert!(system.is_normal(&Path::single('i')));
+        assert!(!system.is_normal(&Path::pair('i', 's')));
+        assert!(system.is_equal(&Path::pair('i', 't'), &Path::Seq(nonempty!['i', 'i', 's'])));
+        assert!(!system.is_equal(&Path::single('s'), &Path::single('t')));
+    }
+
+    #[test]
+    fn rewrite_unordered_edges() {
+        // Edges that can be compared for equality but not ordered.
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        struct Edge(char);
+
+        let mut graph: HashGraph<_, _> = Default::default();
+        graph.add_vertices(['V', 'E']);
+        graph.add_edge(Edge('s'), 'E', 'V');
+        graph.add_edge(Edge('t'), 'E', 'V');
+        graph.add_edge(Edge('i'), 'E', 'E');
+        graph.add_edge(Edge('j'), 'E', 'E');
+        let (i, s, t) = (Edge('i'), Edge('s'), Edge('t'));
+        let eqs = [
+            PathEq::new(Path::pair(i.clone(), i.clone()), Path::empty('E')),
+            PathEq::new(Path::pair(i.clone(), s.clone()), Path::single(t.clone())),
+            PathEq::new(Path::pair(i.clone(), t.clone()), Path::single(s.clone())),
+        ];
+        let system = IndexedRewriteSystem::complete(&graph, &eqs, 10).unwrap();
+        let path = Path::Seq(nonempty![i.clone(), i.clone(), i.clone(), s.clone()]);
+        assert!(system.is_equal(&path, &Path::single(t.clone())));
+        assert!(!system.is_equal(&Path::single(s.clone()), &Path::single(t.clone())));
+
+        // Edges not occurring in the equations are left alone.
+        let path = Path::Seq(nonempty![Edge('j'), i.clone(), i.clone(), s.clone()]);
+        assert_eq!(system.normalize(path), Path::pair(Edge('j'), s.clone()));
+        assert!(system.is_normal(&Path::pair(Edge('j'), t)));
+        assert!(!system.is_normal(&Path::pair(i.clone(), i)));
+    }
+
+    #[test]
+    fn complete_alternating_group() {
+        // The alternating group A_4 as a one-object category presented by
+        // a^2 = b^3 = (ab)^3 = 1, which requires completion to be confluent.
+        let mut graph: HashGraph<_, _> = Default::default();
+        graph.add_vertex('x');
+        graph.add_edge('a', 'x', 'x');
+        graph.add_edge('b', 'x', 'x');
+        let eqs = [
+            PathEq::new(Path::pair('a', 'a'), Path::empty('x')),
+            PathEq::new(Path::Seq(nonempty!['b', 'b', 'b']), Path::empty('x')),
+            PathEq::new(Path::Seq(nonempty!['a', 'b', 'a', 'b', 'a', 'b']), Path::empty('x')),
+        ];
+        let system = RewriteSystem::complete(&graph, &eqs, 100).unwrap();
+        assert!(system.len() > eqs.len());
+
+        let mut words = vec![Vec::new()];
+        for _ in 0..8 {
+            let longer: Vec<_> = words
+                .iter()
+                .filter(|w: &&Vec<char>| w.len() == words.last().unwrap().len())
+                .flat_map(|w| ['a', 'b'].map(|e| [&w[..], &[e]].concat()))
+                .collect();
+            words.extend(longer);
+        }
+        let normal_forms: HashSet<_> =
+            words.into_iter().map(|w| system.normalize(to_path('x', w))).collect();
+        assert_eq!(normal_forms.len(), 12);
+
+        assert!(RewriteSystem::complete(&graph, &eqs, 2).is_none());
+    }
+}
@@ -121,6 +121,35 @@ pub fn backward_link(th: Arc<UstrDiscreteTabTheory>) -> UstrDiscreteTabModel {
     model
 }
 
+/** The SIR model as a Petri net.
+
+A free model of the [theory of symmetric monoidal
+categories](super::theories::th_symmetric_monoidal_category) with places for
+susceptible, infected, and recovered individuals and with transitions for
+infection and recovery. The inputs and outputs of a transition are symmetric
+lists of places, so their order does not matter.
+ */
+pub fn sir_petri(th: Arc<UstrModalDblTheory>) -> UstrModalDblModel {
+    let mut model = UstrModalDblModel::new(th.clone());
+    let ob_type = ModeApp::new(ustr("Object"));
+    let (s, i, r) = (ustr("S"), ustr("I"), ustr("R"));
+    model.add_ob(s, ob_type.clone());
+    model.add_ob(i, ob_type.clone());
+    model.add_ob(r, ob_type.clone());
+    let tensor = |obs: Vec<Ustr>| {
+        let obs = obs.into_iter().map(ModalOb::Basic);
+        ModalOb::list(Modality::SymList, ob_type.clone(), obs).app(ustr("tensor"))
+    };
+    model.add_mor(
+        ustr("infection"),
+        tensor(vec![s, i]),
+        tensor(vec![i, i]),
+        th.hom_type(ob_type.clone()),
+    );
+    model.add_mor(ustr("recovery"), tensor(vec![i]), tensor(vec![r]), th.hom_type(ob_type));
+    model
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::theories::*;
@@ -156,4 +185,10 @@ mod tests {
         let th = Arc::new(th_category_links());
         assert!(backward_link(th).validate().is_ok());
     }
+
+    #[test]
+    fn petri_nets() {
+        let th = Arc::new(th_symmetric_monoidal_category());
+        assert!(sir_petri(th).validate().is_ok());
+    }
 }
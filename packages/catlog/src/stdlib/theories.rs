@@ -4,6 +4,7 @@ use ustr::ustr;
 
 use crate::dbl::theory::*;
 use crate::one::fin_category::{FinMor, UstrFinCategory};
+use crate::one::fin_category_constructions::{WithZero, adjoin_zero, product};
 
 /** The empty theory, which has a single model, the empty model.
 
@@ -44,55 +45,43 @@ signed categories are signed graphs, a simple mathematical model of [regulatory
 networks](crate::refs::RegNets) and causal loop diagrams.
  */
 pub fn th_signed_category() -> UstrDiscreteDblTheory {
-    let mut sgn: UstrFinCategory = Default::default();
-    let (x, n) = (ustr("Object"), ustr("Negative"));
-    sgn.add_ob_generator(x);
-    sgn.add_mor_generator(n, x, x);
-    sgn.set_composite(n, n, FinMor::Id(x));
-    DiscreteDblTheory::from(sgn)
+    DiscreteDblTheory::from(signs())
 }
 
 /** The theory of delayable signed categories.
 
 Free delayable signed categories are causal loop diagrams with delays, often
-depicted as [caesuras](https://en.wikipedia.org/wiki/Caesura).
+depicted as [caesuras](https://en.wikipedia.org/wiki/Caesura). The monoid of
+signs with delays is the product of the group of signs with the monoid of delays
+`{fast, slow}`, in which slow is idempotent.
  */
 pub fn th_delayable_signed_category() -> UstrDiscreteDblTheory {
-    let mut cat: UstrFinCategory = Default::default();
-    let (x, neg) = (ustr("Object"), ustr("Negative"));
-    let (pos_slow, neg_slow) = (ustr("PositiveSlow"), ustr("NegativeSlow"));
-    cat.add_ob_generator(x);
-    cat.add_mor_generator(neg, x, x);
-    cat.add_mor_generator(pos_slow, x, x);
-    cat.add_mor_generator(neg_slow, x, x);
-    cat.set_composite(neg, neg, FinMor::Id(x));
-    cat.set_composite(neg, pos_slow, FinMor::Generator(neg_slow));
-    cat.set_composite(neg, neg_slow, FinMor::Generator(pos_slow));
-    cat.set_composite(pos_slow, neg, FinMor::Generator(neg_slow));
-    cat.set_composite(neg_slow, neg, FinMor::Generator(pos_slow));
-    cat.set_composite(pos_slow, pos_slow, FinMor::Generator(pos_slow));
-    cat.set_composite(neg_slow, neg_slow, FinMor::Generator(pos_slow));
-    cat.set_composite(neg_slow, pos_slow, FinMor::Generator(neg_slow));
-    cat.set_composite(pos_slow, neg_slow, FinMor::Generator(neg_slow));
+    let prod = product(&signs(), &delays());
+    let cat = prod.apex().relabel(
+        |_| ustr("Object"),
+        |(sign, delay)| match (sign, delay) {
+            (FinMor::Generator(_), FinMor::Id(_)) => ustr("Negative"),
+            (FinMor::Id(_), _) => ustr("PositiveSlow"),
+            (FinMor::Generator(_), FinMor::Generator(_)) => ustr("NegativeSlow"),
+        },
+    );
     DiscreteDblTheory::from(cat)
 }
 
 /** The theory of nullable signed categories.
 
 A *nullable signed category* is a category sliced over the monoid of signs,
-including zero.
+including zero, which is obtained from the group of signs by adjoining a zero.
  */
 pub fn th_nullable_signed_category() -> UstrDiscreteDblTheory {
-    let mut sgn: UstrFinCategory = Default::default();
-    let (x, n, z) = (ustr("Object"), ustr("Negative"), ustr("Zero"));
-    sgn.add_ob_generator(x);
-    sgn.add_mor_generator(n, x, x);
-    sgn.add_mor_generator(z, x, x);
-    sgn.set_composite(n, n, FinMor::Id(x));
-    sgn.set_composite(z, z, FinMor::Generator(z));
-    sgn.set_composite(n, z, FinMor::Generator(z));
-    sgn.set_composite(z, n, FinMor::Generator(z));
-    DiscreteDblTheory::from(sgn)
+    let cat = adjoin_zero(&signs()).apex().relabel(
+        |x| *x,
+        |e| match e {
+            WithZero::Generator(e) => *e,
+            WithZero::Zero(_, _) => ustr("Zero"),
+        },
+    );
+    DiscreteDblTheory::from(cat)
 }
 
 /** The theory of categories with scalars.
@@ -134,6 +123,70 @@ pub fn th_category_links() -> UstrDiscreteTabTheory {
     th
 }
 
+/** The theory of categories with an endofunctor.
+
+As a double category, this is the free category on an endomorphism, regarded as
+a double category with only identity proarrows and cells.
+ */
+pub fn th_category_with_endofunctor() -> UstrSimpleDblTheory {
+    let mut th: UstrSimpleDblTheory = Default::default();
+    let x = ustr("Object");
+    th.add_ob_type(x);
+    th.add_ob_op(ustr("Endofunctor"), x, x);
+    th
+}
+
+/** The theory of monoidal categories.
+
+The tensor product is an object operation out of the list modality. The
+associativity and unit laws are not yet part of the theory.
+ */
+pub fn th_monoidal_category() -> UstrModalDblTheory {
+    monoidal_category_theory(Modality::List)
+}
+
+/** The theory of symmetric monoidal categories.
+
+Tensors are taken along the symmetric list modality, so that tensor products of
+lists that are permutations of each other are equal. As in the [theory of
+monoidal categories](th_monoidal_category), none of the laws are part of the
+theory, and there are no symmetry morphisms. Free models of this theory include [Petri
+nets](crate::stdlib::models::sir_petri), whose transitions are morphisms between
+tensor products of lists of places.
+ */
+pub fn th_symmetric_monoidal_category() -> UstrModalDblTheory {
+    monoidal_category_theory(Modality::SymList)
+}
+
+/// Theory of monoidal categories where tensors are taken along a list modality.
+fn monoidal_category_theory(modality: Modality) -> UstrModalDblTheory {
+    let mut th: UstrModalDblTheory = Default::default();
+    let x = ustr("Object");
+    th.add_ob_type(x);
+    th.add_ob_op(ustr("tensor"), ModeApp::new(x).apply(modality), ModeApp::new(x));
+    th
+}
+
+/// The group of nonzero signs, as a category with one object.
+fn signs() -> UstrFinCategory {
+    let mut sgn: UstrFinCategory = Default::default();
+    let (x, n) = (ustr("Object"), ustr("Negative"));
+    sgn.add_ob_generator(x);
+    sgn.add_mor_generator(n, x, x);
+    sgn.set_composite(n, n, FinMor::Id(x));
+    sgn
+}
+
+/// The monoid of delays, where fast is the identity and slow is idempotent.
+fn delays() -> UstrFinCategory {
+    let mut delays: UstrFinCategory = Default::default();
+    let (x, slow) = (ustr("Object"), ustr("Slow"));
+    delays.add_ob_generator(x);
+    delays.add_mor_generator(slow, x, x);
+    delays.set_composite(slow, slow, FinMor::Generator(slow));
+    delays
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,7 +201,9 @@ mod tests {
         assert!(th_delayable_signed_category().validate().is_ok());
         assert!(th_nullable_signed_category().validate().is_ok());
         assert!(th_category_with_scalars().validate().is_ok());
-        // TODO: Validate discrete tabulator theories.
-        th_category_links();
+        assert!(th_category_links().validate().is_ok());
+        assert!(th_category_with_endofunctor().validate().is_ok());
+        assert!(th_monoidal_category().validate().is_ok());
+        assert!(th_symmetric_monoidal_category().validate().is_ok());
     }
 }
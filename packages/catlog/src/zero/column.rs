@@ -254,6 +254,16 @@ pub struct HashColumn<K, V, S = RandomState>(HashMap<K, V, S>);
 /// An unindexed column with keys of type `Ustr`.
 pub type UstrColumn<V> = HashColumn<Ustr, V, BuildHasherDefault<IdentityHasher>>;
 
+impl<K, V, S> FromIterator<(K, V)> for HashColumn<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = (K, V)>>(iter: T) -> Self {
+        Self(HashMap::from_iter(iter))
+    }
+}
+
 impl<K, V, S> Mapping for HashColumn<K, V, S>
 where
     K: Eq + Hash + Clone,
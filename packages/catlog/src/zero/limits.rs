@@ -0,0 +1,484 @@
+/*! Finite limits and colimits of finite sets.
+
+The limits computed here are products, pullbacks, and equalizers, and the
+colimits are coproducts, pushouts, and coequalizers. In each case, the apex of
+the (co)limit is a [skeletal finite set](SkelFinSet), regardless of the kind of
+sets in the diagram. The legs of a limit are [vector-backed
+columns](VecColumn) out of the apex, while the legs of a colimit are [hash
+columns](HashColumn) into the apex. Each (co)limit also knows how to compute the
+map induced by its universal property.
+
+The mappings in the diagram are assumed to be defined on the whole of their
+domain sets. Wherever that is not true, the mappings are treated as relations
+and elements at which they are undefined are simply ignored.
+ */
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::column::*;
+use super::set::*;
+
+/** A limit of finite sets with two legs.
+
+Products and pullbacks are both limits of this kind: the product of `X` and `Y`
+is the pullback of `X` and `Y` over the one-point set.
+ */
+#[derive(Clone, Debug)]
+pub struct LimitSpan<X, Y> {
+    apex: SkelFinSet,
+    left: VecColumn<X>,
+    right: VecColumn<Y>,
+    index: HashMap<(X, Y), usize>,
+}
+
+impl<X, Y> LimitSpan<X, Y>
+where
+    X: Eq + Clone + Hash,
+    Y: Eq + Clone + Hash,
+{
+    /// Gets the apex of the limit.
+    pub fn apex(&self) -> &SkelFinSet {
+        &self.apex
+    }
+
+    /// Gets the left leg of the limit.
+    pub fn left(&self) -> &VecColumn<X> {
+        &self.left
+    }
+
+    /// Gets the right leg of the limit.
+    pub fn right(&self) -> &VecColumn<Y> {
+        &self.right
+    }
+
+    /** Computes the map into the limit induced by a cone over the diagram.
+
+    The cone has apex `dom` and legs `f` and `g`. Returns `None` if the cone
+    does not commute with the diagram.
+     */
+    pub fn universal<W, F, G>(&self, dom: &W, f: &F, g: &G) -> Option<HashColumn<W::Elem, usize>>
+    where
+        W: FinSet,
+        W::Elem: Hash,
+        F: Mapping<Dom = W::Elem, Cod = X>,
+        G: Mapping<Dom = W::Elem, Cod = Y>,
+    {
+        self.universal_by(dom.iter(), |w| f.apply(w), |w| g.apply(w))
+    }
+
+    pub(crate) fn universal_by<W: Eq + Hash>(
+        &self,
+        dom: impl Iterator<Item = W>,
+        f: impl Fn(&W) -> Option<X>,
+        g: impl Fn(&W) -> Option<Y>,
+    ) -> Option<HashColumn<W, usize>> {
+        dom.map(|w| {
+            let i = *self.index.get(&(f(&w)?, g(&w)?))?;
+            Some((w, i))
+        })
+        .collect()
+    }
+
+    /// Looks up the element of the apex projecting to a given pair.
+    pub(crate) fn get(&self, x: X, y: Y) -> Option<usize> {
+        self.index.get(&(x, y)).copied()
+    }
+}
+
+/** A colimit of finite sets with two legs.
+
+Coproducts and pushouts are both colimits of this kind: the coproduct of `X`
+and `Y` is the pushout of `X` and `Y` under the empty set.
+ */
+#[derive(Clone, Debug)]
+pub struct ColimitCospan<X, Y> {
+    apex: SkelFinSet,
+    left: HashColumn<X, usize>,
+    right: HashColumn<Y, usize>,
+}
+
+impl<X, Y> ColimitCospan<X, Y>
+where
+    X: Eq + Clone + Hash,
+    Y: Eq + Clone + Hash,
+{
+    /// Gets the apex of the colimit.
+    pub fn apex(&self) -> &SkelFinSet {
+        &self.apex
+    }
+
+    /// Gets the left leg of the colimit.
+    pub fn left(&self) -> &HashColumn<X, usize> {
+        &self.left
+    }
+
+    /// Gets the right leg of the colimit.
+    pub fn right(&self) -> &HashColumn<Y, usize> {
+        &self.right
+    }
+
+    /** Computes the map out of the colimit induced by a cocone under the diagram.
+
+    The cocone has legs `f` and `g` with a common codomain. Returns `None` if
+    the cocone does not commute with the diagram.
+     */
+    pub fn universal<T, F, G>(&self, f: &F, g: &G) -> Option<VecColumn<T>>
+    where
+        T: Eq + Clone,
+        F: Mapping<Dom = X, Cod = T>,
+        G: Mapping<Dom = Y, Cod = T>,
+    {
+        self.universal_by(|x| f.apply(x), |y| g.apply(y))
+    }
+
+    pub(crate) fn universal_by<T: Eq + Clone>(
+        &self,
+        f: impl Fn(&X) -> Option<T>,
+        g: impl Fn(&Y) -> Option<T>,
+    ) -> Option<VecColumn<T>> {
+        let mut result = VecColumn::default();
+        let left = self.left.iter().map(|(x, i)| (*i, f(&x)));
+        let right = self.right.iter().map(|(y, i)| (*i, g(&y)));
+        for (i, value) in left.chain(right) {
+            let value = value?;
+            if result.get(&i).is_some_and(|other| *other != value) {
+                return None;
+            }
+            result.set(i, value);
+        }
+        Some(result)
+    }
+}
+
+/// An equalizer of two parallel maps between finite sets.
+#[derive(Clone, Debug)]
+pub struct Equalizer<X> {
+    apex: SkelFinSet,
+    incl: VecColumn<X>,
+    index: HashMap<X, usize>,
+}
+
+impl<X: Eq + Clone + Hash> Equalizer<X> {
+    /// Gets the apex of the equalizer.
+    pub fn apex(&self) -> &SkelFinSet {
+        &self.apex
+    }
+
+    /// Gets the inclusion of the equalizer into the domain of the maps.
+    pub fn incl(&self) -> &VecColumn<X> {
+        &self.incl
+    }
+
+    /** Computes the map into the equalizer induced by a map that equalizes.
+
+    Returns `None` if the map `h` does not in fact equalize the maps.
+     */
+    pub fn universal<W, H>(&self, dom: &W, h: &H) -> Option<HashColumn<W::Elem, usize>>
+    where
+        W: FinSet,
+        W::Elem: Hash,
+        H: Mapping<Dom = W::Elem, Cod = X>,
+    {
+        self.universal_by(dom.iter(), |w| h.apply(w))
+    }
+
+    pub(crate) fn universal_by<W: Eq + Hash>(
+        &self,
+        dom: impl Iterator<Item = W>,
+        h: impl Fn(&W) -> Option<X>,
+    ) -> Option<HashColumn<W, usize>> {
+        dom.map(|w| {
+            let i = *self.index.get(&h(&w)?)?;
+            Some((w, i))
+        })
+        .collect()
+    }
+
+    /// Looks up the element of the apex included as a given element.
+    pub(crate) fn get(&self, x: &X) -> Option<usize> {
+        self.index.get(x).copied()
+    }
+}
+
+/// A coequalizer of two parallel maps between finite sets.
+#[derive(Clone, Debug)]
+pub struct Coequalizer<X> {
+    apex: SkelFinSet,
+    proj: HashColumn<X, usize>,
+}
+
+impl<X: Eq + Clone + Hash> Coequalizer<X> {
+    /// Gets the apex of the coequalizer.
+    pub fn apex(&self) -> &SkelFinSet {
+        &self.apex
+    }
+
+    /// Gets the projection from the codomain of the maps onto the coequalizer.
+    pub fn proj(&self) -> &HashColumn<X, usize> {
+        &self.proj
+    }
+
+    /** Computes the map out of the coequalizer induced by a map that coequalizes.
+
+    Returns `None` if the map `h` does not in fact coequalize the maps.
+     */
+    pub fn universal<T, H>(&self, h: &H) -> Option<VecColumn<T>>
+    where
+        T: Eq + Clone,
+        H: Mapping<Dom = X, Cod = T>,
+    {
+        self.universal_by(|x| h.apply(x))
+    }
+
+    pub(crate) fn universal_by<T: Eq + Clone>(
+        &self,
+        h: impl Fn(&X) -> Option<T>,
+    ) -> Option<VecColumn<T>> {
+        let mut result = VecColumn::default();
+        for (x, i) in self.proj.iter() {
+            let value = h(&x)?;
+            if result.get(i).is_some_and(|other| *other != value) {
+                return None;
+            }
+            result.set(*i, value);
+        }
+        Some(result)
+    }
+}
+
+/// Computes the product of two finite sets.
+pub fn product<X, Y>(x: &X, y: &Y) -> LimitSpan<X::Elem, Y::Elem>
+where
+    X: FinSet,
+    X::Elem: Hash,
+    Y: FinSet,
+    Y::Elem: Hash,
+{
+    pullback_by(x.iter(), y.iter(), |_| Some(()), |_| Some(()))
+}
+
+/// Computes the pullback of two maps `f: X → Z` and `g: Y → Z`.
+pub fn pullback<X, Y, F, G>(x: &X, y: &Y, f: &F, g: &G) -> LimitSpan<X::Elem, Y::Elem>
+where
+    X: FinSet,
+    X::Elem: Hash,
+    Y: FinSet,
+    Y::Elem: Hash,
+    F: Mapping<Dom = X::Elem>,
+    F::Cod: Hash,
+    G: Mapping<Dom = Y::Elem, Cod = F::Cod>,
+{
+    pullback_by(x.iter(), y.iter(), |x| f.apply(x), |y| g.apply(y))
+}
+
+/// Computes the equalizer of two parallel maps `f, g: X → Y`.
+pub fn equalizer<X, F, G>(x: &X, f: &F, g: &G) -> Equalizer<X::Elem>
+where
+    X: FinSet,
+    X::Elem: Hash,
+    F: Mapping<Dom = X::Elem>,
+    G: Mapping<Dom = X::Elem, Cod = F::Cod>,
+{
+    equalizer_by(x.iter(), |x| f.apply(x), |x| g.apply(x))
+}
+
+/// Computes the coproduct, or disjoint union, of two finite sets.
+pub fn coproduct<X, Y>(x: &X, y: &Y) -> ColimitCospan<X::Elem, Y::Elem>
+where
+    X: FinSet,
+    X::Elem: Hash,
+    Y: FinSet,
+    Y::Elem: Hash,
+{
+    pushout_by(x.iter(), y.iter(), std::iter::empty::<()>(), |_| None, |_| None)
+}
+
+/// Computes the pushout of two maps `f: Z → X` and `g: Z → Y`.
+pub fn pushout<X, Y, Z, F, G>(x: &X, y: &Y, z: &Z, f: &F, g: &G) -> ColimitCospan<X::Elem, Y::Elem>
+where
+    X: FinSet,
+    X::Elem: Hash,
+    Y: FinSet,
+    Y::Elem: Hash,
+    Z: FinSet,
+    F: Mapping<Dom = Z::Elem, Cod = X::Elem>,
+    G: Mapping<Dom = Z::Elem, Cod = Y::Elem>,
+{
+    pushout_by(x.iter(), y.iter(), z.iter(), |z| f.apply(z), |z| g.apply(z))
+}
+
+/// Computes the coequalizer of two parallel maps `f, g: X → Y`.
+pub fn coequalizer<X, Y, F, G>(x: &X, y: &Y, f: &F, g: &G) -> Coequalizer<Y::Elem>
+where
+    X: FinSet,
+    Y: FinSet,
+    Y::Elem: Hash,
+    F: Mapping<Dom = X::Elem, Cod = Y::Elem>,
+    G: Mapping<Dom = X::Elem, Cod = Y::Elem>,
+{
+    coequalizer_by(x.iter(), y.iter(), |x| f.apply(x), |x| g.apply(x))
+}
+
+pub(crate) fn pullback_by<X, Y, Z>(
+    x: impl Iterator<Item = X>,
+    y: impl Iterator<Item = Y>,
+    f: impl Fn(&X) -> Option<Z>,
+    g: impl Fn(&Y) -> Option<Z>,
+) -> LimitSpan<X, Y>
+where
+    X: Eq + Clone + Hash,
+    Y: Eq + Clone + Hash,
+    Z: Eq + Hash,
+{
+    let mut fibers: HashMap<Z, Vec<Y>> = HashMap::new();
+    for y in y {
+        if let Some(z) = g(&y) {
+            fibers.entry(z).or_default().push(y);
+        }
+    }
+    let (mut left, mut right) = (Vec::new(), Vec::new());
+    for x in x {
+        for y in f(&x).and_then(|z| fibers.get(&z)).into_iter().flatten() {
+            left.push(x.clone());
+            right.push(y.clone());
+        }
+    }
+    let index = left.iter().cloned().zip(right.iter().cloned()).enumerate();
+    LimitSpan {
+        apex: SkelFinSet::from(left.len()),
+        index: index.map(|(i, pair)| (pair, i)).collect(),
+        left: VecColumn::new(left),
+        right: VecColumn::new(right),
+    }
+}
+
+pub(crate) fn equalizer_by<X, Y>(
+    x: impl Iterator<Item = X>,
+    f: impl Fn(&X) -> Option<Y>,
+    g: impl Fn(&X) -> Option<Y>,
+) -> Equalizer<X>
+where
+    X: Eq + Clone + Hash,
+    Y: Eq,
+{
+    let incl: Vec<_> = x.filter(|x| f(x).is_some_and(|y| g(x) == Some(y))).collect();
+    Equalizer {
+        apex: SkelFinSet::from(incl.len()),
+        index: incl.iter().cloned().enumerate().map(|(i, x)| (x, i)).collect(),
+        incl: VecColumn::new(incl),
+    }
+}
+
+pub(crate) fn pushout_by<X, Y, Z>(
+    x: impl Iterator<Item = X>,
+    y: impl Iterator<Item = Y>,
+    z: impl Iterator<Item = Z>,
+    f: impl Fn(&Z) -> Option<X>,
+    g: impl Fn(&Z) -> Option<Y>,
+) -> ColimitCospan<X, Y>
+where
+    X: Eq + Clone + Hash,
+    Y: Eq + Clone + Hash,
+{
+    let x_index: HashMap<_, _> = x.enumerate().map(|(i, x)| (x, i)).collect();
+    let y_index: HashMap<_, _> = y.enumerate().map(|(j, y)| (y, x_index.len() + j)).collect();
+    let mut classes = UnionFind::new(x_index.len() + y_index.len());
+    for z in z {
+        if let (Some(i), Some(j)) =
+            (f(&z).and_then(|x| x_index.get(&x)), g(&z).and_then(|y| y_index.get(&y)))
+        {
+            classes.union(*i, *j);
+        }
+    }
+    let (apex, proj) = classes.quotient();
+    ColimitCospan {
+        apex,
+        left: x_index.into_iter().map(|(x, i)| (x, proj.apply(&i).unwrap())).collect(),
+        right: y_index.into_iter().map(|(y, j)| (y, proj.apply(&j).unwrap())).collect(),
+    }
+}
+
+pub(crate) fn coequalizer_by<X, Y>(
+    x: impl Iterator<Item = X>,
+    y: impl Iterator<Item = Y>,
+    f: impl Fn(&X) -> Option<Y>,
+    g: impl Fn(&X) -> Option<Y>,
+) -> Coequalizer<Y>
+where
+    Y: Eq + Clone + Hash,
+{
+    let index: HashMap<_, _> = y.enumerate().map(|(i, y)| (y, i)).collect();
+    let mut classes = UnionFind::new(index.len());
+    for x in x {
+        if let (Some(i), Some(j)) =
+            (f(&x).and_then(|y| index.get(&y)), g(&x).and_then(|y| index.get(&y)))
+        {
+            classes.union(*i, *j);
+        }
+    }
+    let (apex, proj) = classes.quotient();
+    Coequalizer {
+        apex,
+        proj: index.into_iter().map(|(y, i)| (y, proj.apply(&i).unwrap())).collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn limits() {
+        let (x, y) = (SkelFinSet::from(3), HashFinSet::from(HashSet::from(['a', 'b'])));
+        let prod = product(&x, &y);
+        assert_eq!(prod.apex().len(), 6);
+        let (f, g) = (VecColumn::new(vec![2, 0]), VecColumn::new(vec!['b', 'a']));
+        let pair = prod.universal(&SkelFinSet::from(2), &f, &g).unwrap();
+        assert_eq!(prod.left().apply(&pair.apply(&0).unwrap()), Some(2));
+        assert_eq!(prod.right().apply(&pair.apply(&1).unwrap()), Some('a'));
+
+        // Pullback of parity maps.
+        let (x, y) = (SkelFinSet::from(4), SkelFinSet::from(3));
+        let f = VecColumn::new(vec![0, 1, 0, 1]);
+        let g = VecColumn::new(vec![1, 1, 0]);
+        let pb = pullback(&x, &y, &f, &g);
+        assert_eq!(pb.apex().len(), 6);
+        let h = VecColumn::new(vec![1]);
+        assert!(pb.universal(&SkelFinSet::from(1), &h, &VecColumn::new(vec![0])).is_some());
+        assert!(pb.universal(&SkelFinSet::from(1), &h, &VecColumn::new(vec![2])).is_none());
+
+        let eq = equalizer(&x, &f, &VecColumn::new(vec![0, 0, 0, 0]));
+        assert_eq!(eq.incl(), &VecColumn::new(vec![0, 2]));
+        let incl = eq.universal(&SkelFinSet::from(1), &VecColumn::new(vec![2])).unwrap();
+        assert_eq!(incl.apply(&0), Some(1));
+        assert!(eq.universal(&SkelFinSet::from(1), &VecColumn::new(vec![1])).is_none());
+    }
+
+    #[test]
+    fn colimits() {
+        let (x, y) = (SkelFinSet::from(2), HashFinSet::from(HashSet::from(['a', 'b', 'c'])));
+        let coprod = coproduct(&x, &y);
+        assert_eq!(coprod.apex().len(), 5);
+        let g: HashColumn<_, _> = [('a', false), ('b', false), ('c', true)].into_iter().collect();
+        let copair = coprod.universal(&VecColumn::new(vec![true, true]), &g).unwrap();
+        assert_eq!(copair.apply(&coprod.right().apply(&'c').unwrap()), Some(true));
+
+        // Glue two paths at their endpoints.
+        let (x, y, z) = (SkelFinSet::from(3), SkelFinSet::from(2), SkelFinSet::from(2));
+        let f = VecColumn::new(vec![0, 2]);
+        let g = VecColumn::new(vec![0, 1]);
+        let po = pushout(&x, &y, &z, &f, &g);
+        assert_eq!(po.apex().len(), 3);
+        assert_eq!(po.left().apply(&2), po.right().apply(&1));
+        let h = VecColumn::new(vec!['p', 'q', 'r']);
+        assert!(po.universal(&h, &VecColumn::new(vec!['p', 'r'])).is_some());
+        assert!(po.universal(&h, &VecColumn::new(vec!['p', 'q'])).is_none());
+
+        let coeq = coequalizer(&z, &x, &f, &VecColumn::new(vec![1, 2]));
+        assert_eq!(coeq.apex().len(), 2);
+        assert!(coeq.universal(&VecColumn::new(vec![7, 7, 8])).is_some());
+        assert!(coeq.universal(&VecColumn::new(vec![7, 8, 8])).is_none());
+    }
+}
@@ -4,7 +4,7 @@ This module provides interfaces and simple wrapper types to enable sets to be
 treated in a generic way.
  */
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::hash::{BuildHasher, BuildHasherDefault, Hash, RandomState};
 use std::ops::Range;
 
@@ -13,6 +13,8 @@ use derive_more::{From, Into};
 use ref_cast::RefCast;
 use ustr::{IdentityHasher, Ustr};
 
+use super::column::VecColumn;
+
 /** A set.
 
 The interface is minimal. A set has an element type ([`Elem`](Self::Elem)) and
@@ -246,6 +248,101 @@ impl<T> FinSet for AttributedSkelSet<T> {
     }
 }
 
+/** A union-find data structure on a skeletal finite set.
+
+Also known as a [disjoint-set
+forest](https://en.wikipedia.org/wiki/Disjoint-set_data_structure), this data
+structure maintains an equivalence relation on the set `0..n` that can be made
+coarser by merging equivalence classes. It is the basic tool for computing
+quotients of finite sets, such as coequalizers and pushouts.
+ */
+#[derive(Clone, Debug, Default)]
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+}
+
+impl UnionFind {
+    /// Creates a union-find structure in which each element of `0..n` is in its
+    /// own equivalence class.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            ranks: vec![0; n],
+        }
+    }
+
+    /// Adds a new element in its own equivalence class.
+    pub fn insert(&mut self) -> usize {
+        let new = self.parents.len();
+        self.parents.push(new);
+        self.ranks.push(0);
+        new
+    }
+
+    /// Number of elements, not of equivalence classes.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// Is the underlying set empty?
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Finds the representative of the equivalence class of an element.
+    pub fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    /** Merges the equivalence classes of two elements.
+
+    Returns whether the classes were distinct before merging.
+     */
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let (x, y) = (self.find(x), self.find(y));
+        if x == y {
+            return false;
+        }
+        let (x, y) = if self.ranks[x] < self.ranks[y] {
+            (x, y)
+        } else {
+            (y, x)
+        };
+        self.parents[x] = y;
+        if self.ranks[x] == self.ranks[y] {
+            self.ranks[y] += 1;
+        }
+        true
+    }
+
+    /// Are the two elements in the same equivalence class?
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    /** Computes the quotient by the equivalence relation.
+
+    Returns the set of equivalence classes, as a skeletal finite set, together
+    with the projection onto it. Classes are numbered in order of their least
+    elements.
+     */
+    pub fn quotient(&mut self) -> (SkelFinSet, VecColumn<usize>) {
+        let mut classes: HashMap<usize, usize> = HashMap::new();
+        let projection = (0..self.len())
+            .map(|x| {
+                let n = classes.len();
+                *classes.entry(self.find(x)).or_insert(n)
+            })
+            .collect();
+        (SkelFinSet::from(classes.len()), VecColumn::new(projection))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -300,4 +397,21 @@ mod tests {
         assert!(!s.contains(&3));
         assert_eq!(*s.view(1), 'b');
     }
+
+    #[test]
+    fn union_find() {
+        let mut uf = UnionFind::new(4);
+        assert_eq!(uf.insert(), 4);
+        assert_eq!(uf.len(), 5);
+        assert!(uf.union(0, 2));
+        assert!(uf.union(3, 4));
+        assert!(uf.union(2, 4));
+        assert!(!uf.union(0, 3));
+        assert!(uf.same(0, 4));
+        assert!(!uf.same(1, 2));
+
+        let (classes, projection) = uf.quotient();
+        assert_eq!(classes.len(), 2);
+        assert_eq!(projection, VecColumn::new(vec![0, 1, 0, 0, 0]));
+    }
 }
@@ -2,7 +2,7 @@ import ChevronLeft from "lucide-solid/icons/chevron-left";
 import ChevronRight from "lucide-solid/icons/chevron-right";
 import { Show, createMemo } from "solid-js";
 
-import type { DblModel, MotifsOptions } from "catlog-wasm";
+import type { DblModel, Motifs, MotifsOptions } from "catlog-wasm";
 import type { ModelAnalysisProps } from "../../analysis";
 import { Foldable, FormGroup, IconButton, InputField } from "../../components";
 
@@ -11,7 +11,13 @@ import { ModelGraphviz } from "./model_graph";
 
 import "./submodel_graphs.css";
 
-type FindSubmodelsFn = (model: DblModel, options: MotifsOptions) => DblModel[];
+type FindSubmodelsFn = (model: DblModel, options: MotifsOptions) => Motifs;
+
+/** Maximum number of distinct submodels to search for. */
+const MAX_RESULTS = 1000;
+
+/** Time limit on the search for submodels, in milliseconds. */
+const TIME_LIMIT = 2000;
 
 /** Configuration and state of a submodels analysis. */
 export type SubmodelsAnalysisContent = {
@@ -50,19 +56,28 @@ function SubmodelsAnalysis(
         title?: string;
     } & ModelAnalysisProps<SubmodelsAnalysisContent>,
 ) {
-    const submodels = createMemo<DblModel[]>(
+    const motifs = createMemo<{ models: DblModel[]; truncated: boolean }>(
         () => {
             const validated = props.liveModel.validatedModel();
             if (validated?.result.tag !== "Ok") {
-                return [];
+                return { models: [], truncated: false };
             }
-            return props.findSubmodels(validated.model, {
+            const result = props.findSubmodels(validated.model, {
                 maxPathLength: props.content.maxPathLength ?? null,
+                maxResults: MAX_RESULTS,
+                timeLimit: TIME_LIMIT,
             });
+            // The result owns memory in Wasm, which must be freed explicitly.
+            try {
+                return { models: result.models, truncated: result.truncated };
+            } finally {
+                result.free();
+            }
         },
-        [],
+        { models: [], truncated: false },
         { equals: false },
     );
+    const submodels = () => motifs().models;
 
     const index = () => props.content.activeIndex;
     const setIndex = (index: number) =>
@@ -97,6 +112,7 @@ function SubmodelsAnalysis(
                 {(length) => (
                     <span>
                         {index() + 1} / {length()}
+                        {motifs().truncated ? "+" : ""}
                     </span>
                 )}
             </Show>